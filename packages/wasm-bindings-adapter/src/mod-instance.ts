@@ -7,6 +7,9 @@ export interface ParseAndRenderOptions {
   block_extension_list: Extension[];
   inline_extension_list: Extension[];
   should_include_block_ids: boolean;
+  should_render_code_blocks_as_pre_code?: boolean;
+  should_include_outline?: boolean;
+  should_include_statistics?: boolean;
 }
 
 export interface TagNameMap {
@@ -14,6 +17,7 @@ export interface TagNameMap {
   inline_call_error: string;
   code_block: string;
   ref_link: string;
+  external_link: string;
   dicexp: string;
   wiki_link: string;
 }
@@ -40,6 +44,9 @@ export type Extension =
       >;
     };
   }
+  | { FootnoteReference: { name: string } }
+  | { FootnoteList: { name: string } }
+  | { TableOfContents: { name: string } }
   | { Alias: { name: string; to: string } };
 
 export type ParameterWrapper<T> = { Real: T } | { Alias: string };
@@ -47,9 +54,62 @@ export type ParameterWrapper<T> = { Real: T } | { Alias: string };
 export interface ParseAndRenderResult {
   html: string;
   block_id_to_lines_map: Record<number, [number, number]>;
+  block_id_to_positions_map: Record<number, [Position, Position]>;
+  /** 仅在 `should_include_outline` 为真时存在。 */
+  outline?: OutlineItem[];
+  /** 仅在 `should_include_statistics` 为真时存在。 */
+  statistics?: Statistics;
+  diagnostics: Diagnostic[];
   dev_events_in_debug_format?: string;
 }
 
+export interface OutlineItem {
+  level: number;
+  text: string;
+  id: string;
+  block_id: number;
+  lines: [number, number];
+  children: OutlineItem[];
+}
+
+export interface Statistics {
+  document: StatisticsItem;
+  blocks: Record<number, StatisticsItem>;
+}
+
+export interface StatisticsItem {
+  characters: number;
+  cjk_characters: number;
+  latin_words: number;
+  links: number;
+  calls: number;
+  tables: number;
+  code_blocks: number;
+}
+
+export interface Diagnostic {
+  code:
+    | "UnclosedVerbatimEscaping"
+    | "UnclosedComment"
+    | "UnclosedInlineElement"
+    | "UnclosedBlockCall"
+    | "UnclosedTable"
+    | "WikiLinkOpeningAsText"
+    | "BlockCallOpeningAsText";
+  range: [number, number];
+  line: number;
+  position: Position;
+}
+
+/**
+ * 行与列均从 1 开始计。`utf16_column` 以 UTF-16 码元计，可直接用于 JavaScript 字符串。
+ */
+export interface Position {
+  line: number;
+  column: number;
+  utf16_column: number;
+}
+
 export function parseAndRender(
   input: string,
   opts: ParseAndRenderOptions,
@@ -4,8 +4,13 @@ import { bindings, setBindings } from "./bindings";
 import * as modInstance from "./mod-instance";
 
 export type {
+  Diagnostic,
   Extension,
+  OutlineItem,
   ParseAndRenderResult,
+  Position,
+  Statistics,
+  StatisticsItem,
   TagNameMap,
 } from "./mod-instance";
 
@@ -10,20 +10,29 @@ fn compile_and_execute(input: &[u8], events: &[rotext::Event]) {
     // 由于 `new_demo_instance_for_test` 位于 `test` 特性旗帜之后，这里会误报错误。
     let tag_name_map = rotext::TagNameMap::new_demo_instance_for_test();
 
+    // 由于 `new_demo_*_extension_map_for_test` 位于 `test` 特性旗帜之后，这里会误报错误。
+    let block_extension_map =
+        rotext::executing::extensions::new_demo_block_extension_map_for_test();
+    let inline_extension_map =
+        rotext::executing::extensions::new_demo_inline_extension_map_for_test();
+
     let compile_opts = rotext::CompileOption {
         restrictions: rotext::CompileRestrictions {
             max_call_depth_in_document: 100,
         },
+        block_extension_map: &block_extension_map,
+        inline_extension_map: &inline_extension_map,
     };
     let compiled = rotext::compile(input, events, &compile_opts).unwrap();
 
     let exec_opts = rotext::ExecuteOptions {
         tag_name_map: &tag_name_map,
-        // 由于 `new_demo_block_extension_map_for_test` 位于 `test` 特性旗帜之后，这里会误报错误。
-        block_extension_map: &rotext::executing::extensions::new_demo_block_extension_map_for_test(
-        ),
-        inline_extension_map:
-            &rotext::executing::extensions::new_demo_inline_extension_map_for_test(),
+        block_extension_map: &block_extension_map,
+        inline_extension_map: &inline_extension_map,
+        template_provider: None,
+        compile_options: &compile_opts,
+        code_block_rendering: rotext::CodeBlockRendering::CustomElement,
+        footnote_id_prefix: b"",
         #[cfg(feature = "block-id")]
         should_include_block_ids: true,
     };
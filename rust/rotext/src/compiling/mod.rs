@@ -1,6 +1,11 @@
-use std::ops::Range;
+use std::{collections::HashMap, ops::Range};
 
-use rotext_core::{BlockId, Event, events::Call};
+use rotext_core::{
+    BlockId, Event,
+    events::{AttachedInfo, AttachedInfoTarget, Call},
+};
+
+use crate::executing::extensions::Extension;
 
 pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug)]
@@ -22,16 +27,43 @@ pub enum CompiledItem<'a> {
     BlockExtension(CompiledItemCall<'a>),
     InlineTransclusion(CompiledItemCall<'a>),
     InlineExtension(CompiledItemCall<'a>),
+    BlockParameterFilling(CompiledItemParameterFilling<'a>),
+    InlineParameterFilling(CompiledItemParameterFilling<'a>),
+    /// 编译时收集到的对旁注的引用。
+    FootnoteReference(CompiledItemFootnoteReference<'a>),
 }
 
 pub struct CompiledItemCall<'a> {
     pub name: &'a [u8],
     pub arguments: Vec<(ArgumentKey<'a>, Vec<CompiledItem<'a>>)>,
     pub verbatim_arguments: Vec<(ArgumentKey<'a>, Vec<u8>)>,
+    /// 附加到调用之上的整体附加信息。
+    pub attached_infos: Vec<AttachedInfo>,
 
     pub block_id: Option<BlockId>,
 }
 
+pub struct CompiledItemParameterFilling<'a> {
+    /// 参数的名称。
+    pub name: &'a [u8],
+    /// 没有对应参数时填充的内容。
+    pub fallback: Option<Vec<CompiledItem<'a>>>,
+
+    pub block_id: Option<BlockId>,
+}
+
+pub struct CompiledItemFootnoteReference<'a> {
+    /// 所调用的扩展的名称。
+    pub name: &'a [u8],
+    /// 具名旁注的名称。匿名旁注为 `None`。
+    pub footnote_name: Option<Vec<u8>>,
+    /// 旁注的内容。对于具名旁注，只有对其的第一处引用带有内容，内容来自对应的旁注内容扩
+    /// 展；没有对应的内容时为 `None`。
+    pub content: Option<Vec<CompiledItem<'a>>>,
+    /// 附加到调用之上的整体附加信息。
+    pub attached_infos: Vec<AttachedInfo>,
+}
+
 #[derive(Eq, Hash, PartialEq)]
 pub enum ArgumentKey<'a> {
     Named(&'a [u8]),
@@ -47,39 +79,84 @@ impl ArgumentKey<'_> {
             }
         }
     }
+
+    /// 判断本参数是否对应于参数填充中名为 `name` 的参数。参数名称大小写不敏感。
+    pub fn matches(&self, name: &[u8]) -> bool {
+        match self {
+            ArgumentKey::Named(key) => {
+                match (std::str::from_utf8(key), std::str::from_utf8(name)) {
+                    (Ok(key), Ok(name)) => key
+                        .chars()
+                        .flat_map(char::to_lowercase)
+                        .eq(name.chars().flat_map(char::to_lowercase)),
+                    _ => key.eq_ignore_ascii_case(name),
+                }
+            }
+            ArgumentKey::Unnamed(_) => self.to_vec() == name,
+        }
+    }
 }
 
-pub struct NewCompileOptions {
+pub struct NewCompileOptions<'a> {
     pub restrictions: Restrictions,
+    /// 用于在编译时识别旁注相关的扩展，应与执行时使用的一致。
+    pub block_extension_map: &'a HashMap<&'a [u8], Extension<'a>>,
+    pub inline_extension_map: &'a HashMap<&'a [u8], Extension<'a>>,
 }
 
 pub struct Restrictions {
-    /// 单份文档中最多允许的调用（包括最外层的）的嵌套数量。
+    /// 最多允许的调用（包括最外层的）的嵌套数量。
+    ///
+    /// 被嵌入包含的模板中的调用也会计入，即该限制是跨文档生效的。
     pub max_call_depth_in_document: usize,
 }
 
+/// 具名旁注的名称到其内容的映射。
+type FootnoteDefinitions<'a> = HashMap<Vec<u8>, Vec<CompiledItem<'a>>>;
+
 pub struct Compiler<'a> {
     restrictions: &'a Restrictions,
+    block_extension_map: &'a HashMap<&'a [u8], Extension<'a>>,
+    inline_extension_map: &'a HashMap<&'a [u8], Extension<'a>>,
 }
 
 impl<'a> Compiler<'a> {
-    pub fn new(opts: &'a NewCompileOptions) -> Self {
+    pub fn new(opts: &'a NewCompileOptions<'a>) -> Self {
         Self {
             restrictions: &opts.restrictions,
+            block_extension_map: opts.block_extension_map,
+            inline_extension_map: opts.inline_extension_map,
         }
     }
 
     pub fn compile(&self, input: &'a [u8], evs: &[Event]) -> Result<Vec<CompiledItem<'a>>> {
-        let (_, result) = self.compile_internal(1, input, evs, 0)?;
+        self.compile_at_depth(input, evs, 1)
+    }
+
+    /// 以 `depth` 作为最外层的嵌套深度进行编译。用于编译被嵌入包含的模板，以使嵌套深度的
+    /// 限制能跨越文档生效。
+    pub(crate) fn compile_at_depth(
+        &self,
+        input: &'a [u8],
+        evs: &[Event],
+        depth: usize,
+    ) -> Result<Vec<CompiledItem<'a>>> {
+        let mut footnote_definitions = FootnoteDefinitions::new();
+        let (_, mut result) =
+            self.compile_internal(depth, input, evs, 0, &mut footnote_definitions)?;
+        fill_named_footnotes(&mut result, &mut footnote_definitions);
         Ok(result)
     }
 
+    /// 对旁注内容扩展的调用中，以 `名称` 逐字参数定义具名旁注的内容的那些会被收集到
+    /// `footnote_definitions` 中，不再留在编译结果里。
     fn compile_internal(
         &self,
         depth: usize,
         input: &'a [u8],
         evs: &[Event],
         mut i: usize,
+        footnote_definitions: &mut FootnoteDefinitions<'a>,
     ) -> Result<(usize, Vec<CompiledItem<'a>>)> {
         if depth > self.restrictions.max_call_depth_in_document {
             return Err(Error::RecursionDepthExceeded);
@@ -117,7 +194,7 @@ impl<'a> Compiler<'a> {
             // 实际上并不存在问题。
             match &evs[i] {
                 Event::ExitBlock(_)
-                | Event::ExitInline
+                | Event::ExitInline(_)
                 | Event::IndicateCallNormalArgument(_)
                 | Event::IndicateCallVerbatimArgument(_)
                     if stack_depth == 0 =>
@@ -125,14 +202,51 @@ impl<'a> Compiler<'a> {
                     push_simple_events(&mut result, &mut last_simple_evs);
                     return Ok((i, result));
                 }
-                Event::IndicateCallNormalArgument(_) | Event::IndicateCallVerbatimArgument(_) => {
+                Event::IndicateCallNormalArgument(_)
+                | Event::IndicateCallVerbatimArgument(_)
+                | Event::IndicateParameterFillingFallback(_) => {
                     unreachable!()
                 }
+                Event::EnterParameterFilling(filling) => {
+                    push_simple_events(&mut result, &mut last_simple_evs);
+
+                    i += 1;
+                    let fallback = if matches!(evs[i], Event::IndicateParameterFillingFallback(_)) {
+                        let value: Vec<CompiledItem>;
+                        (i, value) =
+                            self.compile_internal(depth, input, evs, i + 1, footnote_definitions)?;
+                        Some(value)
+                    } else {
+                        None
+                    };
+                    i += 1;
+
+                    result.push(match filling {
+                        Call::Block { id, name, .. } => {
+                            debug_assert!(matches!(evs[i - 1], Event::ExitBlock(_)));
+                            CompiledItem::BlockParameterFilling(CompiledItemParameterFilling {
+                                name: &input[name.clone()],
+                                fallback,
+                                block_id: Some(*id),
+                            })
+                        }
+                        Call::Inline { name, .. } => {
+                            debug_assert!(matches!(evs[i - 1], Event::ExitInline(_)));
+                            CompiledItem::InlineParameterFilling(CompiledItemParameterFilling {
+                                name: &input[name.clone()],
+                                fallback,
+                                block_id: None,
+                            })
+                        }
+                    });
+                }
                 Event::EnterCallOnTemplate(call) | Event::EnterCallOnExtension(call) => {
                     let is_transclusion = matches!(evs[i], Event::EnterCallOnTemplate(_));
 
                     push_simple_events(&mut result, &mut last_simple_evs);
 
+                    let attached_infos = collect_preceding_attached_infos(&evs[..i]);
+
                     let mut arguments = Vec::new();
                     let mut verbatim_arguments = Vec::new();
 
@@ -143,7 +257,7 @@ impl<'a> Compiler<'a> {
                     loop {
                         match &evs[i] {
                             Event::ExitBlock(_) => {
-                                let Call::Block { id, name } = call else {
+                                let Call::Block { id, name, .. } = call else {
                                     unreachable!()
                                 };
 
@@ -151,19 +265,31 @@ impl<'a> Compiler<'a> {
                                     name: &input[name.clone()],
                                     arguments,
                                     verbatim_arguments,
+                                    attached_infos,
                                     block_id: Some(*id),
                                 };
 
-                                result.push(if is_transclusion {
-                                    CompiledItem::BlockTransclusion(call_compiled)
+                                if is_transclusion {
+                                    result.push(CompiledItem::BlockTransclusion(call_compiled));
                                 } else {
-                                    CompiledItem::BlockExtension(call_compiled)
-                                });
+                                    match self.as_footnote_definition(call_compiled) {
+                                        Ok((footnote_name, content)) => {
+                                            // 重复定义时以第一处为准。
+                                            footnote_definitions
+                                                .entry(footnote_name)
+                                                .or_insert(content);
+                                        }
+                                        Err(call_compiled) => {
+                                            result
+                                                .push(CompiledItem::BlockExtension(call_compiled));
+                                        }
+                                    }
+                                }
                                 i += 1;
                                 break;
                             }
-                            Event::ExitInline => {
-                                let Call::Inline { name } = call else {
+                            Event::ExitInline(_) => {
+                                let Call::Inline { name, .. } = call else {
                                     unreachable!()
                                 };
 
@@ -171,31 +297,43 @@ impl<'a> Compiler<'a> {
                                     name: &input[name.clone()],
                                     arguments,
                                     verbatim_arguments,
+                                    attached_infos,
                                     block_id: None,
                                 };
 
                                 result.push(if is_transclusion {
                                     CompiledItem::InlineTransclusion(call_compiled)
                                 } else {
-                                    CompiledItem::InlineExtension(call_compiled)
+                                    match self.as_footnote_reference(call_compiled) {
+                                        Ok(reference) => CompiledItem::FootnoteReference(reference),
+                                        Err(call_compiled) => {
+                                            CompiledItem::InlineExtension(call_compiled)
+                                        }
+                                    }
                                 });
                                 i += 1;
                                 break;
                             }
-                            Event::IndicateCallNormalArgument(arg_name) => {
-                                let arg_name = if let Some(arg_name) = arg_name {
+                            Event::IndicateCallNormalArgument(argument) => {
+                                let arg_name = if let Some(arg_name) = &argument.name {
                                     ArgumentKey::Named(&input[arg_name.clone()])
                                 } else {
                                     ArgumentKey::Unnamed(unnamed_arg_name_gen.next())
                                 };
 
                                 let value: Vec<CompiledItem>;
-                                (i, value) = self.compile_internal(depth + 1, input, evs, i + 1)?;
+                                (i, value) = self.compile_internal(
+                                    depth + 1,
+                                    input,
+                                    evs,
+                                    i + 1,
+                                    footnote_definitions,
+                                )?;
 
                                 arguments.push((arg_name, value));
                             }
-                            Event::IndicateCallVerbatimArgument(arg_name) => {
-                                let arg_name = if let Some(arg_name) = arg_name {
+                            Event::IndicateCallVerbatimArgument(argument) => {
+                                let arg_name = if let Some(arg_name) = &argument.name {
                                     ArgumentKey::Named(&input[arg_name.clone()])
                                 } else {
                                     ArgumentKey::Unnamed(unnamed_verbatim_arg_name_gen.next())
@@ -222,7 +360,7 @@ impl<'a> Compiler<'a> {
                         }
                     }
                 }
-                Event::ExitBlock(_) | Event::ExitInline => {
+                Event::ExitBlock(_) | Event::ExitInline(_) => {
                     stack_depth -= 1;
                     advance_simple_events(&mut last_simple_evs, &mut i);
                 }
@@ -242,12 +380,14 @@ impl<'a> Compiler<'a> {
                 | Event::EnterDescriptionDetails(_)
                 | Event::EnterCodeBlock(_)
                 | Event::EnterTable(_)
-                | Event::EnterCodeSpan
-                | Event::EnterEmphasis
-                | Event::EnterStrong
-                | Event::EnterStrikethrough
-                | Event::EnterRuby
-                | Event::EnterRubyText
+                | Event::EnterCodeSpan(_)
+                | Event::EnterEmphasis(_)
+                | Event::EnterStrong(_)
+                | Event::EnterStrikethrough(_)
+                | Event::EnterRuby(_)
+                | Event::EnterRubyText(_)
+                | Event::EnterDicexp(_)
+                | Event::EnterAssigningDicexp(_)
                 | Event::EnterWikiLink(_) => {
                     stack_depth += 1;
                     advance_simple_events(&mut last_simple_evs, &mut i);
@@ -258,15 +398,151 @@ impl<'a> Compiler<'a> {
                 | Event::VerbatimEscaping(_)
                 | Event::ThematicBreak(_)
                 | Event::RefLink(_)
-                | Event::Dicexp(_)
-                | Event::IndicateTableRow
-                | Event::IndicateTableCaption
-                | Event::IndicateTableHeaderCell
-                | Event::IndicateTableDataCell
-                | Event::IndicateCodeBlockCode => {
+                | Event::ExternalLink(_)
+                | Event::IndicateTableRow(_)
+                | Event::IndicateTableCaption(_)
+                | Event::IndicateTableHeaderCell(_)
+                | Event::IndicateTableDataCell(_)
+                | Event::IndicateCodeBlockCode(_)
+                | Event::BlockAttachedInfo(_)
+                | Event::InlineAttachedInfo(_) => {
                     advance_simple_events(&mut last_simple_evs, &mut i);
                 }
             }
         }
     }
+
+    /// 若 `call` 是参数正确的对旁注扩展的调用，将其转换为对旁注的引用，否则原样返回。
+    ///
+    /// 旁注扩展要么只有参数 `1`（作为匿名旁注的内容），要么只有逐字参数 `名称`（引用具名
+    /// 旁注）。
+    fn as_footnote_reference(
+        &self,
+        call: CompiledItemCall<'a>,
+    ) -> std::result::Result<CompiledItemFootnoteReference<'a>, CompiledItemCall<'a>> {
+        if !matches!(
+            resolve_extension(self.inline_extension_map, call.name),
+            Some(Extension::FootnoteReference)
+        ) {
+            return Err(call);
+        }
+
+        let CompiledItemCall {
+            name,
+            mut arguments,
+            mut verbatim_arguments,
+            attached_infos,
+            ..
+        } = call;
+        let (footnote_name, content) = match (&arguments[..], &verbatim_arguments[..]) {
+            ([(key, _)], []) if key.matches(b"1") => (None, arguments.pop().map(|(_, v)| v)),
+            ([], [(key, _)]) if is_footnote_name_key(key) => {
+                let (_, value) = verbatim_arguments.pop().unwrap();
+                (Some(value.trim_ascii().to_vec()), None)
+            }
+            _ => {
+                return Err(CompiledItemCall {
+                    name,
+                    arguments,
+                    verbatim_arguments,
+                    attached_infos,
+                    block_id: None,
+                });
+            }
+        };
+
+        Ok(CompiledItemFootnoteReference {
+            name,
+            footnote_name,
+            content,
+            attached_infos,
+        })
+    }
+
+    /// 若 `call` 是同时只有逐字参数 `名称` 与参数 `1` 的对旁注内容扩展的调用，返回其定义的具
+    /// 名旁注的名称与内容，否则原样返回。
+    fn as_footnote_definition(
+        &self,
+        mut call: CompiledItemCall<'a>,
+    ) -> std::result::Result<(Vec<u8>, Vec<CompiledItem<'a>>), CompiledItemCall<'a>> {
+        if !matches!(
+            resolve_extension(self.block_extension_map, call.name),
+            Some(Extension::FootnoteList)
+        ) {
+            return Err(call);
+        }
+
+        match (&call.arguments[..], &call.verbatim_arguments[..]) {
+            ([(key, _)], [(verbatim_key, _)])
+                if key.matches(b"1") && is_footnote_name_key(verbatim_key) =>
+            {
+                let (_, content) = call.arguments.pop().unwrap();
+                let (_, footnote_name) = call.verbatim_arguments.pop().unwrap();
+                Ok((footnote_name.trim_ascii().to_vec(), content))
+            }
+            _ => Err(call),
+        }
+    }
+}
+
+fn resolve_extension<'m>(
+    map: &'m HashMap<&[u8], Extension<'m>>,
+    name: &[u8],
+) -> Option<&'m Extension<'m>> {
+    match map.get(name)? {
+        Extension::Alias { to } => map.get(to),
+        ext => Some(ext),
+    }
+}
+
+fn is_footnote_name_key(key: &ArgumentKey) -> bool {
+    key.matches(b"name") || key.matches("名称".as_bytes())
+}
+
+/// 将具名旁注的内容移至按文档顺序对该旁注的第一处引用之上。
+fn fill_named_footnotes<'a>(
+    items: &mut [CompiledItem<'a>],
+    footnote_definitions: &mut FootnoteDefinitions<'a>,
+) {
+    for item in items {
+        match item {
+            CompiledItem::SimpleEvents(_) => {}
+            CompiledItem::BlockTransclusion(call)
+            | CompiledItem::BlockExtension(call)
+            | CompiledItem::InlineTransclusion(call)
+            | CompiledItem::InlineExtension(call) => {
+                for (_, value) in &mut call.arguments {
+                    fill_named_footnotes(value, footnote_definitions);
+                }
+            }
+            CompiledItem::BlockParameterFilling(filling)
+            | CompiledItem::InlineParameterFilling(filling) => {
+                if let Some(fallback) = &mut filling.fallback {
+                    fill_named_footnotes(fallback, footnote_definitions);
+                }
+            }
+            CompiledItem::FootnoteReference(reference) => {
+                if let (Some(footnote_name), None) = (&reference.footnote_name, &reference.content)
+                {
+                    reference.content = footnote_definitions.remove(footnote_name);
+                }
+                if let Some(content) = &mut reference.content {
+                    fill_named_footnotes(content, footnote_definitions);
+                }
+            }
+        }
+    }
+}
+
+/// 收集位于 `evs` 末尾的连续的整体附加信息。
+fn collect_preceding_attached_infos(evs: &[Event]) -> Vec<AttachedInfo> {
+    let mut infos: Vec<AttachedInfo> = evs
+        .iter()
+        .rev()
+        .map_while(crate::utils::as_attached_info)
+        .take_while(|info| info.target == AttachedInfoTarget::Following)
+        .cloned()
+        .collect();
+    infos.reverse();
+    infos
 }
@@ -0,0 +1,325 @@
+//! 处理事件序列的辅助工具。
+
+use std::ops::Range;
+
+use rotext_core::{
+    BlockId, Event,
+    events::{AttachedInfo, BlockWithId, Call, ExitBlock, ListItem, OrderedList, ThematicBreak},
+};
+
+/// 一个顶层块级元素，包括位于其前的、附加于其上的块级附加信息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TopLevelBlock {
+    /// 其事件在事件列表中的位置。
+    pub(crate) events: Range<usize>,
+    /// 其所在的范围。
+    pub(crate) source: Range<usize>,
+}
+
+/// 逐个接收事件，在顶层块级元素结束时将其返回。
+pub(crate) struct TopLevelBlockSplitter {
+    depth: usize,
+    /// 当前尚未结束的顶层块级元素的第一个事件的位置，以及其开始处。
+    pending: Option<(usize, usize)>,
+}
+
+impl TopLevelBlockSplitter {
+    pub(crate) fn new() -> Self {
+        Self {
+            depth: 0,
+            pending: None,
+        }
+    }
+
+    /// `events` 的最后一项是新接收的事件。
+    pub(crate) fn push(&mut self, events: &[Event]) -> Option<TopLevelBlock> {
+        let index = events.len() - 1;
+        let ev = &events[index];
+        if self.depth == 0 && self.pending.is_none() {
+            let start = match ev {
+                Event::BlockAttachedInfo(AttachedInfo { content, .. }) => {
+                    content.start - "{&>".len()
+                }
+                ev => ev.span().map_or(0, |span| span.start()),
+            };
+            self.pending = Some((index, start));
+        }
+
+        let end = match ev {
+            Event::ExitBlock(ExitBlock { span, .. }) => {
+                self.depth -= 1;
+                (self.depth == 0).then_some(span.end())
+            }
+            Event::ThematicBreak(ThematicBreak { span, .. }) => {
+                (self.depth == 0).then_some(span.end())
+            }
+            ev => {
+                if block_id(ev).is_some() {
+                    self.depth += 1;
+                }
+                None
+            }
+        };
+
+        let end = end?;
+        let (first_index, start) = self.pending.take().unwrap();
+        Some(TopLevelBlock {
+            events: first_index..(index + 1),
+            source: start..end,
+        })
+    }
+
+    /// 尚未结束的顶层块级元素的第一个事件的位置，以及其开始处。
+    #[cfg(feature = "block-id")]
+    pub(crate) fn pending(&self) -> Option<(usize, usize)> {
+        self.pending
+    }
+}
+
+#[cfg(feature = "block-id")]
+/// 返回 `end` 之后的第一个换行之后的位置，即下一行的开头。`end` 与该换行之间只能有空白，否
+/// 则（或不存在这样的换行时）返回 `None`。
+pub(crate) fn find_next_line_start(input: &[u8], end: usize) -> Option<usize> {
+    let index = end
+        + input[end..]
+            .iter()
+            .position(|c| !matches!(c, b' ' | b'\t'))?;
+    match input[index] {
+        b'\r' if input.get(index + 1) == Some(&b'\n') => Some(index + 2),
+        b'\r' | b'\n' => Some(index + 1),
+        _ => None,
+    }
+}
+
+pub(crate) fn has_line_break(input: &[u8]) -> bool {
+    input.iter().any(|c| matches!(c, b'\r' | b'\n'))
+}
+
+/// `\r\n` 只算作一个换行。
+#[cfg(any(feature = "line-number", feature = "block-id"))]
+pub(crate) fn count_line_breaks(input: &[u8]) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .filter(|(index, c)| match c {
+            b'\n' => true,
+            b'\r' => input.get(index + 1) != Some(&b'\n'),
+            _ => false,
+        })
+        .count()
+}
+
+#[cfg(feature = "block-id")]
+/// 若 `ev` 是进入块级元素的事件（包括分割线），返回该块级元素的 ID。
+pub(crate) fn block_id_entered(ev: &Event) -> Option<BlockId> {
+    match ev {
+        Event::ExitBlock(_) => None,
+        ev => block_id(ev),
+    }
+}
+
+pub(crate) fn block_id(ev: &Event) -> Option<BlockId> {
+    block_id_mut(&mut ev.clone()).copied()
+}
+
+pub(crate) fn block_id_mut(ev: &mut Event) -> Option<&mut BlockId> {
+    match ev {
+        Event::ThematicBreak(ThematicBreak { id, .. })
+        | Event::EnterParagraph(BlockWithId { id, .. })
+        | Event::EnterHeading1(BlockWithId { id, .. })
+        | Event::EnterHeading2(BlockWithId { id, .. })
+        | Event::EnterHeading3(BlockWithId { id, .. })
+        | Event::EnterHeading4(BlockWithId { id, .. })
+        | Event::EnterHeading5(BlockWithId { id, .. })
+        | Event::EnterHeading6(BlockWithId { id, .. })
+        | Event::EnterBlockQuote(BlockWithId { id, .. })
+        | Event::EnterOrderedList(OrderedList { id, .. })
+        | Event::EnterUnorderedList(BlockWithId { id, .. })
+        | Event::EnterListItem(ListItem { id, .. })
+        | Event::EnterDescriptionList(BlockWithId { id, .. })
+        | Event::EnterDescriptionTerm(BlockWithId { id, .. })
+        | Event::EnterDescriptionDetails(BlockWithId { id, .. })
+        | Event::EnterCodeBlock(BlockWithId { id, .. })
+        | Event::EnterTable(BlockWithId { id, .. })
+        | Event::EnterCallOnTemplate(Call::Block { id, .. })
+        | Event::EnterCallOnExtension(Call::Block { id, .. })
+        | Event::EnterParameterFilling(Call::Block { id, .. })
+        | Event::ExitBlock(ExitBlock { id, .. }) => Some(id),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "block-id")]
+pub(crate) fn eq_ignoring_block_ids(a: &[Event], b: &[Event]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            let mut b = b.clone();
+            if let (Some(a_id), Some(b_id)) = (block_id(a), block_id_mut(&mut b)) {
+                *b_id = a_id;
+            }
+            *a == b
+        })
+}
+
+#[cfg(any(test, feature = "block-id"))]
+/// 将 `ev` 所含的范围移动 `offset_delta`，所含的行号移动 `line_delta`。
+pub(crate) fn shift_event(
+    ev: &mut Event,
+    offset_delta: isize,
+    #[allow(unused_variables)] line_delta: isize,
+) {
+    use rotext_core::{
+        LineNumber, SourceSpan,
+        events::{AssigningDicexp, CallArgument, NewLine, TableCell, VerbatimEscaping, WikiLink},
+    };
+
+    let shift_range = |range: &mut Range<usize>| {
+        range.start = range.start.wrapping_add_signed(offset_delta);
+        range.end = range.end.wrapping_add_signed(offset_delta);
+    };
+    let shift_span = |span: &mut SourceSpan| {
+        *span = SourceSpan::new(
+            span.start().wrapping_add_signed(offset_delta),
+            span.end().wrapping_add_signed(offset_delta),
+        );
+    };
+    #[allow(unused_variables)]
+    let shift_line = |line: &mut LineNumber| {
+        #[cfg(feature = "line-number")]
+        {
+            *line = LineNumber::new(line.value().wrapping_add_signed(line_delta));
+        }
+    };
+    #[allow(unused_variables)]
+    let shift_position = |position: &mut rotext_core::Position| {
+        #[cfg(feature = "position")]
+        {
+            *position = rotext_core::Position::new(
+                position.line().wrapping_add_signed(line_delta),
+                position.column(),
+                position.utf16_column(),
+            );
+        }
+    };
+
+    #[rotext_internal_macros::ensure_cases_for_event(
+        prefix = Event,
+        group = Blend,
+    )]
+    match ev {
+        Event::Raw(range)
+        | Event::Text(range)
+        | Event::RefLink(range)
+        | Event::ExternalLink(range)
+        | Event::BlockAttachedInfo(AttachedInfo { content: range, .. })
+        | Event::InlineAttachedInfo(AttachedInfo { content: range, .. }) => shift_range(range),
+        Event::VerbatimEscaping(VerbatimEscaping {
+            content,
+            line_after,
+            ..
+        }) => {
+            shift_range(content);
+            shift_line(line_after);
+        }
+        Event::NewLine(NewLine { line_after }) => shift_line(line_after),
+        Event::ThematicBreak(ThematicBreak {
+            line,
+            span,
+            start,
+            end,
+            ..
+        }) => {
+            shift_line(line);
+            shift_span(span);
+            shift_position(start);
+            shift_position(end);
+        }
+        Event::ExitBlock(ExitBlock {
+            start_line,
+            end_line,
+            span,
+            start,
+            end,
+            ..
+        }) => {
+            shift_line(start_line);
+            shift_line(end_line);
+            shift_span(span);
+            shift_position(start);
+            shift_position(end);
+        }
+        Event::EnterParagraph(BlockWithId { span, position, .. })
+        | Event::EnterHeading1(BlockWithId { span, position, .. })
+        | Event::EnterHeading2(BlockWithId { span, position, .. })
+        | Event::EnterHeading3(BlockWithId { span, position, .. })
+        | Event::EnterHeading4(BlockWithId { span, position, .. })
+        | Event::EnterHeading5(BlockWithId { span, position, .. })
+        | Event::EnterHeading6(BlockWithId { span, position, .. })
+        | Event::EnterBlockQuote(BlockWithId { span, position, .. })
+        | Event::EnterOrderedList(OrderedList { span, position, .. })
+        | Event::EnterUnorderedList(BlockWithId { span, position, .. })
+        | Event::EnterListItem(ListItem { span, position, .. })
+        | Event::EnterDescriptionList(BlockWithId { span, position, .. })
+        | Event::EnterDescriptionTerm(BlockWithId { span, position, .. })
+        | Event::EnterDescriptionDetails(BlockWithId { span, position, .. })
+        | Event::EnterCodeBlock(BlockWithId { span, position, .. })
+        | Event::EnterTable(BlockWithId { span, position, .. }) => {
+            shift_span(span);
+            shift_position(position);
+        }
+        Event::IndicateCodeBlockCode(span)
+        | Event::IndicateTableCaption(span)
+        | Event::IndicateTableRow(span)
+        | Event::IndicateTableHeaderCell(TableCell { span, .. })
+        | Event::IndicateTableDataCell(TableCell { span, .. })
+        | Event::IndicateParameterFillingFallback(span)
+        | Event::EnterCodeSpan(span)
+        | Event::EnterEmphasis(span)
+        | Event::EnterStrong(span)
+        | Event::EnterStrikethrough(span)
+        | Event::EnterRuby(span)
+        | Event::EnterRubyText(span)
+        | Event::EnterDicexp(span)
+        | Event::ExitInline(span) => shift_span(span),
+        Event::EnterCallOnTemplate(Call::Block {
+            name,
+            span,
+            position,
+            ..
+        })
+        | Event::EnterCallOnExtension(Call::Block {
+            name,
+            span,
+            position,
+            ..
+        })
+        | Event::EnterParameterFilling(Call::Block {
+            name,
+            span,
+            position,
+            ..
+        }) => {
+            shift_range(name);
+            shift_span(span);
+            shift_position(position);
+        }
+        Event::EnterCallOnTemplate(Call::Inline { name, span })
+        | Event::EnterCallOnExtension(Call::Inline { name, span })
+        | Event::EnterParameterFilling(Call::Inline { name, span })
+        | Event::EnterAssigningDicexp(AssigningDicexp { name, span })
+        | Event::EnterWikiLink(WikiLink {
+            address: name,
+            span,
+        }) => {
+            shift_range(name);
+            shift_span(span);
+        }
+        Event::IndicateCallNormalArgument(CallArgument { name, span })
+        | Event::IndicateCallVerbatimArgument(CallArgument { name, span }) => {
+            if let Some(name) = name {
+                shift_range(name);
+            }
+            shift_span(span);
+        }
+    }
+}
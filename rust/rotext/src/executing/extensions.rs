@@ -2,7 +2,19 @@ use std::collections::{HashMap, HashSet};
 
 pub enum Extension<'a> {
     ElementMapper(Box<ExtensionElementMapper<'a>>),
-    Alias { to: &'a [u8] },
+    /// 旁注，仅用于行内扩展。参数 `1` 为旁注的内容，逐字参数 `名称` 则引用具名旁注，二者只能
+    /// 提供其一。参数正确的调用会在编译时被收集为对旁注的引用，调用处只留下指向它的带编号
+    /// 的链接。
+    FootnoteReference,
+    /// 旁注内容，仅用于块级扩展。在调用处渲染此前尚未渲染的旁注。文档结束时仍未渲染的旁注
+    /// 会被渲染在文档末尾。同时提供逐字参数 `名称` 与参数 `1` 时，转而定义具名旁注的内容，
+    /// 该调用会在编译时被收集起来。
+    FootnoteList,
+    /// 目录，仅用于块级扩展。渲染由文档中各标题组成的嵌套列表，各项链接至对应标题的锚点。
+    TableOfContents,
+    Alias {
+        to: &'a [u8],
+    },
 }
 
 pub struct ExtensionElementMapper<'a> {
@@ -153,6 +165,14 @@ pub fn new_demo_block_extension_map_for_test() -> HashMap<&'static [u8], Extensi
         })),
     );
     map.insert("折叠".as_bytes(), Extension::Alias { to: b"Collapse" });
+    map.insert(b"Footnotes", Extension::FootnoteList);
+    map.insert("旁注内容".as_bytes(), Extension::Alias {
+        to: b"Footnotes",
+    });
+    map.insert(b"TableOfContents", Extension::TableOfContents);
+    map.insert("目录".as_bytes(), Extension::Alias {
+        to: b"TableOfContents",
+    });
     for (name, variant, alias) in [
         (&b"Note"[..], &b"note"[..], "注".as_bytes()),
         (b"Tip", b"tip", "提示".as_bytes()),
@@ -237,6 +257,8 @@ pub fn new_demo_inline_extension_map_for_test() -> HashMap<&'static [u8], Extens
         })),
     );
     map.insert("刮开".as_bytes(), Extension::Alias { to: b"ScratchOff" });
+    map.insert(b"Footnote", Extension::FootnoteReference);
+    map.insert("旁注".as_bytes(), Extension::Alias { to: b"Footnote" });
 
     map
 }
@@ -3,21 +3,39 @@ mod renderer;
 #[cfg(test)]
 mod tests;
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    ops::Range,
+    rc::Rc,
+};
 
 #[cfg(feature = "block-id")]
 use rotext_core::BlockId;
-use rotext_core::Event;
+use rotext_core::{Event, events::AttachedInfoTarget};
 
 use crate::{CompiledItem, compiling};
-pub use renderer::TagNameMap;
+pub use renderer::{CodeBlockRendering, TagNameMap};
 
 pub mod extensions;
+pub mod templates;
+
+/// 执行一份文档时，至多展开多少次嵌入包含。调用深度的限制无法约束形如 `{{A}}{{A}}` 这
+/// 样、展开次数随深度指数增长的模板，因此另设此限制。
+const MAX_TRANSCLUSION_EXPANSIONS: usize = 1000;
 
 pub struct NewExecutorOptions<'a> {
     pub tag_name_map: &'a TagNameMap<'a>,
     pub block_extension_map: &'a HashMap<&'a [u8], extensions::Extension<'a>>,
     pub inline_extension_map: &'a HashMap<&'a [u8], extensions::Extension<'a>>,
+    /// 为嵌入包含提供模板。为 `None` 时，所有嵌入包含都会因找不到模板而出错。
+    pub template_provider: Option<&'a dyn templates::TemplateProvider>,
+    /// 编译被嵌入包含的模板时使用的选项。
+    pub compile_options: &'a compiling::NewCompileOptions<'a>,
+    pub code_block_rendering: CodeBlockRendering,
+    /// 旁注相关的 ID（`fn-N` 与 `fnref-N`）的前缀，用于在同一页面中放置多份文档时避免 ID
+    /// 冲突。
+    pub footnote_id_prefix: &'a [u8],
 
     #[cfg(feature = "block-id")]
     pub should_include_block_ids: bool,
@@ -27,9 +45,16 @@ pub struct Executor<'a> {
     tag_name_map: &'a TagNameMap<'a>,
     block_extension_map: &'a HashMap<&'a [u8], extensions::Extension<'a>>,
     inline_extension_map: &'a HashMap<&'a [u8], extensions::Extension<'a>>,
+    template_provider: Option<&'a dyn templates::TemplateProvider>,
+    compile_options: &'a compiling::NewCompileOptions<'a>,
+    footnote_id_prefix: &'a [u8],
 
     #[cfg(feature = "block-id")]
     with_block_id: bool,
+    /// 执行模板用的执行器。模板中的块级 ID 与本文档中的并不对应，因此在输出块级 ID 时，
+    /// 需要另外一个不输出块级 ID 的执行器来执行模板。
+    #[cfg(feature = "block-id")]
+    template_executor: Option<Box<Executor<'a>>>,
 
     renderer: renderer::Renderer<'a>,
 }
@@ -48,11 +73,14 @@ impl CallType {
 }
 
 enum CallError<'a> {
-    /// 功能还没实现。TODO: 去掉此变体。
-    Todo,
-
     /// 没有找到要调用的对象。
     UnknownCallee(&'a [u8]),
+    /// 解析被嵌入包含的模板时出错。
+    ParseError(rotext_core::Error),
+    /// 编译被嵌入包含的模板时出错。
+    CompilationError(compiling::Error),
+    /// 嵌入包含展开的次数超过了 [MAX_TRANSCLUSION_EXPANSIONS]。
+    TooManyTransclusions,
     BadParameters {
         normal: Option<Box<CallErrorBadParameters>>,
         verbatim: Option<Box<CallErrorBadParameters>>,
@@ -61,8 +89,12 @@ enum CallError<'a> {
 impl CallError<'_> {
     fn destruct(self) -> (&'static [u8], Option<Vec<u8>>) {
         match self {
-            CallError::Todo => (b"TODO", None),
             CallError::UnknownCallee(name) => (b"UnknownCallee", Some(name.to_vec())),
+            CallError::ParseError(err) => (b"ParseError", Some(err.name().as_bytes().to_vec())),
+            CallError::CompilationError(err) => {
+                (b"CompilationError", Some(err.name().as_bytes().to_vec()))
+            }
+            CallError::TooManyTransclusions => (b"TooManyTransclusions", None),
             CallError::BadParameters { normal, verbatim } => {
                 let normal_vec = normal.map(|v| v.to_vec());
                 let verbatim_vec = verbatim.map(|v| v.to_vec());
@@ -131,6 +163,60 @@ impl CallErrorBadParameters {
     }
 }
 
+/// 执行某份文档（或其中的一部分）时所需的上下文。
+#[derive(Clone, Copy)]
+struct ExecutionContext<'b> {
+    input: &'b [u8],
+    parsed: &'b [Event],
+    /// 正在执行的内容所处的调用嵌套深度，与编译时的深度一致。
+    depth: usize,
+    /// 正在执行的内容属于被嵌入包含的模板时，为该嵌入包含的调用方。
+    caller: Option<&'b TransclusionCaller<'b>>,
+    /// 整份文档共享的旁注。
+    footnotes: &'b RefCell<Footnotes>,
+    /// 整份文档共享的嵌入包含的状态。
+    transclusions: &'b Transclusions,
+    /// `parsed` 中各标题的锚点。
+    heading_anchors: &'b [crate::headings::HeadingAnchor],
+}
+
+/// 执行过程中渲染的旁注。旁注的编号即其在 `contents` 中的位置加一。
+#[derive(Default)]
+struct Footnotes {
+    contents: Vec<Vec<u8>>,
+    /// 已经编号的具名旁注的名称到其在 `contents` 中的位置的映射。
+    named: HashMap<Vec<u8>, usize>,
+    /// 已经渲染到旁注列表中的旁注的数量。
+    rendered: usize,
+}
+
+/// 执行过程中嵌入包含的状态。
+#[derive(Default)]
+struct Transclusions {
+    /// 已经展开的嵌入包含的数量。
+    expanded: Cell<usize>,
+    /// 已经解析过的模板，以模板的名称及是否作为块级内容解析为键。解析失败的模板不会被记
+    /// 录。
+    ///
+    /// 编译的结果取决于调用所处的深度，因此模板在每次展开时仍会重新编译。
+    parsed_templates: RefCell<ParsedTemplates>,
+}
+
+type ParsedTemplates = HashMap<(Vec<u8>, bool), Rc<ParsedTemplate>>;
+
+struct ParsedTemplate {
+    source: Vec<u8>,
+    /// 属于 `Blend` 分组的事件。作为行内内容解析的模板，不含包裹其内容的段落。
+    parsed: Vec<Event>,
+}
+
+struct TransclusionCaller<'b> {
+    executor: &'b Executor<'b>,
+    /// 调用所处的上下文。
+    ctx: &'b ExecutionContext<'b>,
+    call: &'b compiling::CompiledItemCall<'b>,
+}
+
 struct RenderCallErrorInput<'a> {
     is_block: bool,
     call_type: CallType,
@@ -145,6 +231,7 @@ impl<'a> Executor<'a> {
     pub fn new(opts: &NewExecutorOptions<'a>) -> Self {
         let renderer_opts = renderer::NewRendererOptions {
             tag_name_map: opts.tag_name_map,
+            code_block_rendering: opts.code_block_rendering,
             #[cfg(feature = "block-id")]
             should_include_block_ids: opts.should_include_block_ids,
         };
@@ -153,8 +240,18 @@ impl<'a> Executor<'a> {
             tag_name_map: opts.tag_name_map,
             block_extension_map: opts.block_extension_map,
             inline_extension_map: opts.inline_extension_map,
+            template_provider: opts.template_provider,
+            compile_options: opts.compile_options,
+            footnote_id_prefix: opts.footnote_id_prefix,
             #[cfg(feature = "block-id")]
             with_block_id: opts.should_include_block_ids,
+            #[cfg(feature = "block-id")]
+            template_executor: opts.should_include_block_ids.then(|| {
+                Box::new(Executor::new(&NewExecutorOptions {
+                    should_include_block_ids: false,
+                    ..*opts
+                }))
+            }),
             renderer: renderer::Renderer::new(renderer_opts),
         }
     }
@@ -162,44 +259,266 @@ impl<'a> Executor<'a> {
     pub fn execute(
         &self,
         buf: &mut Vec<u8>,
-        input: &'a [u8],
+        input: &[u8],
         parsed: &[Event],
         compiled: &[CompiledItem],
+    ) {
+        let footnotes = RefCell::new(Footnotes::default());
+        let transclusions = Transclusions::default();
+        let heading_anchors = crate::headings::collect_heading_anchors_reserving_footnote_ids(
+            input,
+            parsed,
+            self.footnote_id_prefix,
+        );
+        let ctx = ExecutionContext {
+            input,
+            parsed,
+            depth: 1,
+            caller: None,
+            footnotes: &footnotes,
+            transclusions: &transclusions,
+            heading_anchors: &heading_anchors,
+        };
+        self.execute_internal(buf, &ctx, compiled);
+
+        // 没有被 “旁注内容” 扩展渲染的旁注，统一渲染在文档末尾。
+        self.render_footnote_list(buf, &ctx, None);
+    }
+
+    fn execute_internal(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        compiled: &[CompiledItem],
     ) {
         let mut stack: Vec<renderer::StackEntryBox> = vec![];
 
         for item in compiled {
-            match item {
-                CompiledItem::SimpleEvents(range) => {
-                    let evs = &parsed[range.clone()];
-                    self.renderer.render_events(buf, input, evs, &mut stack);
-                }
-                CompiledItem::BlockTransclusion(call) | CompiledItem::InlineTransclusion(call) => {
-                    self.render_call_error(buf, RenderCallErrorInput {
-                        is_block: matches!(item, CompiledItem::BlockTransclusion(_)),
-                        call_type: CallType::Transclusion,
-                        call_name: call.name,
-                        error: CallError::Todo,
-                        #[cfg(feature = "block-id")]
-                        block_id: call.block_id,
-                    });
-                }
-                CompiledItem::BlockExtension(call) => {
-                    self.render_block_extension(buf, input, parsed, call);
-                }
-                CompiledItem::InlineExtension(call) => {
-                    self.render_inline_extension(buf, input, parsed, call);
+            self.execute_item(buf, ctx, item, &mut stack);
+        }
+    }
+
+    fn execute_item(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        item: &CompiledItem,
+        stack: &mut Vec<renderer::StackEntryBox>,
+    ) {
+        match item {
+            CompiledItem::SimpleEvents(range) => {
+                let evs = &ctx.parsed[range.clone()];
+                let anchors = renderer::HeadingAnchors::new(ctx.heading_anchors, range.start);
+                self.renderer
+                    .render_events(buf, ctx.input, evs, anchors, stack);
+            }
+            CompiledItem::BlockTransclusion(call) => {
+                self.render_transclusion(buf, ctx, true, call);
+            }
+            CompiledItem::InlineTransclusion(call) => {
+                self.render_transclusion(buf, ctx, false, call);
+            }
+            CompiledItem::BlockExtension(call) => {
+                self.render_block_extension(buf, ctx, call);
+            }
+            CompiledItem::InlineExtension(call) => {
+                self.render_inline_extension(buf, ctx, call);
+            }
+            CompiledItem::BlockParameterFilling(filling) => {
+                self.render_parameter_filling(buf, ctx, false, filling);
+            }
+            CompiledItem::InlineParameterFilling(filling) => {
+                self.render_parameter_filling(buf, ctx, true, filling);
+            }
+            CompiledItem::FootnoteReference(reference) => {
+                self.render_footnote_reference(buf, ctx, reference);
+            }
+        }
+    }
+
+    /// 以行内内容的形式执行 `compiled`。若其恰为单个段落（如块级调用的参数），则只执行
+    /// 段落之内的部分。
+    fn execute_internal_as_inline(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        compiled: &[CompiledItem],
+    ) {
+        let Some(content) = single_paragraph_content(ctx.parsed, compiled) else {
+            self.execute_internal(buf, ctx, compiled);
+            return;
+        };
+
+        let mut stack: Vec<renderer::StackEntryBox> = vec![];
+        for item in compiled {
+            if let CompiledItem::SimpleEvents(range) = item {
+                let range = range.start.max(content.start)..range.end.min(content.end);
+                if !range.is_empty() {
+                    let anchors = renderer::HeadingAnchors::new(ctx.heading_anchors, range.start);
+                    let evs = &ctx.parsed[range];
+                    self.renderer
+                        .render_events(buf, ctx.input, evs, anchors, &mut stack);
                 }
+            } else {
+                self.execute_item(buf, ctx, item, &mut stack);
             }
         }
     }
 
+    /// 行内嵌入包含的模板会被视为单个段落的行内内容进行解析，其结果直接嵌入所在的段落之中。
+    fn render_transclusion(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        is_block: bool,
+        call: &compiling::CompiledItemCall<'_>,
+    ) {
+        let render_error = |buf: &mut Vec<u8>, error: CallError| {
+            self.render_call_error(buf, RenderCallErrorInput {
+                is_block,
+                call_type: CallType::Transclusion,
+                call_name: call.name,
+                error,
+                #[cfg(feature = "block-id")]
+                block_id: call.block_id,
+            });
+        };
+
+        let expanded = ctx.transclusions.expanded.get();
+        if expanded >= MAX_TRANSCLUSION_EXPANSIONS {
+            render_error(buf, CallError::TooManyTransclusions);
+            return;
+        }
+        ctx.transclusions.expanded.set(expanded + 1);
+
+        let template = match self.get_parsed_template(ctx, is_block, call.name) {
+            Ok(template) => template,
+            Err(err) => {
+                render_error(buf, err);
+                return;
+            }
+        };
+        let source = &template.source[..];
+        let parsed = &template.parsed[..];
+
+        let caller = TransclusionCaller {
+            executor: self,
+            ctx,
+            call,
+        };
+        // 模板中的内容与调用的参数处于同一深度。
+        let template_ctx = ExecutionContext {
+            input: source,
+            parsed,
+            depth: ctx.depth + 1,
+            caller: Some(&caller),
+            footnotes: ctx.footnotes,
+            transclusions: ctx.transclusions,
+            // 模板中的标题不参与本文档的锚点，因此不输出 ID。
+            heading_anchors: &[],
+        };
+        let compiler = compiling::Compiler::new(self.compile_options);
+        let compiled = match compiler.compile_at_depth(source, parsed, template_ctx.depth) {
+            Ok(compiled) => compiled,
+            Err(err) => {
+                render_error(buf, CallError::CompilationError(err));
+                return;
+            }
+        };
+
+        #[cfg(feature = "block-id")]
+        let executor = self.template_executor.as_deref().unwrap_or(self);
+        #[cfg(not(feature = "block-id"))]
+        let executor = self;
+
+        executor.execute_internal(buf, &template_ctx, &compiled);
+    }
+
+    /// 取得名为 `name` 的模板解析后的结果。同一份文档中的同一模板只会被获取并解析一次。
+    fn get_parsed_template<'c>(
+        &self,
+        ctx: &ExecutionContext,
+        is_block: bool,
+        name: &'c [u8],
+    ) -> Result<Rc<ParsedTemplate>, CallError<'c>> {
+        let key = (name.to_vec(), is_block);
+        if let Some(template) = ctx.transclusions.parsed_templates.borrow().get(&key) {
+            return Ok(template.clone());
+        }
+
+        let Some(source) = self
+            .template_provider
+            .and_then(|provider| provider.get_template_source(name))
+        else {
+            return Err(CallError::UnknownCallee(name));
+        };
+        let source = source.into_owned();
+
+        let parsed = if is_block {
+            crate::parse(&source).collect::<rotext_core::Result<Vec<_>>>()
+        } else {
+            crate::parse_inline_only(&source).collect::<rotext_core::Result<Vec<_>>>()
+        };
+        let mut parsed = parsed.map_err(CallError::ParseError)?;
+        if !is_block && !parsed.is_empty() {
+            // 去掉包裹行内内容的段落。
+            debug_assert!(matches!(parsed.first(), Some(Event::EnterParagraph(_))));
+            debug_assert!(matches!(parsed.last(), Some(Event::ExitBlock(_))));
+            parsed.pop();
+            parsed.remove(0);
+        }
+
+        let template = Rc::new(ParsedTemplate { source, parsed });
+        ctx.transclusions
+            .parsed_templates
+            .borrow_mut()
+            .insert(key, template.clone());
+        Ok(template)
+    }
+
+    /// 模板中的参数填充会被替换为调用方提供的对应参数，没有对应参数时则使用 fallback。
+    fn render_parameter_filling(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        is_inline: bool,
+        filling: &compiling::CompiledItemParameterFilling<'_>,
+    ) {
+        let argument = ctx.caller.and_then(|caller| {
+            let (_, value) = caller
+                .call
+                .arguments
+                .iter()
+                .find(|(key, _)| key.matches(filling.name))?;
+            Some((caller, value))
+        });
+
+        let Some((caller, value)) = argument else {
+            if let Some(fallback) = &filling.fallback {
+                self.execute_internal(buf, ctx, fallback);
+            }
+            return;
+        };
+
+        // 参数值属于调用方，因此在调用方的上下文中执行。
+        let arg_ctx = ExecutionContext {
+            depth: caller.ctx.depth + 1,
+            ..*caller.ctx
+        };
+        if is_inline {
+            caller
+                .executor
+                .execute_internal_as_inline(buf, &arg_ctx, value);
+        } else {
+            caller.executor.execute_internal(buf, &arg_ctx, value);
+        }
+    }
+
     fn render_block_extension(
         &self,
         buf: &mut Vec<u8>,
-        input: &'a [u8],
-        parsed: &[Event],
-        call: &crate::compiling::CompiledItemCall<'a>,
+        ctx: &ExecutionContext,
+        call: &crate::compiling::CompiledItemCall<'_>,
     ) {
         #[cfg(all(debug_assertions, feature = "block-id"))]
         {
@@ -226,9 +545,37 @@ impl<'a> Executor<'a> {
             ext
         };
 
+        let render_error = |buf: &mut Vec<u8>, error: CallError| {
+            self.render_call_error(buf, RenderCallErrorInput {
+                is_block,
+                call_type: CallType::Extension,
+                call_name: call.name,
+                error,
+                #[cfg(feature = "block-id")]
+                block_id: call.block_id,
+            });
+        };
+
         match ext {
             extensions::Extension::ElementMapper(ext) => {
-                self.render_element_mapper_extension(buf, input, parsed, is_block, call, ext);
+                self.render_element_mapper_extension(buf, ctx, is_block, call, ext);
+            }
+            extensions::Extension::FootnoteList => {
+                if let Some(error) = check_builtin_extension_parameters(call, None) {
+                    render_error(buf, error);
+                    return;
+                }
+                self.render_footnote_list(buf, ctx, Some(call));
+            }
+            extensions::Extension::TableOfContents => {
+                if let Some(error) = check_builtin_extension_parameters(call, None) {
+                    render_error(buf, error);
+                    return;
+                }
+                self.render_table_of_contents(buf, ctx, call);
+            }
+            extensions::Extension::FootnoteReference => {
+                render_error(buf, CallError::UnknownCallee(call.name));
             }
             extensions::Extension::Alias { .. } => unreachable!(),
         }
@@ -237,9 +584,8 @@ impl<'a> Executor<'a> {
     fn render_inline_extension(
         &self,
         buf: &mut Vec<u8>,
-        input: &'a [u8],
-        parsed: &[Event],
-        call: &crate::compiling::CompiledItemCall<'a>,
+        ctx: &ExecutionContext,
+        call: &crate::compiling::CompiledItemCall<'_>,
     ) {
         let is_block = false;
 
@@ -263,19 +609,234 @@ impl<'a> Executor<'a> {
 
         match ext {
             extensions::Extension::ElementMapper(ext) => {
-                self.render_element_mapper_extension(buf, input, parsed, is_block, call, ext);
+                self.render_element_mapper_extension(buf, ctx, is_block, call, ext);
+            }
+            extensions::Extension::FootnoteReference => {
+                // 参数正确的调用已在编译时被收集为对旁注的引用。
+                let error = check_builtin_extension_parameters(call, Some(b"1"))
+                    .unwrap_or(CallError::UnknownCallee(call.name));
+                self.render_call_error(buf, RenderCallErrorInput {
+                    is_block,
+                    call_type: CallType::Extension,
+                    call_name: call.name,
+                    error,
+                    #[cfg(feature = "block-id")]
+                    block_id: None,
+                });
+            }
+            extensions::Extension::FootnoteList | extensions::Extension::TableOfContents => {
+                self.render_call_error(buf, RenderCallErrorInput {
+                    is_block,
+                    call_type: CallType::Extension,
+                    call_name: call.name,
+                    error: CallError::UnknownCallee(call.name),
+                    #[cfg(feature = "block-id")]
+                    block_id: None,
+                });
             }
             extensions::Extension::Alias { .. } => unreachable!(),
         }
     }
 
+    /// 为旁注编号并渲染其内容，在调用处渲染指向该旁注的链接。已经编号的具名旁注沿用原有
+    /// 的编号，此时的链接不带 ID，旁注列表中的链接只会回到第一处引用。
+    fn render_footnote_reference(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        reference: &compiling::CompiledItemFootnoteReference<'_>,
+    ) {
+        let numbered = reference
+            .footnote_name
+            .as_ref()
+            .and_then(|name| ctx.footnotes.borrow().named.get(name).copied());
+        let (index, is_first) = if let Some(index) = numbered {
+            (index, false)
+        } else {
+            let Some(value) = &reference.content else {
+                // 具名旁注没有对应的内容。
+                let mut bad = CallErrorBadParameters::default();
+                bad.missing.insert(b"1".to_vec());
+                self.render_call_error(buf, RenderCallErrorInput {
+                    is_block: false,
+                    call_type: CallType::Extension,
+                    call_name: reference.name,
+                    error: CallError::BadParameters {
+                        normal: Some(Box::new(bad)),
+                        verbatim: None,
+                    },
+                    #[cfg(feature = "block-id")]
+                    block_id: None,
+                });
+                return;
+            };
+
+            // 先占下编号，这样嵌套在内容中的旁注会排在本旁注之后。
+            let index = {
+                let mut footnotes = ctx.footnotes.borrow_mut();
+                footnotes.contents.push(vec![]);
+                let index = footnotes.contents.len() - 1;
+                if let Some(name) = &reference.footnote_name {
+                    footnotes.named.insert(name.clone(), index);
+                }
+                index
+            };
+            // 参数值比调用本身深一层。
+            let arg_ctx = ExecutionContext {
+                depth: ctx.depth + 1,
+                ..*ctx
+            };
+            // 具名旁注的内容来自块级调用的参数，为单个段落时只取其中的行内内容。
+            let mut content = vec![];
+            self.execute_internal_as_inline(&mut content, &arg_ctx, value);
+            ctx.footnotes.borrow_mut().contents[index] = content;
+            (index, true)
+        };
+
+        let mut number_buffer = itoa::Buffer::new();
+        let number = number_buffer.format(index + 1).as_bytes();
+        let id = [self.footnote_id_prefix, b"fnref-", number].concat();
+        let href = [b"#", self.footnote_id_prefix, b"fn-", number].concat();
+
+        let classes = crate::utils::join_attached_classes(ctx.input, &reference.attached_infos);
+        let mut attrs: Vec<(&[u8], &[u8])> = vec![];
+        if is_first {
+            attrs.push((b"id", &id));
+        }
+        if let Some(classes) = &classes {
+            attrs.push((b"class", classes));
+        }
+        attrs.extend(crate::utils::collect_attached_attributes(
+            ctx.input,
+            &reference.attached_infos,
+        ));
+        crate::utils::render_eopening_tag(buf, b"sup", &attrs);
+        crate::utils::render_eopening_tag(buf, b"a", &[(b"href", &href)]);
+        buf.push(b'[');
+        buf.extend(number);
+        buf.push(b']');
+        crate::utils::render_closing_tag(buf, b"a");
+        crate::utils::render_closing_tag(buf, b"sup");
+    }
+
+    /// 渲染此前尚未渲染的旁注。没有这样的旁注时不渲染任何内容。`call` 为 `None` 时代表是
+    /// 在文档末尾渲染。
+    fn render_footnote_list(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        call: Option<&compiling::CompiledItemCall<'_>>,
+    ) {
+        let mut footnotes = ctx.footnotes.borrow_mut();
+        if footnotes.rendered == footnotes.contents.len() {
+            return;
+        }
+
+        let mut classes = b"footnotes".to_vec();
+        if let Some(attached) = call
+            .and_then(|call| crate::utils::join_attached_classes(ctx.input, &call.attached_infos))
+        {
+            classes.push(b' ');
+            classes.extend(attached);
+        }
+        let mut attrs: Vec<(&[u8], &[u8])> = vec![(b"class", &classes)];
+        if let Some(call) = call {
+            attrs.extend(crate::utils::collect_attached_attributes(
+                ctx.input,
+                &call.attached_infos,
+            ));
+        }
+        let mut start_buffer = itoa::Buffer::new();
+        if footnotes.rendered > 0 {
+            attrs.push((
+                b"start",
+                start_buffer.format(footnotes.rendered + 1).as_bytes(),
+            ));
+        }
+        #[cfg(feature = "block-id")]
+        let mut block_id_buffer = itoa::Buffer::new();
+        #[cfg(feature = "block-id")]
+        if let Some(block_id) = call.and_then(|call| call.block_id) {
+            if self.with_block_id {
+                attrs.push((
+                    b"data-block-id",
+                    block_id_buffer.format(block_id.value()).as_bytes(),
+                ));
+            }
+        }
+        crate::utils::render_eopening_tag(buf, b"ol", &attrs);
+
+        let mut number_buffer = itoa::Buffer::new();
+        for (i, content) in footnotes
+            .contents
+            .iter()
+            .enumerate()
+            .skip(footnotes.rendered)
+        {
+            let number = number_buffer.format(i + 1).as_bytes();
+            let id = [self.footnote_id_prefix, b"fn-", number].concat();
+            let href = [b"#", self.footnote_id_prefix, b"fnref-", number].concat();
+            crate::utils::render_eopening_tag(buf, b"li", &[(b"id", &id)]);
+            crate::utils::render_eopening_tag(buf, b"a", &[(b"href", &href)]);
+            buf.extend("↑".as_bytes());
+            crate::utils::render_closing_tag(buf, b"a");
+            buf.push(b' ');
+            buf.extend(content);
+            crate::utils::render_closing_tag(buf, b"li");
+        }
+
+        crate::utils::render_closing_tag(buf, b"ol");
+        footnotes.rendered = footnotes.contents.len();
+    }
+
+    /// 渲染由文档中各标题组成的嵌套列表，其结构同 [crate::headings::outline]。文档中没有标
+    /// 题时不渲染任何内容。
+    fn render_table_of_contents(
+        &self,
+        buf: &mut Vec<u8>,
+        ctx: &ExecutionContext,
+        call: &compiling::CompiledItemCall<'_>,
+    ) {
+        if ctx.heading_anchors.is_empty() {
+            return;
+        }
+
+        let mut classes = b"toc".to_vec();
+        if let Some(attached) = crate::utils::join_attached_classes(ctx.input, &call.attached_infos)
+        {
+            classes.push(b' ');
+            classes.extend(attached);
+        }
+        let mut attrs: Vec<(&[u8], &[u8])> = vec![(b"class", &classes)];
+        attrs.extend(crate::utils::collect_attached_attributes(
+            ctx.input,
+            &call.attached_infos,
+        ));
+        #[cfg(feature = "block-id")]
+        let mut block_id_buffer = itoa::Buffer::new();
+        #[cfg(feature = "block-id")]
+        if let Some(block_id) = call.block_id {
+            if self.with_block_id {
+                attrs.push((
+                    b"data-block-id",
+                    block_id_buffer.format(block_id.value()).as_bytes(),
+                ));
+            }
+        }
+        crate::utils::render_eopening_tag(buf, b"nav", &attrs);
+
+        let outline = crate::headings::outline_from_anchors(ctx.parsed, ctx.heading_anchors);
+        render_outline_items(buf, &outline);
+
+        crate::utils::render_closing_tag(buf, b"nav");
+    }
+
     fn render_element_mapper_extension(
         &self,
         buf: &mut Vec<u8>,
-        input: &'a [u8],
-        parsed: &[Event],
+        ctx: &ExecutionContext,
         is_block: bool,
-        call: &compiling::CompiledItemCall<'a>,
+        call: &compiling::CompiledItemCall<'_>,
         ext: &extensions::ExtensionElementMapper<'a>,
     ) {
         // 不记别名。
@@ -292,8 +853,7 @@ impl<'a> Executor<'a> {
         for (key, value) in &call.arguments {
             self.process_element_mapper_extension_argument(
                 &mut content,
-                input,
-                parsed,
+                ctx,
                 ProcessBlockElementMapperExtensionArgumentParameters {
                     ext,
                     key,
@@ -357,11 +917,19 @@ impl<'a> Executor<'a> {
             attrs.push((b"variant".to_vec(), variant));
         }
 
-        #[allow(unused_mut)]
+        let classes = crate::utils::join_attached_classes(ctx.input, &call.attached_infos);
+
         let mut attrs = attrs
             .iter()
             .map(|(k, v)| (k.as_slice(), *v))
             .collect::<Vec<_>>();
+        if let Some(classes) = &classes {
+            attrs.push((b"class", classes));
+        }
+        attrs.extend(crate::utils::collect_attached_attributes(
+            ctx.input,
+            &call.attached_infos,
+        ));
 
         #[cfg(feature = "block-id")]
         {
@@ -392,8 +960,7 @@ impl<'a> Executor<'a> {
     fn process_element_mapper_extension_argument(
         &self,
         content_buf: &mut Vec<u8>,
-        input: &'a [u8],
-        parsed: &[Event],
+        ctx: &ExecutionContext,
         params: ProcessBlockElementMapperExtensionArgumentParameters<'a, '_>,
     ) {
         let key_vec = params.key.to_vec();
@@ -417,14 +984,24 @@ impl<'a> Executor<'a> {
             return;
         }
 
+        // 参数值比调用本身深一层。
+        let arg_ctx = ExecutionContext {
+            depth: ctx.depth + 1,
+            ..*ctx
+        };
         match param.mapping_to {
             extensions::ExtensionElementMapperParameterMappingTo::NamedSlot(slot_name) => {
-                crate::utils::render_eopening_tag(content_buf, b"div", &[(b"slot", slot_name)]);
-                self.execute(content_buf, input, parsed, params.value);
+                let mut attrs: Vec<(&[u8], &[u8])> = vec![(b"slot", slot_name)];
+                let classes = find_slot_classes(ctx.input, ctx.parsed, params.value);
+                if let Some(classes) = &classes {
+                    attrs.push((b"class", classes));
+                }
+                crate::utils::render_eopening_tag(content_buf, b"div", &attrs);
+                self.execute_internal(content_buf, &arg_ctx, params.value);
                 crate::utils::render_closing_tag(content_buf, b"div");
             }
             extensions::ExtensionElementMapperParameterMappingTo::UnnamedSlot => {
-                self.execute(content_buf, input, parsed, params.value);
+                self.execute_internal(content_buf, &arg_ctx, params.value);
             }
         }
     }
@@ -504,6 +1081,78 @@ impl<'a> Executor<'a> {
     }
 }
 
+/// 检查旁注、目录等内置扩展的参数。`param` 为唯一（且必要）的参数的名称，为 `None` 时代
+/// 表不接受任何参数。
+fn check_builtin_extension_parameters<'a>(
+    call: &compiling::CompiledItemCall<'_>,
+    param: Option<&[u8]>,
+) -> Option<CallError<'a>> {
+    let mut bad: Option<CallErrorBadParameters> = None;
+    let mut seen = false;
+    for (key, _) in &call.arguments {
+        let key = key.to_vec();
+        if param != Some(&key[..]) {
+            let bad = bad.get_or_insert_with(CallErrorBadParameters::default);
+            bad.unknown.insert(key);
+        } else if seen {
+            let bad = bad.get_or_insert_with(CallErrorBadParameters::default);
+            bad.duplicated.insert(key);
+        } else {
+            seen = true;
+        }
+    }
+    if let Some(param) = param {
+        if !seen {
+            let bad = bad.get_or_insert_with(CallErrorBadParameters::default);
+            bad.missing.insert(param.to_vec());
+        }
+    }
+
+    let mut bad_verbatim: Option<CallErrorBadParameters> = None;
+    for (key, _) in &call.verbatim_arguments {
+        let bad_verbatim = bad_verbatim.get_or_insert_with(CallErrorBadParameters::default);
+        bad_verbatim.unknown.insert(key.to_vec());
+    }
+
+    if bad.is_none() && bad_verbatim.is_none() {
+        return None;
+    }
+    Some(CallError::BadParameters {
+        normal: bad.map(Box::new),
+        verbatim: bad_verbatim.map(Box::new),
+    })
+}
+
+/// 找出位于参数值开头的槽位附加信息中的类名。
+fn find_slot_classes(input: &[u8], parsed: &[Event], value: &[CompiledItem]) -> Option<Vec<u8>> {
+    let Some(CompiledItem::SimpleEvents(range)) = value.first() else {
+        return None;
+    };
+
+    let infos = parsed[range.clone()]
+        .iter()
+        .map_while(crate::utils::as_attached_info)
+        .take_while(|info| info.target == AttachedInfoTarget::Slot);
+    crate::utils::join_attached_classes(input, infos)
+}
+
+/// 将大纲中的各项渲染为嵌套的有序列表。
+fn render_outline_items(buf: &mut Vec<u8>, items: &[crate::headings::OutlineItem]) {
+    buf.extend(b"<ol>");
+    for item in items {
+        let href = [b"#", item.id.as_bytes()].concat();
+        buf.extend(b"<li>");
+        crate::utils::render_eopening_tag(buf, b"a", &[(b"href", &href)]);
+        crate::utils::render_escaped_html_text(buf, item.text.as_bytes());
+        crate::utils::render_closing_tag(buf, b"a");
+        if !item.children.is_empty() {
+            render_outline_items(buf, &item.children);
+        }
+        buf.extend(b"</li>");
+    }
+    buf.extend(b"</ol>");
+}
+
 struct ProcessBlockElementMapperExtensionArgumentParameters<'a, 'b> {
     ext: &'b extensions::ExtensionElementMapper<'a>,
     key: &'b compiling::ArgumentKey<'a>,
@@ -519,3 +1168,31 @@ struct ProcessBlockElementMapperExtensionVerbatimArgumentParameters<'a, 'b> {
     seen: &'b mut HashSet<Vec<u8>>,
     bad: &'b mut Option<CallErrorBadParameters>,
 }
+
+/// 若 `compiled` 恰为单个段落，返回段落之内的事件在 `parsed` 中的范围。
+///
+/// 段落中只会出现行内事件，因此段落之内不会出现 [Event::ExitBlock]。
+fn single_paragraph_content(parsed: &[Event], compiled: &[CompiledItem]) -> Option<Range<usize>> {
+    let (Some(CompiledItem::SimpleEvents(first)), Some(CompiledItem::SimpleEvents(last))) =
+        (compiled.first(), compiled.last())
+    else {
+        return None;
+    };
+    if !matches!(parsed[first.start], Event::EnterParagraph(_)) {
+        return None;
+    }
+
+    let first_exit = compiled
+        .iter()
+        .filter_map(|item| match item {
+            CompiledItem::SimpleEvents(range) => Some(range.clone()),
+            _ => None,
+        })
+        .flatten()
+        .find(|i| matches!(parsed[*i], Event::ExitBlock(_)))?;
+    if first_exit != last.end - 1 {
+        return None;
+    }
+
+    Some(first.start + 1..first_exit)
+}
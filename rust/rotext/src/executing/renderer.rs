@@ -1,8 +1,12 @@
+use std::num::NonZeroUsize;
+
 use rotext_core::{
     Event,
-    events::{BlockWithId, VerbatimEscaping},
+    events::{AttachedInfoTarget, BlockWithId, TableCell, VerbatimEscaping},
 };
 
+use crate::headings::HeadingAnchor;
+
 macro_rules! write_data_block_id_attribute_if_applicable {
     ($self:ident, $buf:ident, $data:ident) => {
         #[cfg(feature = "block-id")]
@@ -16,6 +20,7 @@ macro_rules! write_data_block_id_attribute_if_applicable {
 
 pub struct NewRendererOptions<'a> {
     pub tag_name_map: &'a TagNameMap<'a>,
+    pub code_block_rendering: CodeBlockRendering,
 
     #[cfg(feature = "block-id")]
     pub should_include_block_ids: bool,
@@ -30,6 +35,7 @@ pub struct TagNameMap<'a> {
     pub code_block: &'a [u8],
 
     pub ref_link: &'a [u8],
+    pub external_link: &'a [u8],
     pub dicexp: &'a [u8],
     pub wiki_link: &'a [u8],
 }
@@ -44,14 +50,27 @@ impl TagNameMap<'_> {
             code_block: b"x-code-block",
 
             ref_link: b"x-ref-link",
+            external_link: b"x-external-link",
             dicexp: b"x-dicexp",
             wiki_link: b"x-wiki-link",
         }
     }
 }
 
+/// 代码块的渲染方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeBlockRendering {
+    /// 渲染为 [TagNameMap::code_block] 对应的元素，信息字符串与代码分别作为其
+    /// `info-string` 与 `content` 属性。
+    CustomElement,
+    /// 渲染为标准的 `<pre><code>`。信息字符串中第一处空白前的内容会成为 `code` 上以
+    /// `language-` 为前缀的类，代码作为转义后的文本内容。
+    PreCode,
+}
+
 pub struct Renderer<'a> {
     tag_name_map: &'a TagNameMap<'a>,
+    code_block_rendering: CodeBlockRendering,
 
     #[cfg(feature = "block-id")]
     with_block_id: bool,
@@ -61,9 +80,21 @@ pub struct StackEntryBox<'a>(StackEntry<'a>);
 
 enum StackEntry<'a> {
     Normal(&'a [u8]),
-    Table(TableState),
+    Table(Box<Table>),
     WikiLink,
 }
+struct Table {
+    state: TableState,
+    /// 各列的对齐方式，来自表格附加信息中的 `align`。
+    column_alignments: Vec<Option<Alignment>>,
+    /// 当前行中下一个单元格所在的列。
+    next_column: usize,
+    /// 各列仍被之前行中的单元格以 `rowspan` 占据的行数（包含当前行）。
+    occupied_rows: Vec<usize>,
+    /// 当前行中是否已经出现过数据单元格。
+    has_data_cell_in_row: bool,
+}
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum TableState {
     AtBeginning,
     InCaption,
@@ -71,9 +102,105 @@ enum TableState {
     InHeaderCell,
     InDataCell,
 }
-impl From<TableState> for StackEntry<'_> {
-    fn from(val: TableState) -> Self {
-        StackEntry::Table(val)
+#[derive(Clone, Copy)]
+enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl Table {
+    fn new(column_alignments: Vec<Option<Alignment>>) -> Self {
+        Self {
+            state: TableState::AtBeginning,
+            column_alignments,
+            next_column: 0,
+            occupied_rows: vec![],
+            has_data_cell_in_row: false,
+        }
+    }
+
+    fn start_row(&mut self) {
+        for rows in &mut self.occupied_rows {
+            *rows = rows.saturating_sub(1);
+        }
+        self.next_column = 0;
+        self.has_data_cell_in_row = false;
+    }
+
+    /// 为新的单元格分配列，返回该单元格所在列的对齐方式。`colspan` 与 `rowspan` 按照
+    /// HTML 的上限截断。
+    fn place_cell(
+        &mut self,
+        colspan: Option<NonZeroUsize>,
+        rowspan: Option<NonZeroUsize>,
+    ) -> Option<Alignment> {
+        while self
+            .occupied_rows
+            .get(self.next_column)
+            .is_some_and(|n| *n > 0)
+        {
+            self.next_column += 1;
+        }
+        let column = self.next_column;
+        let colspan = clamp_colspan(colspan).map_or(1, |n| n.get());
+        let end = column.saturating_add(colspan);
+        self.next_column = end;
+
+        if let Some(rowspan) = clamp_rowspan(rowspan).filter(|n| n.get() > 1) {
+            if self.occupied_rows.len() < end {
+                self.occupied_rows.resize(end, 0);
+            }
+            self.occupied_rows[column..end].fill(rowspan.get());
+        }
+
+        self.column_alignments.get(column).copied().flatten()
+    }
+}
+
+impl Alignment {
+    fn parse_list(value: &[u8]) -> Vec<Option<Alignment>> {
+        value
+            .split(|c| c.is_ascii_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(|v| match v {
+                b"left" => Some(Alignment::Left),
+                b"center" => Some(Alignment::Center),
+                b"right" => Some(Alignment::Right),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn class(self) -> &'static [u8] {
+        match self {
+            Alignment::Left => b"align-left",
+            Alignment::Center => b"align-center",
+            Alignment::Right => b"align-right",
+        }
+    }
+}
+
+/// 正在渲染的事件中的标题所对应的锚点。
+#[derive(Clone, Copy)]
+pub struct HeadingAnchors<'c> {
+    anchors: &'c [HeadingAnchor],
+    /// 正在渲染的事件在完整的事件列表中的起始位置。
+    offset: usize,
+}
+impl<'c> HeadingAnchors<'c> {
+    pub fn new(anchors: &'c [HeadingAnchor], offset: usize) -> Self {
+        Self { anchors, offset }
+    }
+
+    /// 返回正在渲染的事件中第 `i` 个事件所进入的标题的 ID。
+    fn find_id(&self, i: usize) -> Option<&'c [u8]> {
+        let index = self.offset + i;
+        let found = self
+            .anchors
+            .binary_search_by_key(&index, |anchor| anchor.index)
+            .ok()?;
+        Some(self.anchors[found].id.as_bytes())
     }
 }
 
@@ -81,6 +208,7 @@ impl<'a> Renderer<'a> {
     pub fn new(opts: NewRendererOptions<'a>) -> Self {
         Self {
             tag_name_map: opts.tag_name_map,
+            code_block_rendering: opts.code_block_rendering,
             #[cfg(feature = "block-id")]
             with_block_id: opts.should_include_block_ids,
         }
@@ -90,14 +218,15 @@ impl<'a> Renderer<'a> {
     pub fn render_events(
         &self,
         buf: &mut Vec<u8>,
-        input: &'a [u8],
+        input: &[u8],
         evs: &[Event],
+        anchors: HeadingAnchors,
         stack: &mut Vec<StackEntryBox>,
     ) {
         let mut i = 0;
 
         while i < evs.len() {
-            i = self.render_event(buf, input, evs, i, stack);
+            i = self.render_event(buf, input, evs, i, anchors, stack);
         }
     }
 
@@ -105,17 +234,20 @@ impl<'a> Renderer<'a> {
     fn render_event(
         &self,
         buf: &mut Vec<u8>,
-        input: &'a [u8],
+        input: &[u8],
         evs: &[Event],
         mut i: usize,
+        anchors: HeadingAnchors,
         stack: &mut Vec<StackEntryBox>,
     ) -> usize {
         let ev = &evs[i];
 
-        if let Some(()) = self.render_table_related_event(buf, ev, stack) {
+        if let Some(()) = self.render_table_related_event(buf, input, evs, i, stack) {
             return i + 1;
         }
 
+        let attached = find_attached_attributes(input, evs, i);
+
         #[rotext_internal_macros::ensure_cases_for_event(
                 prefix = Event,
                 group = Blend,
@@ -126,9 +258,19 @@ impl<'a> Renderer<'a> {
             Event::Raw(content) => render_raw_html(buf, &input[content.clone()]),
             Event::NewLine(_) => buf.extend(b"<br>"),
             Event::Text(content) | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                // 文本本身没有可以承载属性的元素，因此以 `<span>` 包裹附加于其上的文本。
+                let should_wrap = !attached.is_empty();
+                if should_wrap {
+                    buf.extend(b"<span");
+                    render_attached_attributes(buf, &attached);
+                    buf.push(b'>');
+                }
                 crate::utils::render_escaped_html_text(buf, &input[content.clone()]);
+                if should_wrap {
+                    buf.extend(b"</span>");
+                }
             }
-            Event::ExitBlock(_) | Event::ExitInline => {
+            Event::ExitBlock(_) | Event::ExitInline(_) => {
                 let top = stack.pop().unwrap();
                 match top.0 {
                     StackEntry::Normal(top) => {
@@ -147,117 +289,191 @@ impl<'a> Renderer<'a> {
             #[allow(unused_variables)]
             Event::ThematicBreak(data) => {
                 buf.extend(b"<hr");
+                render_attached_attributes(buf, &attached);
+                write_data_block_id_attribute_if_applicable!(self, buf, data);
+                buf.push(b'>');
+            }
+            Event::EnterParagraph(data) => self.push_simple_block(buf, stack, b"p", data, attached),
+            Event::EnterHeading1(data) => {
+                let id = anchors.find_id(i);
+                self.push_heading(buf, stack, b"h1", data, attached, id);
+            }
+            Event::EnterHeading2(data) => {
+                let id = anchors.find_id(i);
+                self.push_heading(buf, stack, b"h2", data, attached, id);
+            }
+            Event::EnterHeading3(data) => {
+                let id = anchors.find_id(i);
+                self.push_heading(buf, stack, b"h3", data, attached, id);
+            }
+            Event::EnterHeading4(data) => {
+                let id = anchors.find_id(i);
+                self.push_heading(buf, stack, b"h4", data, attached, id);
+            }
+            Event::EnterHeading5(data) => {
+                let id = anchors.find_id(i);
+                self.push_heading(buf, stack, b"h5", data, attached, id);
+            }
+            Event::EnterHeading6(data) => {
+                let id = anchors.find_id(i);
+                self.push_heading(buf, stack, b"h6", data, attached, id);
+            }
+            Event::EnterBlockQuote(data) => {
+                self.push_simple_block(buf, stack, b"blockquote", data, attached)
+            }
+            Event::EnterOrderedList(data) => {
+                buf.extend(b"<ol");
+                render_attached_attributes(buf, &attached);
+                if let Some(start) = data.start {
+                    buf.extend(br#" start=""#);
+                    crate::utils::write_usize(buf, start);
+                    buf.push(b'"');
+                }
+                if let Some(numbering) = data.numbering {
+                    buf.extend(br#" type=""#);
+                    buf.extend(numbering.as_type_attribute_value());
+                    buf.push(b'"');
+                }
                 write_data_block_id_attribute_if_applicable!(self, buf, data);
                 buf.push(b'>');
+                stack.push(StackEntryBox(StackEntry::Normal(b"ol")));
+            }
+            Event::EnterUnorderedList(data) => {
+                self.push_simple_block(buf, stack, b"ul", data, attached)
             }
-            Event::EnterParagraph(data) => self.push_simple_block(buf, stack, b"p", data),
-            Event::EnterHeading1(data) => self.push_simple_block(buf, stack, b"h1", data),
-            Event::EnterHeading2(data) => self.push_simple_block(buf, stack, b"h2", data),
-            Event::EnterHeading3(data) => self.push_simple_block(buf, stack, b"h3", data),
-            Event::EnterHeading4(data) => self.push_simple_block(buf, stack, b"h4", data),
-            Event::EnterHeading5(data) => self.push_simple_block(buf, stack, b"h5", data),
-            Event::EnterHeading6(data) => self.push_simple_block(buf, stack, b"h6", data),
-            Event::EnterBlockQuote(data) => self.push_simple_block(buf, stack, b"blockquote", data),
-            Event::EnterOrderedList(data) => self.push_simple_block(buf, stack, b"ol", data),
-            Event::EnterUnorderedList(data) => self.push_simple_block(buf, stack, b"ul", data),
-            Event::EnterListItem(data) => self.push_simple_block(buf, stack, b"li", data),
-            Event::EnterDescriptionList(data) => self.push_simple_block(buf, stack, b"dl", data),
-            Event::EnterDescriptionTerm(data) => self.push_simple_block(buf, stack, b"dt", data),
-            Event::EnterDescriptionDetails(data) => self.push_simple_block(buf, stack, b"dd", data),
             #[allow(unused_variables)]
+            Event::EnterListItem(data) => {
+                buf.extend(b"<li");
+                render_attached_attributes(buf, &attached);
+                write_data_block_id_attribute_if_applicable!(self, buf, data);
+                buf.push(b'>');
+                match data.checked {
+                    Some(false) => buf.extend(br#"<input type="checkbox" disabled>"#),
+                    Some(true) => buf.extend(br#"<input type="checkbox" checked disabled>"#),
+                    None => {}
+                }
+                stack.push(StackEntryBox(StackEntry::Normal(b"li")));
+            }
+            Event::EnterDescriptionList(data) => {
+                self.push_simple_block(buf, stack, b"dl", data, attached)
+            }
+            Event::EnterDescriptionTerm(data) => {
+                self.push_simple_block(buf, stack, b"dt", data, attached)
+            }
+            Event::EnterDescriptionDetails(data) => {
+                self.push_simple_block(buf, stack, b"dd", data, attached)
+            }
             Event::EnterCodeBlock(data) => {
-                buf.push(b'<');
-                buf.extend(self.tag_name_map.code_block);
-
-                buf.extend(br#" info-string=""#);
-                loop {
-                    i += 1;
-                    match &evs[i] {
-                        Event::Text(content)
-                        | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
-                            crate::utils::render_escaped_double_quoted_attribute_value(
-                                buf,
-                                &input[content.clone()],
-                            )
-                        }
-                        Event::IndicateCodeBlockCode => break,
-                        _ => unreachable!(),
+                i = match self.code_block_rendering {
+                    CodeBlockRendering::CustomElement => {
+                        self.render_code_block_as_custom_element(buf, input, evs, i, data, attached)
                     }
-                }
+                    CodeBlockRendering::PreCode => {
+                        self.render_code_block_as_pre_code(buf, input, evs, i, data, attached)
+                    }
+                };
+            }
+            #[allow(unused_variables)]
+            Event::EnterTable(data) => {
+                buf.extend(b"<table");
+                render_attached_attributes(buf, &attached);
+                write_data_block_id_attribute_if_applicable!(self, buf, data);
+                buf.push(b'>');
+                let alignments = crate::utils::find_attached_value(
+                    input,
+                    attached_infos(evs, i).filter_map(crate::utils::as_attached_info),
+                    b"align",
+                )
+                .map(Alignment::parse_list)
+                .unwrap_or_default();
+                stack.push(StackEntryBox(StackEntry::Table(Box::new(Table::new(
+                    alignments,
+                )))))
+            }
+            // 附加信息在渲染其所附加的元素时一并处理。
+            Event::BlockAttachedInfo(_) | Event::InlineAttachedInfo(_) => {}
+            Event::EnterCallOnTemplate(_)
+            | Event::EnterCallOnExtension(_)
+            | Event::IndicateCodeBlockCode(_)
+            | Event::IndicateTableCaption(_)
+            | Event::IndicateTableRow(_)
+            | Event::IndicateTableHeaderCell(_)
+            | Event::IndicateTableDataCell(_)
+            | Event::IndicateCallNormalArgument(_)
+            | Event::IndicateCallVerbatimArgument(_)
+            | Event::EnterParameterFilling(_)
+            | Event::IndicateParameterFillingFallback(_) => unreachable!(),
+            Event::RefLink(content) => {
+                let attrs = with_attached_attributes(
+                    vec![(b"address", &input[content.clone()])],
+                    &attached,
+                );
+                crate::utils::render_empty_element(buf, self.tag_name_map.ref_link, &attrs);
+            }
+            Event::ExternalLink(content) => {
+                let attrs = with_attached_attributes(
+                    vec![(b"address", &input[content.clone()])],
+                    &attached,
+                );
+                crate::utils::render_empty_element(buf, self.tag_name_map.external_link, &attrs);
+            }
+            Event::EnterDicexp(_) | Event::EnterAssigningDicexp(_) => {
+                buf.push(b'<');
+                buf.extend(self.tag_name_map.dicexp);
 
-                buf.extend(br#"" content=""#);
+                buf.extend(br#" code=""#);
                 loop {
                     i += 1;
-                    match &evs[i] {
-                        Event::Text(content)
-                        | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                    match evs.get(i) {
+                        Some(Event::Text(content)) => {
                             crate::utils::render_escaped_double_quoted_attribute_value(
                                 buf,
                                 &input[content.clone()],
                             )
                         }
-                        Event::NewLine(_) => {
+                        Some(Event::NewLine(_)) => {
                             buf.extend(b"&#10;");
                         }
-                        Event::ExitBlock(exit_block) => {
-                            #[cfg(feature = "block-id")]
-                            {
-                                debug_assert_eq!(data.id, exit_block.id);
-                            }
-
-                            break;
-                        }
-                        _ => unreachable!(),
+                        Some(Event::ExitInline(_)) | None => break,
+                        // 解析器不会在 Dicexp 之中产出其他事件，即使出现也不计入表达式。
+                        Some(_) => {}
                     }
                 }
-
                 buf.push(b'"');
 
-                write_data_block_id_attribute_if_applicable!(self, buf, data);
+                if let Event::EnterAssigningDicexp(dicexp) = ev {
+                    buf.extend(br#" name=""#);
+                    crate::utils::render_escaped_double_quoted_attribute_value(
+                        buf,
+                        &input[dicexp.name.clone()],
+                    );
+                    buf.push(b'"');
+                }
+
+                render_attached_attributes(buf, &attached);
 
                 buf.extend(b"></");
-                buf.extend(self.tag_name_map.code_block);
-                buf.push(b'>');
-            }
-            #[allow(unused_variables)]
-            Event::EnterTable(data) => {
-                buf.extend(b"<table");
-                write_data_block_id_attribute_if_applicable!(self, buf, data);
+                buf.extend(self.tag_name_map.dicexp);
                 buf.push(b'>');
-                stack.push(StackEntryBox(TableState::AtBeginning.into()))
             }
-            Event::EnterCallOnTemplate(_)
-            | Event::EnterCallOnExtension(_)
-            | Event::IndicateCodeBlockCode
-            | Event::IndicateTableCaption
-            | Event::IndicateTableRow
-            | Event::IndicateTableHeaderCell
-            | Event::IndicateTableDataCell
-            | Event::IndicateCallNormalArgument(_)
-            | Event::IndicateCallVerbatimArgument(_) => unreachable!(),
-            Event::RefLink(content) => {
-                crate::utils::render_empty_element(buf, self.tag_name_map.ref_link, &[(
-                    b"address",
-                    &input[content.clone()],
-                )]);
-            }
-            Event::Dicexp(content) => {
-                crate::utils::render_empty_element(buf, self.tag_name_map.dicexp, &[(
-                    b"code",
-                    &input[content.clone()],
-                )]);
-            }
-            Event::EnterCodeSpan => self.push_simple_inline(buf, stack, b"code"),
-            Event::EnterEmphasis => self.push_simple_inline(buf, stack, b"em"),
-            Event::EnterStrong => self.push_simple_inline(buf, stack, b"strong"),
-            Event::EnterStrikethrough => self.push_simple_inline(buf, stack, b"s"),
-            Event::EnterRuby => self.push_simple_inline(buf, stack, b"ruby"),
-            Event::EnterRubyText => self.push_simple_inline(buf, stack, b"rt"),
-            Event::EnterWikiLink(address) => {
-                crate::utils::render_eopening_tag(buf, self.tag_name_map.wiki_link, &[(
-                    b"address",
-                    &input[address.clone()],
-                )]);
+            Event::EnterCodeSpan(_) => self.push_simple_inline(buf, stack, b"code", attached),
+            Event::EnterEmphasis(_) => self.push_simple_inline(buf, stack, b"em", attached),
+            Event::EnterStrong(_) => self.push_simple_inline(buf, stack, b"strong", attached),
+            Event::EnterStrikethrough(_) => self.push_simple_inline(buf, stack, b"s", attached),
+            Event::EnterRuby(_) => self.push_simple_inline(buf, stack, b"ruby", attached),
+            Event::EnterRubyText(_) => self.push_simple_inline(buf, stack, b"rt", attached),
+            Event::EnterWikiLink(wiki_link) => {
+                let address = &input[wiki_link.address.clone()];
+                // 指向本文档中标题的地址，与标题的 ID 一样经过 `slugify`。
+                let fragment_address = address.strip_prefix(b"#").map(|fragment| {
+                    let slug = crate::headings::slugify(&String::from_utf8_lossy(fragment));
+                    [b"#", slug.as_bytes()].concat()
+                });
+                let attrs = with_attached_attributes(
+                    vec![(b"address", fragment_address.as_deref().unwrap_or(address))],
+                    &attached,
+                );
+                crate::utils::render_eopening_tag(buf, self.tag_name_map.wiki_link, &attrs);
                 stack.push(StackEntryBox(StackEntry::WikiLink));
             }
         }
@@ -268,51 +484,86 @@ impl<'a> Renderer<'a> {
     fn render_table_related_event(
         &self,
         buf: &mut Vec<u8>,
-        ev: &Event,
+        input: &[u8],
+        evs: &[Event],
+        i: usize,
         stack: &mut Vec<StackEntryBox>,
     ) -> Option<()> {
-        if let Some(StackEntryBox(StackEntry::Table(table_state))) = stack.last_mut() {
+        let ev = &evs[i];
+        if let Some(StackEntryBox(StackEntry::Table(table))) = stack.last_mut() {
             #[rotext_internal_macros::ensure_cases_for_event(
                     prefix = Event,
                     group = Blend,
                 )]
             match ev {
-                Event::IndicateTableRow => {
-                    match table_state {
+                Event::IndicateTableRow(_) => {
+                    match table.state {
                         TableState::AtBeginning => buf.extend(b"<tr>"),
                         TableState::InCaption => buf.extend(b"</caption><tr>"),
                         TableState::InRow => buf.extend(b"</tr><tr>"),
                         TableState::InHeaderCell => buf.extend(b"</th></tr><tr>"),
                         TableState::InDataCell => buf.extend(b"</td></tr><tr>"),
                     }
-                    *table_state = TableState::InRow;
+                    table.start_row();
+                    table.state = TableState::InRow;
                 }
-                Event::IndicateTableCaption => {
-                    match table_state {
-                        TableState::AtBeginning => buf.extend(b"<caption>"),
+                Event::IndicateTableCaption(_) => {
+                    match table.state {
+                        TableState::AtBeginning => buf.extend(b"<caption"),
                         _ => unreachable!(),
                     }
-                    *table_state = TableState::InCaption;
+                    let attached = find_attached_attributes(input, evs, i);
+                    render_attached_attributes(buf, &attached);
+                    buf.push(b'>');
+                    table.state = TableState::InCaption;
                 }
-                Event::IndicateTableHeaderCell => {
-                    match table_state {
-                        TableState::AtBeginning => buf.extend(b"<tr><th>"),
-                        TableState::InCaption => buf.extend(b"</caption><tr><th>"),
-                        TableState::InRow => buf.extend(b"<th>"),
-                        TableState::InHeaderCell => buf.extend(b"</th><th>"),
-                        TableState::InDataCell => buf.extend(b"</td><th>"),
+                Event::IndicateTableHeaderCell(cell) => {
+                    match table.state {
+                        TableState::AtBeginning => buf.extend(b"<tr><th"),
+                        TableState::InCaption => buf.extend(b"</caption><tr><th"),
+                        TableState::InRow => buf.extend(b"<th"),
+                        TableState::InHeaderCell => buf.extend(b"</th><th"),
+                        TableState::InDataCell => buf.extend(b"</td><th"),
+                    }
+                    if matches!(table.state, TableState::AtBeginning | TableState::InCaption) {
+                        table.start_row();
+                    }
+                    let alignment = table.place_cell(cell.colspan, cell.rowspan);
+                    render_table_cell_attached_attributes(
+                        buf,
+                        find_attached_attributes(input, evs, i),
+                        alignment,
+                    );
+                    render_table_cell_span_attributes(buf, cell);
+                    if table.has_data_cell_in_row || has_data_cell_in_rest_of_row(evs, i) {
+                        buf.extend(br#" scope="row""#);
+                    } else {
+                        buf.extend(br#" scope="col""#);
                     }
-                    *table_state = TableState::InHeaderCell;
+                    buf.push(b'>');
+                    table.state = TableState::InHeaderCell;
                 }
-                Event::IndicateTableDataCell => {
-                    match table_state {
-                        TableState::AtBeginning => buf.extend(b"<tr><td>"),
-                        TableState::InCaption => buf.extend(b"</caption><tr><td>"),
-                        TableState::InRow => buf.extend(b"<td>"),
-                        TableState::InHeaderCell => buf.extend(b"</th><td>"),
-                        TableState::InDataCell => buf.extend(b"</td><td>"),
+                Event::IndicateTableDataCell(cell) => {
+                    match table.state {
+                        TableState::AtBeginning => buf.extend(b"<tr><td"),
+                        TableState::InCaption => buf.extend(b"</caption><tr><td"),
+                        TableState::InRow => buf.extend(b"<td"),
+                        TableState::InHeaderCell => buf.extend(b"</th><td"),
+                        TableState::InDataCell => buf.extend(b"</td><td"),
                     };
-                    *table_state = TableState::InDataCell;
+                    if matches!(table.state, TableState::AtBeginning | TableState::InCaption) {
+                        table.start_row();
+                    }
+                    let alignment = table.place_cell(cell.colspan, cell.rowspan);
+                    render_table_cell_attached_attributes(
+                        buf,
+                        find_attached_attributes(input, evs, i),
+                        alignment,
+                    );
+                    render_table_cell_span_attributes(buf, cell);
+                    buf.push(b'>');
+                    table.has_data_cell_in_row = true;
+                    table.state = TableState::InDataCell;
                 }
                 Event::ExitBlock(_) => {
                     let top = stack.pop().unwrap().0;
@@ -322,31 +573,34 @@ impl<'a> Renderer<'a> {
                             buf.extend(top);
                             buf.push(b'>');
                         }
-                        StackEntry::Table(TableState::AtBeginning) => buf.extend(b"</table>"),
-                        StackEntry::Table(TableState::InCaption) => {
-                            buf.extend(b"</caption></table>")
-                        }
-                        StackEntry::Table(TableState::InRow) => buf.extend(b"</tr></table>"),
-                        StackEntry::Table(TableState::InHeaderCell) => {
-                            buf.extend(b"</th></tr></table>")
-                        }
-                        StackEntry::Table(TableState::InDataCell) => {
-                            buf.extend(b"</td></tr></table>")
-                        }
+                        StackEntry::Table(table) => match table.state {
+                            TableState::AtBeginning => buf.extend(b"</table>"),
+                            TableState::InCaption => buf.extend(b"</caption></table>"),
+                            TableState::InRow => buf.extend(b"</tr></table>"),
+                            TableState::InHeaderCell => buf.extend(b"</th></tr></table>"),
+                            TableState::InDataCell => buf.extend(b"</td></tr></table>"),
+                        },
                         _ => unreachable!(),
                     }
                 }
+                // 附加信息在渲染其所附加的元素时一并处理，不应因此开启新的单元格。
+                Event::BlockAttachedInfo(_) => {}
                 _ => {
-                    match table_state {
-                        TableState::AtBeginning => {
-                            buf.extend(b"<tr><td>");
-                            *table_state = TableState::InDataCell;
-                        }
-                        TableState::InRow => {
-                            buf.extend(b"<td>");
-                            *table_state = TableState::InDataCell;
+                    if matches!(table.state, TableState::AtBeginning | TableState::InRow) {
+                        if table.state == TableState::AtBeginning {
+                            buf.extend(b"<tr>");
+                            table.start_row();
                         }
-                        _ => {}
+                        buf.extend(b"<td");
+                        let alignment = table.place_cell(None, None);
+                        render_table_cell_attached_attributes(
+                            buf,
+                            AttachedAttributes::default(),
+                            alignment,
+                        );
+                        buf.push(b'>');
+                        table.has_data_cell_in_row = true;
+                        table.state = TableState::InDataCell;
                     }
                     return None;
                 }
@@ -357,15 +611,172 @@ impl<'a> Renderer<'a> {
         }
     }
 
+    /// 返回代码块结束处（即其 [Event::ExitBlock] 所在处）的索引。
+    #[allow(unused_variables)]
+    fn render_code_block_as_custom_element(
+        &self,
+        buf: &mut Vec<u8>,
+        input: &[u8],
+        evs: &[Event],
+        mut i: usize,
+        data: &BlockWithId,
+        attached: AttachedAttributes,
+    ) -> usize {
+        buf.push(b'<');
+        buf.extend(self.tag_name_map.code_block);
+
+        buf.extend(br#" info-string=""#);
+        loop {
+            i += 1;
+            match &evs[i] {
+                Event::Text(content)
+                | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                    crate::utils::render_escaped_double_quoted_attribute_value(
+                        buf,
+                        &input[content.clone()],
+                    )
+                }
+                Event::IndicateCodeBlockCode(_) => break,
+                _ => unreachable!(),
+            }
+        }
+
+        buf.extend(br#"" content=""#);
+        loop {
+            i += 1;
+            match &evs[i] {
+                Event::Text(content)
+                | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                    crate::utils::render_escaped_double_quoted_attribute_value(
+                        buf,
+                        &input[content.clone()],
+                    )
+                }
+                Event::NewLine(_) => {
+                    buf.extend(b"&#10;");
+                }
+                Event::ExitBlock(exit_block) => {
+                    #[cfg(feature = "block-id")]
+                    {
+                        debug_assert_eq!(data.id, exit_block.id);
+                    }
+
+                    break;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        buf.push(b'"');
+
+        render_attached_attributes(buf, &attached);
+        write_data_block_id_attribute_if_applicable!(self, buf, data);
+
+        buf.extend(b"></");
+        buf.extend(self.tag_name_map.code_block);
+        buf.push(b'>');
+
+        i
+    }
+
+    /// 返回代码块结束处（即其 [Event::ExitBlock] 所在处）的索引。
+    #[allow(unused_variables)]
+    fn render_code_block_as_pre_code(
+        &self,
+        buf: &mut Vec<u8>,
+        input: &[u8],
+        evs: &[Event],
+        mut i: usize,
+        data: &BlockWithId,
+        attached: AttachedAttributes,
+    ) -> usize {
+        let mut info_string: Vec<u8> = vec![];
+        loop {
+            i += 1;
+            match &evs[i] {
+                Event::Text(content)
+                | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                    info_string.extend(&input[content.clone()]);
+                }
+                Event::IndicateCodeBlockCode(_) => break,
+                _ => unreachable!(),
+            }
+        }
+
+        buf.extend(b"<pre");
+        render_attached_attributes(buf, &attached);
+        write_data_block_id_attribute_if_applicable!(self, buf, data);
+        buf.extend(b"><code");
+        let language = info_string
+            .split(|c| c.is_ascii_whitespace())
+            .next()
+            .unwrap_or_default();
+        if !language.is_empty() {
+            buf.extend(br#" class="language-"#);
+            crate::utils::render_escaped_double_quoted_attribute_value(buf, language);
+            buf.push(b'"');
+        }
+        buf.push(b'>');
+
+        loop {
+            i += 1;
+            match &evs[i] {
+                Event::Text(content)
+                | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                    crate::utils::render_escaped_preformatted_text(buf, &input[content.clone()]);
+                }
+                Event::NewLine(_) => buf.push(b'\n'),
+                Event::ExitBlock(exit_block) => {
+                    #[cfg(feature = "block-id")]
+                    {
+                        debug_assert_eq!(data.id, exit_block.id);
+                    }
+
+                    break;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        buf.extend(b"</code></pre>");
+
+        i
+    }
+
     fn push_simple_block(
         &self,
         buf: &mut Vec<u8>,
         stack: &mut Vec<StackEntryBox>,
         tag_name: &'static [u8],
         #[allow(unused_variables)] data: &BlockWithId,
+        attached: AttachedAttributes,
+    ) {
+        buf.push(b'<');
+        buf.extend(tag_name);
+        render_attached_attributes(buf, &attached);
+        write_data_block_id_attribute_if_applicable!(self, buf, data);
+        buf.push(b'>');
+
+        stack.push(StackEntryBox(StackEntry::Normal(tag_name)));
+    }
+
+    fn push_heading(
+        &self,
+        buf: &mut Vec<u8>,
+        stack: &mut Vec<StackEntryBox>,
+        tag_name: &'static [u8],
+        #[allow(unused_variables)] data: &BlockWithId,
+        attached: AttachedAttributes,
+        id: Option<&[u8]>,
     ) {
         buf.push(b'<');
         buf.extend(tag_name);
+        if let Some(id) = id {
+            buf.extend(br#" id=""#);
+            crate::utils::render_escaped_double_quoted_attribute_value(buf, id);
+            buf.push(b'"');
+        }
+        render_attached_attributes(buf, &attached);
         write_data_block_id_attribute_if_applicable!(self, buf, data);
         buf.push(b'>');
 
@@ -377,15 +788,181 @@ impl<'a> Renderer<'a> {
         buf: &mut Vec<u8>,
         stack: &mut Vec<StackEntryBox>,
         tag_name: &'static [u8],
+        attached: AttachedAttributes,
     ) {
         buf.push(b'<');
         buf.extend(tag_name);
+        render_attached_attributes(buf, &attached);
         buf.push(b'>');
 
         stack.push(StackEntryBox(StackEntry::Normal(tag_name)));
     }
 }
 
+/// 返回附加于 `evs[i]` 的附加信息事件，包括其之前以其为目标的附加信息，以及（若其有槽位）
+/// 其之后属于该槽位的附加信息。
+fn attached_infos(evs: &[Event], i: usize) -> impl Iterator<Item = &Event> + Clone {
+    let is_attached_info_with_target = |ev: &Event, expected: AttachedInfoTarget| {
+        crate::utils::as_attached_info(ev).is_some_and(|info| info.target == expected)
+    };
+
+    let mut start = i;
+    while start > 0 && is_attached_info_with_target(&evs[start - 1], AttachedInfoTarget::Following)
+    {
+        start -= 1;
+    }
+
+    let has_slot = matches!(
+        evs[i],
+        Event::EnterBlockQuote(_)
+            | Event::EnterListItem(_)
+            | Event::EnterDescriptionTerm(_)
+            | Event::EnterDescriptionDetails(_)
+            | Event::IndicateTableCaption(_)
+            | Event::IndicateTableHeaderCell(_)
+            | Event::IndicateTableDataCell(_)
+            | Event::EnterEmphasis(_)
+            | Event::EnterStrong(_)
+            | Event::EnterStrikethrough(_)
+            | Event::EnterRuby(_)
+            | Event::EnterRubyText(_)
+            | Event::EnterWikiLink(_)
+    );
+    let mut end = i + 1;
+    if has_slot {
+        while end < evs.len() && is_attached_info_with_target(&evs[end], AttachedInfoTarget::Slot) {
+            end += 1;
+        }
+    }
+
+    evs[start..i].iter().chain(&evs[i + 1..end])
+}
+
+/// 附加于某个元素之上、需要作为其 HTML 属性输出的内容。
+#[derive(Default)]
+struct AttachedAttributes<'b> {
+    classes: Option<Vec<u8>>,
+    /// 见 [crate::utils::collect_attached_attributes]。
+    others: Vec<(&'b [u8], &'b [u8])>,
+}
+impl AttachedAttributes<'_> {
+    fn is_empty(&self) -> bool {
+        self.classes.is_none() && self.others.is_empty()
+    }
+}
+
+fn find_attached_attributes<'b>(
+    input: &'b [u8],
+    evs: &'b [Event],
+    i: usize,
+) -> AttachedAttributes<'b> {
+    let infos = attached_infos(evs, i).filter_map(crate::utils::as_attached_info);
+    AttachedAttributes {
+        classes: crate::utils::join_attached_classes(input, infos.clone()),
+        others: crate::utils::collect_attached_attributes(input, infos),
+    }
+}
+
+/// 从表格单元格 `evs[i]` 开始向后查看，判断同一行中之后是否还有数据单元格。
+///
+/// 只能看到当前这段事件，被调用分隔开的后续部分不在考虑范围内。
+fn has_data_cell_in_rest_of_row(evs: &[Event], i: usize) -> bool {
+    let mut depth = 0_usize;
+    for ev in &evs[i + 1..] {
+        match ev {
+            Event::EnterParagraph(_)
+            | Event::EnterHeading1(_)
+            | Event::EnterHeading2(_)
+            | Event::EnterHeading3(_)
+            | Event::EnterHeading4(_)
+            | Event::EnterHeading5(_)
+            | Event::EnterHeading6(_)
+            | Event::EnterBlockQuote(_)
+            | Event::EnterOrderedList(_)
+            | Event::EnterUnorderedList(_)
+            | Event::EnterListItem(_)
+            | Event::EnterDescriptionList(_)
+            | Event::EnterDescriptionTerm(_)
+            | Event::EnterDescriptionDetails(_)
+            | Event::EnterCodeBlock(_)
+            | Event::EnterTable(_) => depth += 1,
+            Event::ExitBlock(_) if depth == 0 => return false,
+            Event::ExitBlock(_) => depth -= 1,
+            Event::IndicateTableRow(_) if depth == 0 => return false,
+            Event::IndicateTableDataCell(_) if depth == 0 => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn render_attached_attributes(buf: &mut Vec<u8>, attached: &AttachedAttributes) {
+    if let Some(classes) = &attached.classes {
+        buf.extend(br#" class=""#);
+        crate::utils::render_escaped_double_quoted_attribute_value(buf, classes);
+        buf.push(b'"');
+    }
+    for (name, value) in &attached.others {
+        buf.push(b' ');
+        buf.extend(*name);
+        buf.extend(br#"=""#);
+        crate::utils::render_escaped_double_quoted_attribute_value(buf, value);
+        buf.push(b'"');
+    }
+}
+
+fn render_table_cell_attached_attributes(
+    buf: &mut Vec<u8>,
+    mut attached: AttachedAttributes,
+    alignment: Option<Alignment>,
+) {
+    attached.classes = match (attached.classes, alignment) {
+        (classes, None) => classes,
+        (None, Some(alignment)) => Some(alignment.class().to_vec()),
+        (Some(mut classes), Some(alignment)) => {
+            classes.push(b' ');
+            classes.extend(alignment.class());
+            Some(classes)
+        }
+    };
+    render_attached_attributes(buf, &attached);
+}
+
+fn clamp_colspan(colspan: Option<NonZeroUsize>) -> Option<NonZeroUsize> {
+    colspan.map(|n| n.min(NonZeroUsize::new(TableCell::MAX_COLSPAN).unwrap()))
+}
+
+fn clamp_rowspan(rowspan: Option<NonZeroUsize>) -> Option<NonZeroUsize> {
+    rowspan.map(|n| n.min(NonZeroUsize::new(TableCell::MAX_ROWSPAN).unwrap()))
+}
+
+fn render_table_cell_span_attributes(buf: &mut Vec<u8>, cell: &TableCell) {
+    let spans = [
+        (&b"colspan"[..], clamp_colspan(cell.colspan)),
+        (b"rowspan", clamp_rowspan(cell.rowspan)),
+    ];
+    for (name, value) in spans {
+        if let Some(value) = value {
+            buf.push(b' ');
+            buf.extend(name);
+            buf.extend(br#"=""#);
+            crate::utils::write_usize(buf, value.get());
+            buf.push(b'"');
+        }
+    }
+}
+
+fn with_attached_attributes<'b>(
+    mut attrs: Vec<(&'b [u8], &'b [u8])>,
+    attached: &'b AttachedAttributes,
+) -> Vec<(&'b [u8], &'b [u8])> {
+    if let Some(classes) = &attached.classes {
+        attrs.push((b"class", classes));
+    }
+    attrs.extend(&attached.others);
+    attrs
+}
+
 fn render_raw_html(buf: &mut Vec<u8>, input: &[u8]) {
     buf.extend(input);
 }
@@ -0,0 +1,7 @@
+use std::borrow::Cow;
+
+/// 为嵌入包含提供模板。
+pub trait TemplateProvider {
+    /// 获取名为 `name` 的模板的源码。不存在该模板时返回 `None`。
+    fn get_template_source(&self, name: &[u8]) -> Option<Cow<'_, [u8]>>;
+}
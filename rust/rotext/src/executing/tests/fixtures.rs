@@ -1,10 +1,14 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use crate::executing::extensions::{
     Extension, ExtensionElementMapper, ExtensionElementMapperParameter,
     ExtensionElementMapperParameterMappingTo, ExtensionElementMapperVerbatimParameter,
     ExtensionElementMapperVerbatimParameterMappingTo, ParameterWrapper,
 };
+use crate::executing::templates::TemplateProvider;
 
 pub fn new_block_extension_map() -> HashMap<&'static [u8], Extension<'static>> {
     let mut map: HashMap<&'static [u8], Extension<'static>> = HashMap::new();
@@ -65,6 +69,10 @@ pub fn new_block_extension_map() -> HashMap<&'static [u8], Extension<'static>> {
         })),
     );
 
+    map.insert(b"Footnotes", Extension::FootnoteList);
+    map.insert(b"TableOfContents", Extension::TableOfContents);
+    map.insert(b"Footnote", Extension::FootnoteReference);
+
     map.insert(b"Alias", Extension::Alias { to: b"AllOptional" });
 
     map
@@ -129,11 +137,38 @@ pub fn new_inline_extension_map() -> HashMap<&'static [u8], Extension<'static>>
         })),
     );
 
+    map.insert(b"Footnote", Extension::FootnoteReference);
+    map.insert(b"Footnotes", Extension::FootnoteList);
+
     map.insert(b"Alias", Extension::Alias { to: b"AllOptional" });
 
     map
 }
 
+pub struct TemplateMap(HashMap<&'static [u8], &'static [u8]>);
+impl TemplateProvider for TemplateMap {
+    fn get_template_source(&self, name: &[u8]) -> Option<Cow<'_, [u8]>> {
+        self.0.get(name).map(|source| Cow::Borrowed(*source))
+    }
+}
+
+pub fn new_template_map() -> TemplateMap {
+    let mut map: HashMap<&'static [u8], &'static [u8]> = HashMap::new();
+
+    map.insert(b"Greeting", b"Hello, [*world*]!");
+    map.insert(b"Nested", b"{{Greeting}}\n---");
+    map.insert(b"WithExtension", b"{{#AllOptional||content}}");
+    map.insert(b"Recursive", b"{{Recursive}}");
+    map.insert(b"Param", b"{{{x||fallback}}}");
+    map.insert(b"Unnamed", b"{{{1}}}");
+    map.insert(b"Outer", b"{{Param||x={{{y||outer fallback}}}}}");
+    map.insert(b"MultiLine", b"a\n\n== b ==\n[{Greeting}]");
+    map.insert(b"InlineRecursive", b"[{InlineRecursive}]");
+    map.insert(b"InlineParam", b"a[{{x|fallback}}]b");
+
+    TemplateMap(map)
+}
+
 fn new_parametr_map()
 -> HashMap<&'static [u8], ParameterWrapper<'static, ExtensionElementMapperParameter<'static>>> {
     let mut map: HashMap<&'static [u8], ParameterWrapper<ExtensionElementMapperParameter>> =
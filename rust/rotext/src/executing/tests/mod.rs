@@ -71,16 +71,46 @@ fn it_works_in_block_phase_for_simple_events() {
                         (ExitBlock(..)),
                     ],
                     concat!(
-                        "<h1><p>x1</p></h1>",
-                        "<h2><p>x2</p></h2>",
-                        "<h3><p>x3</p></h3>",
-                        "<h4><p>x4</p></h4>",
-                        "<h5><p>x5</p></h5>",
-                        "<h6><p>x6</p></h6>",
+                        r#"<h1 id="x1"><p>x1</p></h1>"#,
+                        r#"<h2 id="x2"><p>x2</p></h2>"#,
+                        r#"<h3 id="x3"><p>x3</p></h3>"#,
+                        r#"<h4 id="x4"><p>x4</p></h4>"#,
+                        r#"<h5 id="x5"><p>x5</p></h5>"#,
+                        r#"<h6 id="x6"><p>x6</p></h6>"#,
                     ),
                 ),
             ],
         },
+        GroupedCases {
+            group: "标题>锚点",
+            cases: vec![
+                case!(
+                    "a:0/a:4/A:8",
+                    [
+                        (EnterHeading1(..)),
+                        (Text(0..1)),
+                        (ExitBlock(..)),
+                        (EnterHeading2(..)),
+                        (Text(4..5)),
+                        (ExitBlock(..)),
+                        (EnterHeading2(..)),
+                        (Text(8..9)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<h1 id="a">a</h1><h2 id="a-1">a</h2><h2 id="a-2">A</h2>"#,
+                ),
+                case!(
+                    " .foo:0/Hello, 世界!:8",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterHeading1(..)),
+                        (Text(8..22)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<h1 id="hello-世界" class="foo">Hello, 世界!</h1>"#,
+                ),
+            ],
+        },
         GroupedCases {
             group: "item-likes",
             cases: vec![
@@ -160,6 +190,64 @@ fn it_works_in_block_phase_for_simple_events() {
                 ),
             ],
         },
+        GroupedCases {
+            group: "item-likes>有序列表",
+            cases: vec![
+                case!(
+                    "a",
+                    [
+                        (EnterOrderedList(.., start = 3)),
+                        (EnterListItem(..)),
+                        (EnterParagraph(..)),
+                        (Text(0..1)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<ol start="3"><li><p>a</p></li></ol>"#,
+                ),
+                case!(
+                    " .foo:0/a:8",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterOrderedList(.., start = 0, type = LowerRoman)),
+                        (EnterListItem(..)),
+                        (EnterParagraph(..)),
+                        (Text(8..9)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<ol class="foo" start="0" type="i"><li><p>a</p></li></ol>"#,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "item-likes>任务列表",
+            cases: vec![case!(
+                "ab",
+                [
+                    (EnterUnorderedList(..)),
+                    (EnterListItem(.., checked = false)),
+                    (EnterParagraph(..)),
+                    (Text(0..1)),
+                    (ExitBlock(..)),
+                    (ExitBlock(..)),
+                    (EnterListItem(.., checked = true)),
+                    (EnterParagraph(..)),
+                    (Text(1..2)),
+                    (ExitBlock(..)),
+                    (ExitBlock(..)),
+                    (ExitBlock(..)),
+                ],
+                concat!(
+                    "<ul>",
+                    r#"<li><input type="checkbox" disabled><p>a</p></li>"#,
+                    r#"<li><input type="checkbox" checked disabled><p>b</p></li>"#,
+                    "</ul>",
+                ),
+            )],
+        },
         GroupedCases {
             group: "代码块",
             cases: vec![case!(
@@ -176,6 +264,36 @@ fn it_works_in_block_phase_for_simple_events() {
                 r#"<x-code-block info-string="info" content="code&#10;line 2"></x-code-block>"#,
             )],
         },
+        GroupedCases {
+            group: "代码块>pre+code",
+            cases: vec![
+                case!(
+                    @pre_code,
+                    "rust ignored:0/<a> & b:15/line 2:26",
+                    [
+                        (EnterCodeBlock(..)),
+                        (Text(0..12)),
+                        (IndicateCodeBlockCode()),
+                        (Text(15..22)),
+                        (NewLine(..)),
+                        (Text(26..32)),
+                        (ExitBlock(..)),
+                    ],
+                    "<pre><code class=\"language-rust\">&lt;a> &amp; b\nline 2</code></pre>",
+                ),
+                case!(
+                    @pre_code,
+                    "code:0",
+                    [
+                        (EnterCodeBlock(..)),
+                        (IndicateCodeBlockCode()),
+                        (Text(0..4)),
+                        (ExitBlock(..)),
+                    ],
+                    "<pre><code>code</code></pre>",
+                ),
+            ],
+        },
         GroupedCases {
             group: "表格",
             cases: vec![
@@ -207,7 +325,7 @@ fn it_works_in_block_phase_for_simple_events() {
                         (IndicateTableHeaderCell()),
                         (ExitBlock(..)),
                     ],
-                    "<table><tr><th></th></tr></table>",
+                    r#"<table><tr><th scope="col"></th></tr></table>"#,
                 ),
                 case!(
                     "",
@@ -217,7 +335,7 @@ fn it_works_in_block_phase_for_simple_events() {
                         (IndicateTableHeaderCell()),
                         (ExitBlock(..)),
                     ],
-                    "<table><tr><th></th></tr></table>",
+                    r#"<table><tr><th scope="col"></th></tr></table>"#,
                 ),
                 case!(
                     "",
@@ -260,7 +378,7 @@ fn it_works_in_block_phase_for_simple_events() {
                         (ExitBlock(..)),
                         (ExitBlock(..)),
                     ],
-                    "<table><tr><th><p>data</p></th></tr></table>",
+                    r#"<table><tr><th scope="col"><p>data</p></th></tr></table>"#,
                 ),
                 case!(
                     "ABCD",
@@ -286,13 +404,158 @@ fn it_works_in_block_phase_for_simple_events() {
                     ],
                     concat!(
                         "<table>",
-                        "<tr><th><p>A</p></th><th><p>B</p></th></tr>",
+                        r#"<tr><th scope="col"><p>A</p></th><th scope="col"><p>B</p></th></tr>"#,
                         "<tr><td><p>C</p></td><td><p>D</p></td></tr>",
                         "</table>",
                     ),
                 ),
             ],
         },
+        GroupedCases {
+            group: "表格>合并单元格",
+            cases: vec![
+                case!(
+                    "ABC",
+                    [
+                        (EnterTable(..)),
+                        (IndicateTableHeaderCell(2, 0)),
+                        (EnterParagraph(..)),
+                        (Text(0..1)),
+                        (ExitBlock(..)),
+                        (IndicateTableRow()),
+                        (IndicateTableDataCell(0, 2)),
+                        (EnterParagraph(..)),
+                        (Text(1..2)),
+                        (ExitBlock(..)),
+                        (IndicateTableDataCell(3, 4)),
+                        (EnterParagraph(..)),
+                        (Text(2..3)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    concat!(
+                        "<table>",
+                        r#"<tr><th colspan="2" scope="col"><p>A</p></th></tr>"#,
+                        r#"<tr><td rowspan="2"><p>B</p></td><td colspan="3" rowspan="4"><p>C</p></td></tr>"#,
+                        "</table>",
+                    ),
+                ),
+                case!(
+                    "ABC",
+                    [
+                        (EnterTable(..)),
+                        (IndicateTableDataCell(100000000000, 100000000000)),
+                        (EnterParagraph(..)),
+                        (Text(0..1)),
+                        (ExitBlock(..)),
+                        (IndicateTableDataCell(18446744073709551615, 2)),
+                        (EnterParagraph(..)),
+                        (Text(1..2)),
+                        (ExitBlock(..)),
+                        (IndicateTableRow()),
+                        (IndicateTableDataCell()),
+                        (EnterParagraph(..)),
+                        (Text(2..3)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    concat!(
+                        "<table>",
+                        r#"<tr><td colspan="1000" rowspan="65534"><p>A</p></td><td colspan="1000" rowspan="2"><p>B</p></td></tr>"#,
+                        r#"<tr><td><p>C</p></td></tr>"#,
+                        "</table>",
+                    ),
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "表格>列对齐与表头范围",
+            cases: vec![
+                case!(
+                    r#"align="center - right"ABCDEFGHI"#,
+                    [
+                        (BlockAttachedInfo(following, 0..22)),
+                        (EnterTable(..)),
+                        (IndicateTableHeaderCell()),
+                        (EnterParagraph(..)),
+                        (Text(22..23)),
+                        (ExitBlock(..)),
+                        (IndicateTableHeaderCell()),
+                        (EnterParagraph(..)),
+                        (Text(23..24)),
+                        (ExitBlock(..)),
+                        (IndicateTableHeaderCell()),
+                        (EnterParagraph(..)),
+                        (Text(24..25)),
+                        (ExitBlock(..)),
+                        (IndicateTableRow()),
+                        (IndicateTableDataCell(0, 2)),
+                        (EnterParagraph(..)),
+                        (Text(25..26)),
+                        (ExitBlock(..)),
+                        (IndicateTableDataCell()),
+                        (EnterParagraph(..)),
+                        (Text(26..27)),
+                        (ExitBlock(..)),
+                        (IndicateTableRow()),
+                        (IndicateTableDataCell()),
+                        (EnterParagraph(..)),
+                        (Text(27..28)),
+                        (ExitBlock(..)),
+                        (IndicateTableDataCell()),
+                        (EnterParagraph(..)),
+                        (Text(28..29)),
+                        (ExitBlock(..)),
+                        (IndicateTableRow()),
+                        (IndicateTableHeaderCell()),
+                        (EnterParagraph(..)),
+                        (Text(29..30)),
+                        (ExitBlock(..)),
+                        (IndicateTableDataCell()),
+                        (EnterParagraph(..)),
+                        (Text(30..31)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    concat!(
+                        "<table>",
+                        r#"<tr><th class="align-center" scope="col"><p>A</p></th>"#,
+                        r#"<th scope="col"><p>B</p></th>"#,
+                        r#"<th class="align-right" scope="col"><p>C</p></th></tr>"#,
+                        r#"<tr><td class="align-center" rowspan="2"><p>D</p></td><td><p>E</p></td></tr>"#,
+                        r#"<tr><td><p>F</p></td><td class="align-right"><p>G</p></td></tr>"#,
+                        r#"<tr><th class="align-center" scope="row"><p>H</p></th><td><p>I</p></td></tr>"#,
+                        "</table>",
+                    ),
+                ),
+                case!(
+                    r#"align="right":0/ .bar:16/a:25"#,
+                    [
+                        (BlockAttachedInfo(following, 0..13)),
+                        (EnterTable(..)),
+                        (IndicateTableDataCell()),
+                        (BlockAttachedInfo(slot, 16..21)),
+                        (EnterParagraph(..)),
+                        (Text(25..26)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<table><tr><td class="bar align-right"><p>a</p></td></tr></table>"#,
+                ),
+                case!(
+                    r#"align="right"a"#,
+                    [
+                        (BlockAttachedInfo(following, 0..13)),
+                        (EnterTable(..)),
+                        (EnterParagraph(..)),
+                        (Text(13..14)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<table><tr><td class="align-right"><p>a</p></td></tr></table>"#,
+                ),
+            ],
+        },
         GroupedCases {
             group: "表格>captions",
             cases: vec![
@@ -346,7 +609,7 @@ fn it_works_in_block_phase_for_simple_events() {
                         (IndicateTableHeaderCell()),
                         (ExitBlock(..)),
                     ],
-                    "<table><caption><p>CAPTION</p></caption><tr><th></th></tr></table>",
+                    r#"<table><caption><p>CAPTION</p></caption><tr><th scope="col"></th></tr></table>"#,
                 ),
                 case!(
                     "CAPTION",
@@ -399,47 +662,167 @@ fn it_works_in_block_phase_for_simple_events() {
             )],
         },
         GroupedCases {
-            group: "Wiki链接",
-            cases: vec![case!(
-                "ADDR:0/title:10",
-                [
-                    (EnterParagraph(..)),
-                    (EnterWikiLink(0..4)),
-                    (Text(7..12)),
-                    (@inline ExitInline(..)),
-                    (ExitBlock(..)),
-                ],
-                r#"<p><x-wiki-link address="ADDR">title</x-wiki-link></p>"#,
-            )],
-        },
-        GroupedCases {
-            group: "XSS",
+            group: "附加信息",
             cases: vec![
                 case!(
-                    r#"<script>"#,
-                    [(EnterParagraph(..)), (Text(0..8)), (ExitBlock(..)),],
-                    r#"<p>&lt;script></p>"#,
+                    " .foo:0/a:8",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterParagraph(..)),
+                        (Text(8..9)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p class="foo">a</p>"#,
                 ),
                 case!(
-                    r#"">"#,
+                    r#" .foo k="v" .bar:0/ .baz:19/a:28"#,
+                    [
+                        (BlockAttachedInfo(following, 0..16)),
+                        (BlockAttachedInfo(following, 19..24)),
+                        (ThematicBreak(..)),
+                        (EnterParagraph(..)),
+                        (Text(28..29)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<hr class="foo bar baz"><p>a</p>"#,
+                ),
+                case!(
+                    r#" .foo data-x="1" title="t" k="v" data-x="2":0/a:46"#,
+                    [
+                        (BlockAttachedInfo(following, 0..43)),
+                        (EnterParagraph(..)),
+                        (Text(46..47)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p class="foo" data-x="2" title="t">a</p>"#,
+                ),
+                case!(
+                    r#" data-block-id="1" data-X="2" data-="3":0/a:42"#,
+                    [
+                        (BlockAttachedInfo(following, 0..39)),
+                        (EnterParagraph(..)),
+                        (Text(42..43)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>a</p>"#,
+                ),
+                case!(
+                    " .foo:0/ .bar:8/a:16",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterUnorderedList(..)),
+                        (EnterListItem(..)),
+                        (BlockAttachedInfo(slot, 8..13)),
+                        (EnterParagraph(..)),
+                        (Text(16..17)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<ul class="foo"><li class="bar"><p>a</p></li></ul>"#,
+                ),
+                case!(
+                    " .foo:0/ .bar:8/a:16/b:21",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterTable(..)),
+                        (IndicateTableHeaderCell()),
+                        (BlockAttachedInfo(slot, 8..13)),
+                        (EnterParagraph(..)),
+                        (Text(16..17)),
+                        (ExitBlock(..)),
+                        (IndicateTableDataCell()),
+                        (EnterParagraph(..)),
+                        (Text(21..22)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    concat!(
+                        r#"<table class="foo">"#,
+                        r#"<tr><th class="bar" scope="row"><p>a</p></th><td><p>b</p></td></tr>"#,
+                        "</table>"
+                    ),
+                ),
+                case!(
+                    " .foo:0/js:8/a:13",
                     [
+                        (BlockAttachedInfo(following, 0..5)),
                         (EnterCodeBlock(..)),
-                        (Text(0..2)),
+                        (Text(8..10)),
                         (IndicateCodeBlockCode()),
-                        (Text(0..2)),
+                        (Text(13..14)),
                         (ExitBlock(..)),
                     ],
-                    r#"<x-code-block info-string="&quot;>" content="&quot;>"></x-code-block>"#,
+                    r#"<x-code-block info-string="js" content="a" class="foo"></x-code-block>"#,
                 ),
             ],
         },
-    ];
-
-    run_cases(table);
-}
-
-#[cfg(feature = "block-id")]
-#[test]
+        GroupedCases {
+            group: "Wiki链接",
+            cases: vec![
+                case!(
+                    "ADDR:0/title:10",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterWikiLink(0..4)),
+                        (Text(7..12)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p><x-wiki-link address="ADDR">title</x-wiki-link></p>"#,
+                ),
+                case!(
+                    "#Hello, World!:0/t:17",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterWikiLink(0..14)),
+                        (Text(17..18)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><x-wiki-link address="#hello-world">t</x-wiki-link></p>"##,
+                ),
+                case!(
+                    "#里边还可以嵌套块级元素。:0/t:40",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterWikiLink(0..37)),
+                        (Text(40..41)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><x-wiki-link address="#里边还可以嵌套块级元素">t</x-wiki-link></p>"##,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "XSS",
+            cases: vec![
+                case!(
+                    r#"<script>"#,
+                    [(EnterParagraph(..)), (Text(0..8)), (ExitBlock(..)),],
+                    r#"<p>&lt;script></p>"#,
+                ),
+                case!(
+                    r#"">"#,
+                    [
+                        (EnterCodeBlock(..)),
+                        (Text(0..2)),
+                        (IndicateCodeBlockCode()),
+                        (Text(0..2)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<x-code-block info-string="&quot;>" content="&quot;>"></x-code-block>"#,
+                ),
+            ],
+        },
+    ];
+
+    run_cases(table);
+}
+
+#[cfg(feature = "block-id")]
+#[test]
 fn it_works_in_block_phase_for_simple_events_with_feature_block_id() {
     let table: Vec<GroupedCases<_>> = vec![GroupedCases {
         group: "基础",
@@ -477,7 +860,7 @@ fn it_works_in_block_phase_for_simple_events_with_feature_block_id() {
                 ],
                 concat!(
                     r#"<table data-block-id="1">"#,
-                    r#"<tr><th><p data-block-id="2">foo</p></th></tr>"#,
+                    r#"<tr><th scope="col"><p data-block-id="2">foo</p></th></tr>"#,
                     r#"<tr><td><p data-block-id="3">bar</p></td></tr>"#,
                     "</table>"
                 ),
@@ -629,6 +1012,138 @@ fn it_works_in_block_phase_for_events_involving_calls() {
                 ),
             ],
         },
+        GroupedCases {
+            group: "调用>嵌入包含",
+            cases: vec![
+                case!(
+                    "Greeting",
+                    [(EnterCallOnTemplate(block, 0..8)), (ExitBlock(..)),],
+                    r#"<p>Hello, <strong>world</strong>!</p>"#,
+                ),
+                case!(
+                    "Nested",
+                    [(EnterCallOnTemplate(block, 0..6)), (ExitBlock(..)),],
+                    r#"<p>Hello, <strong>world</strong>!</p><hr>"#,
+                ),
+                case!(
+                    "WithExtension",
+                    [(EnterCallOnTemplate(block, 0..13)), (ExitBlock(..)),],
+                    r#"<all-optional><p>content</p></all-optional>"#,
+                ),
+                case!(
+                    "Unknown",
+                    [(EnterCallOnTemplate(block, 0..7)), (ExitBlock(..)),],
+                    r#"<x-block-call-error call-type="transclusion" call-name="Unknown" error-type="UnknownCallee" error-value="Unknown"></x-block-call-error>"#,
+                ),
+                case!(
+                    "Recursive",
+                    [(EnterCallOnTemplate(block, 0..9)), (ExitBlock(..)),],
+                    r#"<x-block-call-error call-type="transclusion" call-name="Recursive" error-type="CompilationError" error-value="RecursionDepthExceeded"></x-block-call-error>"#,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "调用>嵌入包含>参数填充",
+            cases: vec![
+                case!(
+                    "Param",
+                    [(EnterCallOnTemplate(block, 0..5)), (ExitBlock(..)),],
+                    r#"<p>fallback</p>"#,
+                ),
+                case!(
+                    "Param:0/x:8/content:12",
+                    [
+                        (EnterCallOnTemplate(block, 0..5)),
+                        (IndicateCallNormalArgument(8..9)),
+                        (EnterParagraph(..)),
+                        (Text(12..19)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>content</p>"#,
+                ),
+                case!(
+                    "Param:0/X:8/content:12",
+                    [
+                        (EnterCallOnTemplate(block, 0..5)),
+                        (IndicateCallNormalArgument(8..9)),
+                        (EnterParagraph(..)),
+                        (Text(12..19)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>content</p>"#,
+                ),
+                case!(
+                    "Param:0/y:8/content:12",
+                    [
+                        (EnterCallOnTemplate(block, 0..5)),
+                        (IndicateCallNormalArgument(8..9)),
+                        (EnterParagraph(..)),
+                        (Text(12..19)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>fallback</p>"#,
+                ),
+                case!(
+                    "Unnamed:0/content:10",
+                    [
+                        (EnterCallOnTemplate(block, 0..7)),
+                        (IndicateCallNormalArgument()),
+                        (EnterParagraph(..)),
+                        (Text(10..17)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>content</p>"#,
+                ),
+                case!(
+                    "Unnamed",
+                    [(EnterCallOnTemplate(block, 0..7)), (ExitBlock(..)),],
+                    r#""#,
+                ),
+                case!(
+                    "Outer",
+                    [(EnterCallOnTemplate(block, 0..5)), (ExitBlock(..)),],
+                    r#"<p>outer fallback</p>"#,
+                ),
+                case!(
+                    "Outer:0/y:8/content:12",
+                    [
+                        (EnterCallOnTemplate(block, 0..5)),
+                        (IndicateCallNormalArgument(8..9)),
+                        (EnterParagraph(..)),
+                        (Text(12..19)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>content</p>"#,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "调用>嵌入包含>行内参数填充",
+            cases: vec![
+                case!(
+                    "InlineParam",
+                    [(EnterCallOnTemplate(block, 0..11)), (ExitBlock(..)),],
+                    r#"<p>afallbackb</p>"#,
+                ),
+                case!(
+                    "InlineParam:0/x:14/content:19",
+                    [
+                        (EnterCallOnTemplate(block, 0..11)),
+                        (IndicateCallNormalArgument(14..15)),
+                        (EnterParagraph(..)),
+                        (Text(19..26)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p>acontentb</p>"#,
+                ),
+            ],
+        },
         GroupedCases {
             group: "调用>扩展>必要参数",
             cases: vec![
@@ -673,6 +1188,43 @@ fn it_works_in_block_phase_for_events_involving_calls() {
                 r#"<with-variant variant="var"></with-variant>"#,
             )],
         },
+        GroupedCases {
+            group: "调用>扩展>附加信息",
+            cases: vec![
+                case!(
+                    " .foo:0/WithVariant:8",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterCallOnExtension(block, 8..19)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<with-variant variant="var" class="foo"></with-variant>"#,
+                ),
+                case!(
+                    r#" .foo title="t":0/WithVariant:18"#,
+                    [
+                        (BlockAttachedInfo(following, 0..15)),
+                        (EnterCallOnExtension(block, 18..29)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<with-variant variant="var" class="foo" title="t"></with-variant>"#,
+                ),
+                case!(
+                    " .foo:0/AllOptional:8/foo:22/ .baz:29/x:38",
+                    [
+                        (BlockAttachedInfo(following, 0..5)),
+                        (EnterCallOnExtension(block, 8..19)),
+                        (IndicateCallNormalArgument(22..25)),
+                        (BlockAttachedInfo(slot, 29..34)),
+                        (EnterParagraph(..)),
+                        (Text(38..39)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<all-optional class="foo"><div slot="foo" class="baz"><p>x</p></div></all-optional>"#,
+                ),
+            ],
+        },
         GroupedCases {
             group: "调用>扩展>扩展别名",
             cases: vec![case!(
@@ -758,6 +1310,258 @@ fn it_works_in_block_phase_for_events_involving_calls() {
                 ),
             ],
         },
+        GroupedCases {
+            group: "调用>扩展>目录",
+            cases: vec![
+                case!(
+                    "TableOfContents:0/a:18/b:23/c:28/d:33",
+                    [
+                        (EnterCallOnExtension(block, 0..15)),
+                        (ExitBlock(..)),
+                        (EnterHeading1(..)),
+                        (Text(18..19)),
+                        (ExitBlock(..)),
+                        (EnterHeading2(..)),
+                        (Text(23..24)),
+                        (ExitBlock(..)),
+                        (EnterHeading3(..)),
+                        (Text(28..29)),
+                        (ExitBlock(..)),
+                        (EnterHeading1(..)),
+                        (Text(33..34)),
+                        (ExitBlock(..)),
+                    ],
+                    concat!(
+                        r##"<nav class="toc"><ol>"##,
+                        r##"<li><a href="#a">a</a><ol>"##,
+                        r##"<li><a href="#b">b</a><ol><li><a href="#c">c</a></li></ol></li>"##,
+                        r##"</ol></li>"##,
+                        r##"<li><a href="#d">d</a></li>"##,
+                        r##"</ol></nav>"##,
+                        r#"<h1 id="a">a</h1><h2 id="b">b</h2><h3 id="c">c</h3><h1 id="d">d</h1>"#,
+                    ),
+                ),
+                case!(
+                    "a:0/b:4/c:8/TableOfContents:12",
+                    [
+                        (EnterHeading2(..)),
+                        (Text(0..1)),
+                        (ExitBlock(..)),
+                        (EnterHeading4(..)),
+                        (Text(4..5)),
+                        (ExitBlock(..)),
+                        (EnterHeading3(..)),
+                        (Text(8..9)),
+                        (ExitBlock(..)),
+                        (EnterCallOnExtension(block, 12..27)),
+                        (ExitBlock(..)),
+                    ],
+                    concat!(
+                        r#"<h2 id="a">a</h2><h4 id="b">b</h4><h3 id="c">c</h3>"#,
+                        r##"<nav class="toc"><ol>"##,
+                        r##"<li><a href="#a">a</a><ol>"##,
+                        r##"<li><a href="#b">b</a></li><li><a href="#c">c</a></li>"##,
+                        r##"</ol></li>"##,
+                        r##"</ol></nav>"##,
+                    ),
+                ),
+                case!(
+                    "TableOfContents",
+                    [(EnterCallOnExtension(block, 0..15)), (ExitBlock(..)),],
+                    r#""#,
+                ),
+                case!(
+                    "TableOfContents:0/x:18",
+                    [
+                        (EnterCallOnExtension(block, 0..15)),
+                        (IndicateCallNormalArgument()),
+                        (EnterParagraph(..)),
+                        (Text(18..19)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<x-block-call-error call-type="extension" call-name="TableOfContents" error-type="BadParameters" error-value="?1;"></x-block-call-error>"#,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "调用>扩展>旁注",
+            cases: vec![
+                case!(
+                    "Footnote:0/note:11",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallNormalArgument()),
+                        (Text(11..15)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="fnref-1"><a href="#fn-1">[1]</a></sup></p><ol class="footnotes"><li id="fn-1"><a href="#fnref-1">↑</a> note</li></ol>"##,
+                ),
+                case!(
+                    @footnote_id_prefix = b"doc-",
+                    "Footnote:0/note:11",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallNormalArgument()),
+                        (Text(11..15)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="doc-fnref-1"><a href="#doc-fn-1">[1]</a></sup></p><ol class="footnotes"><li id="doc-fn-1"><a href="#doc-fnref-1">↑</a> note</li></ol>"##,
+                ),
+                case!(
+                    "Footnote:0/a:11/Footnotes:16/Footnote:29/b:41",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallNormalArgument()),
+                        (Text(11..12)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                        (EnterCallOnExtension(block, 16..25)),
+                        (ExitBlock(..)),
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 29..37)),
+                        (IndicateCallNormalArgument()),
+                        (Text(41..42)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="fnref-1"><a href="#fn-1">[1]</a></sup></p><ol class="footnotes"><li id="fn-1"><a href="#fnref-1">↑</a> a</li></ol><p><sup id="fnref-2"><a href="#fn-2">[2]</a></sup></p><ol class="footnotes" start="2"><li id="fn-2"><a href="#fnref-2">↑</a> b</li></ol>"##,
+                ),
+                case!(
+                    "Footnote:0/Footnote:11/inner:23",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallNormalArgument()),
+                        (EnterCallOnExtension(inline, 11..19)),
+                        (IndicateCallNormalArgument()),
+                        (Text(23..28)),
+                        (@inline ExitInline(..)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="fnref-1"><a href="#fn-1">[1]</a></sup></p><ol class="footnotes"><li id="fn-1"><a href="#fnref-1">↑</a> <sup id="fnref-2"><a href="#fn-2">[2]</a></sup></li><li id="fn-2"><a href="#fnref-2">↑</a> inner</li></ol>"##,
+                ),
+                case!(
+                    "Footnote:0/x:11/ .bar:16/Footnotes:25",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallNormalArgument()),
+                        (Text(11..12)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                        (BlockAttachedInfo(following, 16..21)),
+                        (EnterCallOnExtension(block, 25..34)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="fnref-1"><a href="#fn-1">[1]</a></sup></p><ol class="footnotes bar"><li id="fn-1"><a href="#fnref-1">↑</a> x</li></ol>"##,
+                ),
+                case!(
+                    "Footnotes",
+                    [(EnterCallOnExtension(block, 0..9)), (ExitBlock(..)),],
+                    r#""#,
+                ),
+                case!(
+                    "Footnote",
+                    [(EnterCallOnExtension(block, 0..8)), (ExitBlock(..)),],
+                    r#"<x-block-call-error call-type="extension" call-name="Footnote" error-type="UnknownCallee" error-value="Footnote"></x-block-call-error>"#,
+                ),
+                case!(
+                    "Footnote",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p><x-inline-call-error call-type="extension" call-name="Footnote" error-type="BadParameters" error-value="!1;"></x-inline-call-error></p>"#,
+                ),
+                case!(
+                    "Footnote:0/foo:11/x:18",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallNormalArgument(11..14)),
+                        (Text(18..19)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p><x-inline-call-error call-type="extension" call-name="Footnote" error-type="BadParameters" error-value="!1,?foo;"></x-inline-call-error></p>"#,
+                ),
+                case!(
+                    "Footnote:0/name:11/x:19/Footnote:24/name:36/x:44/Footnotes:49/name:62/x:70/note:75",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallVerbatimArgument(11..15)),
+                        (Text(19..20)),
+                        (@inline ExitInline(..)),
+                        (EnterCallOnExtension(inline, 24..32)),
+                        (IndicateCallVerbatimArgument(36..40)),
+                        (Text(44..45)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                        (EnterCallOnExtension(block, 49..58)),
+                        (IndicateCallVerbatimArgument(62..66)),
+                        (Text(70..71)),
+                        (IndicateCallNormalArgument()),
+                        (EnterParagraph(..)),
+                        (Text(75..79)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="fnref-1"><a href="#fn-1">[1]</a></sup><sup><a href="#fn-1">[1]</a></sup></p><ol class="footnotes"><li id="fn-1"><a href="#fnref-1">↑</a> note</li></ol>"##,
+                ),
+                case!(
+                    "Footnotes:0/名称:12/y:22/note:27/Footnote:35/名称:47/y:57",
+                    [
+                        (EnterCallOnExtension(block, 0..9)),
+                        (IndicateCallVerbatimArgument(12..18)),
+                        (Text(22..23)),
+                        (IndicateCallNormalArgument()),
+                        (EnterParagraph(..)),
+                        (Text(27..31)),
+                        (ExitBlock(..)),
+                        (ExitBlock(..)),
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 35..43)),
+                        (IndicateCallVerbatimArgument(47..53)),
+                        (Text(57..58)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r##"<p><sup id="fnref-1"><a href="#fn-1">[1]</a></sup></p><ol class="footnotes"><li id="fn-1"><a href="#fnref-1">↑</a> note</li></ol>"##,
+                ),
+                case!(
+                    "Footnote:0/name:11/x:19",
+                    [
+                        (EnterParagraph(..)),
+                        (EnterCallOnExtension(inline, 0..8)),
+                        (IndicateCallVerbatimArgument(11..15)),
+                        (Text(19..20)),
+                        (@inline ExitInline(..)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<p><x-inline-call-error call-type="extension" call-name="Footnote" error-type="BadParameters" error-value="!1;"></x-inline-call-error></p>"#,
+                ),
+                case!(
+                    "Footnotes:0/name:12/x:20",
+                    [
+                        (EnterCallOnExtension(block, 0..9)),
+                        (IndicateCallVerbatimArgument(12..16)),
+                        (Text(20..21)),
+                        (ExitBlock(..)),
+                    ],
+                    r#"<x-block-call-error call-type="extension" call-name="Footnotes" error-type="BadParameters" error-value=";?name"></x-block-call-error>"#,
+                ),
+            ],
+        },
     ];
 
     run_cases(table);
@@ -791,13 +1595,48 @@ fn it_works_in_inline_phase() {
             )],
         },
         GroupedCases {
-            group: "Dicexp",
+            group: "自动外部链接",
             cases: vec![case!(
-                "d100",
-                [(Dicexp(0..4)),],
-                r#"<x-dicexp code="d100"></x-dicexp>"#,
+                "https://example.com/?a=b&c=\"d\"",
+                [(ExternalLink(0..30)),],
+                r#"<x-external-link address="https://example.com/?a=b&amp;c=&quot;d&quot;"></x-external-link>"#,
             )],
         },
+        GroupedCases {
+            group: "Dicexp",
+            cases: vec![
+                case!(
+                    "d100",
+                    [(@inline EnterDicexp(..)), (Text(0..4)), (@inline ExitInline(..)),],
+                    r#"<x-dicexp code="d100"></x-dicexp>"#,
+                ),
+                case!(
+                    "d100:0/+1:7",
+                    [
+                        (@inline EnterDicexp(..)),
+                        (Text(0..4)),
+                        (NewLine(..)),
+                        (Text(7..9)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"<x-dicexp code="d100&#10;+1"></x-dicexp>"#,
+                ),
+                case!(
+                    "d100",
+                    [(@inline EnterDicexp(..)), (Text(0..4)),],
+                    r#"<x-dicexp code="d100"></x-dicexp>"#,
+                ),
+                case!(
+                    "_balls:0/d100:9",
+                    [
+                        (EnterAssigningDicexp(0..6)),
+                        (Text(9..13)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"<x-dicexp code="d100" name="_balls"></x-dicexp>"#,
+                ),
+            ],
+        },
         GroupedCases {
             group: "加粗强调与删除线",
             cases: vec![
@@ -836,11 +1675,64 @@ fn it_works_in_inline_phase() {
                 ),
             ],
         },
+        GroupedCases {
+            group: "附加信息",
+            cases: vec![
+                case!(
+                    " .foo:0/a:8/b:12",
+                    [
+                        (InlineAttachedInfo(following, 0..5)),
+                        (@inline EnterRuby(..)),
+                        (Text(8..9)),
+                        (@inline EnterRubyText(..)),
+                        (Text(12..13)),
+                        (@inline ExitInline(..)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"<ruby class="foo">a<rt>b</rt></ruby>"#,
+                ),
+                case!(
+                    " .foo:0/ .bar:8/a:16",
+                    [
+                        (@inline EnterStrong(..)),
+                        (InlineAttachedInfo(slot, 0..5)),
+                        (InlineAttachedInfo(slot, 8..13)),
+                        (Text(16..17)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"<strong class="foo bar">a</strong>"#,
+                ),
+                case!(
+                    " .foo:0/d100:8",
+                    [
+                        (InlineAttachedInfo(following, 0..5)),
+                        (@inline EnterDicexp(..)),
+                        (Text(8..12)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"<x-dicexp code="d100" class="foo"></x-dicexp>"#,
+                ),
+                case!(
+                    " .foo:0/a:8",
+                    [(InlineAttachedInfo(following, 0..5)), (Text(8..9)),],
+                    r#"<span class="foo">a</span>"#,
+                ),
+                case!(
+                    r#" .foo title="t":0/a:18/b:23"#,
+                    [
+                        (InlineAttachedInfo(following, 0..15)),
+                        (Text(18..19)),
+                        (Text(23..24)),
+                    ],
+                    r#"<span class="foo" title="t">a</span>b"#,
+                ),
+            ],
+        },
         GroupedCases {
             group: "XSS",
             cases: vec![case!(
                 r#"""#,
-                [(Dicexp(0..1)),],
+                [(@inline EnterDicexp(..)), (Text(0..1)), (@inline ExitInline(..)),],
                 r#"<x-dicexp code="&quot;"></x-dicexp>"#,
             )],
         },
@@ -851,14 +1743,83 @@ fn it_works_in_inline_phase() {
 
 #[test]
 fn it_works_in_inline_phase_for_events_involving_calls() {
-    let table: Vec<GroupedCases<_>> = vec![GroupedCases {
-        group: "调用>扩展",
-        cases: vec![case!(
-            "AllOptional",
-            [(EnterCallOnExtension(inline, 0..11)), (@inline ExitInline(..)),],
-            r#"<i-all-optional></i-all-optional>"#,
-        )],
-    }];
+    let table: Vec<GroupedCases<_>> = vec![
+        GroupedCases {
+            group: "调用>扩展",
+            cases: vec![case!(
+                "AllOptional",
+                [(EnterCallOnExtension(inline, 0..11)), (@inline ExitInline(..)),],
+                r#"<i-all-optional></i-all-optional>"#,
+            )],
+        },
+        GroupedCases {
+            group: "调用>嵌入包含",
+            cases: vec![
+                case!(
+                    "Greeting",
+                    [(EnterCallOnTemplate(inline, 0..8)), (@inline ExitInline(..)),],
+                    r#"Hello, <strong>world</strong>!"#,
+                ),
+                case!(
+                    "MultiLine",
+                    [(EnterCallOnTemplate(inline, 0..9)), (@inline ExitInline(..)),],
+                    r#"a<br><br>== b ==<br>Hello, <strong>world</strong>!"#,
+                ),
+                case!(
+                    "Unknown",
+                    [(EnterCallOnTemplate(inline, 0..7)), (@inline ExitInline(..)),],
+                    r#"<x-inline-call-error call-type="transclusion" call-name="Unknown" error-type="UnknownCallee" error-value="Unknown"></x-inline-call-error>"#,
+                ),
+                case!(
+                    "InlineRecursive",
+                    [(EnterCallOnTemplate(inline, 0..15)), (@inline ExitInline(..)),],
+                    r#"<x-inline-call-error call-type="transclusion" call-name="InlineRecursive" error-type="CompilationError" error-value="RecursionDepthExceeded"></x-inline-call-error>"#,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "调用>嵌入包含>参数填充",
+            cases: vec![
+                case!(
+                    "InlineParam",
+                    [(EnterCallOnTemplate(inline, 0..11)), (@inline ExitInline(..)),],
+                    r#"afallbackb"#,
+                ),
+                case!(
+                    "InlineParam:0/x:14/content:19",
+                    [
+                        (EnterCallOnTemplate(inline, 0..11)),
+                        (IndicateCallNormalArgument(14..15)),
+                        (Text(19..26)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"acontentb"#,
+                ),
+                case!(
+                    "InlineParam:0/y:14/content:19",
+                    [
+                        (EnterCallOnTemplate(inline, 0..11)),
+                        (IndicateCallNormalArgument(14..15)),
+                        (Text(19..26)),
+                        (@inline ExitInline(..)),
+                    ],
+                    r#"afallbackb"#,
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "调用>扩展>附加信息",
+            cases: vec![case!(
+                " .foo:0/AllOptional:8",
+                [
+                    (InlineAttachedInfo(following, 0..5)),
+                    (EnterCallOnExtension(inline, 8..19)),
+                    (@inline ExitInline(..)),
+                ],
+                r#"<i-all-optional class="foo"></i-all-optional>"#,
+            )],
+        },
+    ];
 
     // 由于块级阶段与行内阶段目前渲染调用使用同样的实现，这里就不重复测试了。
     // TODO: 也许应该实现一个函数用于同时生成这方面块级阶段与行内阶段的测试用例。
@@ -881,3 +1842,48 @@ fn it_works_in_inline_phase_for_events_involving_calls_with_feature_block_id() {
 
     run_cases(table);
 }
+
+#[test]
+fn it_bounds_transclusion_expansions_and_fetches_each_template_once() {
+    struct CountingTemplateProvider(std::cell::Cell<usize>);
+    impl templates::TemplateProvider for CountingTemplateProvider {
+        fn get_template_source(&self, name: &[u8]) -> Option<std::borrow::Cow<'_, [u8]>> {
+            self.0.set(self.0.get() + 1);
+            (name == b"Doubling").then_some(std::borrow::Cow::Borrowed(
+                b"{{Doubling}}\n{{Doubling}}".as_slice(),
+            ))
+        }
+    }
+
+    let input = b"{{Doubling}}";
+    let parsed = crate::parse(input).collect::<Result<Vec<_>, _>>().unwrap();
+    let block_extension_map = fixtures::new_block_extension_map();
+    let inline_extension_map = fixtures::new_inline_extension_map();
+    let compile_opts = crate::CompileOption {
+        restrictions: crate::CompileRestrictions {
+            max_call_depth_in_document: 100,
+        },
+        block_extension_map: &block_extension_map,
+        inline_extension_map: &inline_extension_map,
+    };
+    let compiled = crate::compile(input, &parsed, &compile_opts).unwrap();
+
+    let tag_name_map = crate::TagNameMap::new_demo_instance_for_test();
+    let template_provider = CountingTemplateProvider(std::cell::Cell::new(0));
+    let exec_opts = crate::ExecuteOptions {
+        tag_name_map: &tag_name_map,
+        block_extension_map: &block_extension_map,
+        inline_extension_map: &inline_extension_map,
+        template_provider: Some(&template_provider),
+        compile_options: &compile_opts,
+        code_block_rendering: CodeBlockRendering::CustomElement,
+        footnote_id_prefix: b"",
+        #[cfg(feature = "block-id")]
+        should_include_block_ids: false,
+    };
+    let actual = crate::execute(input, &parsed, &compiled, &exec_opts);
+    let actual = String::from_utf8(actual).unwrap();
+
+    assert!(actual.contains(r#"error-type="TooManyTransclusions""#));
+    assert_eq!(template_provider.0.get(), 1);
+}
@@ -2,15 +2,35 @@ macro_rules! case {
     (@with_id, $input:literal, [$($ev_tts:tt)*], $expected:expr,) => {
         case!(@__inner, $input, [$($ev_tts)*], $expected, {
             should_include_block_id: true,
+            code_block_rendering: CustomElement,
+            footnote_id_prefix: b"",
+        })
+    };
+    (@pre_code, $input:literal, [$($ev_tts:tt)*], $expected:expr,) => {
+        case!(@__inner, $input, [$($ev_tts)*], $expected, {
+            should_include_block_id: false,
+            code_block_rendering: PreCode,
+            footnote_id_prefix: b"",
+        })
+    };
+    (@footnote_id_prefix = $prefix:literal, $input:literal, [$($ev_tts:tt)*], $expected:expr,) => {
+        case!(@__inner, $input, [$($ev_tts)*], $expected, {
+            should_include_block_id: false,
+            code_block_rendering: CustomElement,
+            footnote_id_prefix: $prefix,
         })
     };
     ($input:literal, [$($ev_tts:tt)*], $expected:expr,) => {
         case!(@__inner, $input, [$($ev_tts)*], $expected, {
             should_include_block_id: false,
+            code_block_rendering: CustomElement,
+            footnote_id_prefix: b"",
         })
     };
     (@__inner, $input:literal, [$($ev_tts:tt)*], $expected:expr, {
         should_include_block_id: $should_include_block_id:literal,
+        code_block_rendering: $code_block_rendering:ident,
+        footnote_id_prefix: $footnote_id_prefix:expr,
     }) => {
         $crate::executing::tests::support::Case {
             input_events: $crate::executing::tests::support::events!($($ev_tts)*),
@@ -19,6 +39,8 @@ macro_rules! case {
 
             options: $crate::executing::tests::support::CaseOptions {
                 tag_name_map: crate::executing::TagNameMap::new_demo_instance_for_test(),
+                code_block_rendering: crate::executing::CodeBlockRendering::$code_block_rendering,
+                footnote_id_prefix: $footnote_id_prefix,
                 #[cfg(feature = "block-id")]
                 should_include_block_id: $should_include_block_id,
             },
@@ -45,20 +67,102 @@ macro_rules! __event {
         $crate::Event::Text($start..$end)
     };
 
+    (EnterOrderedList (..)) => {
+        $crate::Event::EnterOrderedList(
+            rotext_core::events::BlockWithId {
+                id: rotext_core::BlockId::new_invalid(),
+                span: rotext_core::SourceSpan::new_invalid(),
+                position: rotext_core::Position::new_invalid(),
+            }
+            .into(),
+        )
+    };
+    (EnterOrderedList (.., id = $id:literal)) => {
+        $crate::Event::EnterOrderedList(
+            rotext_core::events::BlockWithId {
+                id: rotext_core::BlockId::new($id),
+                span: rotext_core::SourceSpan::new_invalid(),
+                position: rotext_core::Position::new_invalid(),
+            }
+            .into(),
+        )
+    };
+    (EnterOrderedList (.., start = $start:literal)) => {
+        $crate::Event::EnterOrderedList(rotext_core::events::OrderedList {
+            id: rotext_core::BlockId::new_invalid(),
+            start: Some($start),
+            numbering: None,
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
+        })
+    };
+    (EnterOrderedList (.., start = $start:literal, type = $numbering:ident)) => {
+        $crate::Event::EnterOrderedList(rotext_core::events::OrderedList {
+            id: rotext_core::BlockId::new_invalid(),
+            start: Some($start),
+            numbering: Some(rotext_core::events::OrderedListNumbering::$numbering),
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
+        })
+    };
+
+    (EnterListItem (..)) => {
+        $crate::Event::EnterListItem(rotext_core::events::ListItem {
+            id: rotext_core::BlockId::new_invalid(),
+            checked: None,
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
+        })
+    };
+    (EnterListItem (.., id = $id:literal)) => {
+        $crate::Event::EnterListItem(rotext_core::events::ListItem {
+            id: rotext_core::BlockId::new($id),
+            checked: None,
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
+        })
+    };
+    (EnterListItem (.., checked = $checked:literal)) => {
+        $crate::Event::EnterListItem(rotext_core::events::ListItem {
+            id: rotext_core::BlockId::new_invalid(),
+            checked: Some($checked),
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
+        })
+    };
+
     (IndicateCodeBlockCode ()) => {
-        $crate::Event::IndicateCodeBlockCode
+        $crate::Event::IndicateCodeBlockCode(rotext_core::SourceSpan::new_invalid())
     };
     (IndicateTableCaption ()) => {
-        $crate::Event::IndicateTableCaption
+        $crate::Event::IndicateTableCaption(rotext_core::SourceSpan::new_invalid())
     };
     (IndicateTableRow ()) => {
-        $crate::Event::IndicateTableRow
+        $crate::Event::IndicateTableRow(rotext_core::SourceSpan::new_invalid())
     };
     (IndicateTableHeaderCell ()) => {
-        $crate::Event::IndicateTableHeaderCell
+        $crate::Event::IndicateTableHeaderCell(rotext_core::events::TableCell::new(
+            rotext_core::SourceSpan::new_invalid(),
+        ))
+    };
+    (IndicateTableHeaderCell ($colspan:literal, $rowspan:literal)) => {
+        $crate::Event::IndicateTableHeaderCell(rotext_core::events::TableCell {
+            colspan: core::num::NonZeroUsize::new($colspan),
+            rowspan: core::num::NonZeroUsize::new($rowspan),
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
     (IndicateTableDataCell ()) => {
-        $crate::Event::IndicateTableDataCell
+        $crate::Event::IndicateTableDataCell(rotext_core::events::TableCell::new(
+            rotext_core::SourceSpan::new_invalid(),
+        ))
+    };
+    (IndicateTableDataCell ($colspan:literal, $rowspan:literal)) => {
+        $crate::Event::IndicateTableDataCell(rotext_core::events::TableCell {
+            colspan: core::num::NonZeroUsize::new($colspan),
+            rowspan: core::num::NonZeroUsize::new($rowspan),
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
 
     (ExitBlock (..)) => {
@@ -66,6 +170,9 @@ macro_rules! __event {
             id: rotext_core::BlockId::new_invalid(),
             start_line: rotext_core::LineNumber::new_invalid(),
             end_line: rotext_core::LineNumber::new_invalid(),
+            span: rotext_core::SourceSpan::new_invalid(),
+            start: rotext_core::Position::new_invalid(),
+            end: rotext_core::Position::new_invalid(),
         })
     };
     (ExitBlock (.., id = $id:literal)) => {
@@ -73,6 +180,9 @@ macro_rules! __event {
             id: rotext_core::BlockId::new($id),
             start_line: rotext_core::LineNumber::new_invalid(),
             end_line: rotext_core::LineNumber::new_invalid(),
+            span: rotext_core::SourceSpan::new_invalid(),
+            start: rotext_core::Position::new_invalid(),
+            end: rotext_core::Position::new_invalid(),
         })
     };
     (ExitBlock (.., id = $id:literal, lns = $ln_s:literal..=$ln_e:literal)) => {
@@ -80,6 +190,9 @@ macro_rules! __event {
             id: rotext_core::BlockId::new($id),
             start_line: rotext_core::LineNumber::new($ln_s),
             end_line: rotext_core::LineNumber::new($ln_e),
+            span: rotext_core::SourceSpan::new_invalid(),
+            start: rotext_core::Position::new_invalid(),
+            end: rotext_core::Position::new_invalid(),
         })
     };
 
@@ -87,80 +200,149 @@ macro_rules! __event {
         $crate::Event::ThematicBreak(rotext_core::events::ThematicBreak {
             id: rotext_core::BlockId::new_invalid(),
             line: rotext_core::LineNumber::new_invalid(),
+            span: rotext_core::SourceSpan::new_invalid(),
+            start: rotext_core::Position::new_invalid(),
+            end: rotext_core::Position::new_invalid(),
         })
     };
     (ThematicBreak (.., id = $id:literal)) => {
         $crate::Event::ThematicBreak(rotext_core::events::ThematicBreak {
             id: rotext_core::BlockId::new($id),
             line: rotext_core::LineNumber::new_invalid(),
+            span: rotext_core::SourceSpan::new_invalid(),
+            start: rotext_core::Position::new_invalid(),
+            end: rotext_core::Position::new_invalid(),
         })
     };
     (ThematicBreak (.., id = $id:literal, ln = $ln:literal)) => {
         $crate::Event::ThematicBreak(rotext_core::events::ThematicBreak {
             id: rotext_core::BlockId::new($id),
             line: rotext_core::LineNumber::new($ln),
+            span: rotext_core::SourceSpan::new_invalid(),
+            start: rotext_core::Position::new_invalid(),
+            end: rotext_core::Position::new_invalid(),
         })
     };
 
     (RefLink ($start:literal..$end:literal)) => {
         $crate::Event::RefLink($start..$end)
     };
-    (Dicexp ($start:literal..$end:literal)) => {
-        $crate::Event::Dicexp($start..$end)
+    (ExternalLink ($start:literal..$end:literal)) => {
+        $crate::Event::ExternalLink($start..$end)
+    };
+
+    (EnterAssigningDicexp ($start:literal..$end:literal)) => {
+        $crate::Event::EnterAssigningDicexp(rotext_core::events::AssigningDicexp {
+            name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
 
     (EnterWikiLink ($start:literal..$end:literal)) => {
-        $crate::Event::EnterWikiLink($start..$end)
+        $crate::Event::EnterWikiLink(rotext_core::events::WikiLink {
+            address: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
 
     (EnterCallOnExtension (block, $start:literal..$end:literal)) => {
         $crate::Event::EnterCallOnExtension(rotext_core::events::Call::Block {
             id: rotext_core::BlockId::new_invalid(),
             name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
         })
     };
     (EnterCallOnExtension (block, $start:literal..$end:literal, id = $id:literal)) => {
         $crate::Event::EnterCallOnExtension(rotext_core::events::Call::Block {
             id: rotext_core::BlockId::new($id),
             name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
         })
     };
     (EnterCallOnExtension (inline, $start:literal..$end:literal)) => {
         $crate::Event::EnterCallOnExtension(rotext_core::events::Call::Inline {
             name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
         })
     };
     (EnterCallOnExtension (inline, $start:literal..$end:literal, id = $id:literal)) => {
         $crate::Event::EnterCallOnExtension(rotext_core::events::Call::Inline {
             name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
+    };
+
+    (EnterCallOnTemplate (block, $start:literal..$end:literal)) => {
+        $crate::Event::EnterCallOnTemplate(rotext_core::events::Call::Block {
+            id: rotext_core::BlockId::new_invalid(),
+            name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
+        })
+    };
+    (EnterCallOnTemplate (inline, $start:literal..$end:literal)) => {
+        $crate::Event::EnterCallOnTemplate(rotext_core::events::Call::Inline {
+            name: $start..$end,
+            span: rotext_core::SourceSpan::new_invalid(),
         })
     };
 
     (IndicateCallNormalArgument ()) => {
-        $crate::Event::IndicateCallNormalArgument(None)
+        $crate::Event::IndicateCallNormalArgument(rotext_core::events::CallArgument {
+            name: None,
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
     (IndicateCallNormalArgument ($start:literal..$end:literal)) => {
-        $crate::Event::IndicateCallNormalArgument(Some($start..$end))
+        $crate::Event::IndicateCallNormalArgument(rotext_core::events::CallArgument {
+            name: Some($start..$end),
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
     (IndicateCallVerbatimArgument ()) => {
-        $crate::Event::IndicateCallVerbatimArgument(None)
+        $crate::Event::IndicateCallVerbatimArgument(rotext_core::events::CallArgument {
+            name: None,
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
     };
     (IndicateCallVerbatimArgument ($start:literal..$end:literal)) => {
-        $crate::Event::IndicateCallVerbatimArgument(Some($start..$end))
+        $crate::Event::IndicateCallVerbatimArgument(rotext_core::events::CallArgument {
+            name: Some($start..$end),
+            span: rotext_core::SourceSpan::new_invalid(),
+        })
+    };
+
+    ($v:ident (following, $start:literal..$end:literal)) => {
+        $crate::Event::$v(rotext_core::events::AttachedInfo {
+            target: rotext_core::events::AttachedInfoTarget::Following,
+            content: $start..$end,
+        })
+    };
+    ($v:ident (slot, $start:literal..$end:literal)) => {
+        $crate::Event::$v(rotext_core::events::AttachedInfo {
+            target: rotext_core::events::AttachedInfoTarget::Slot,
+            content: $start..$end,
+        })
     };
 
     (@inline $v:tt (..)) => {
-        $crate::Event::$v
+        $crate::Event::$v(rotext_core::SourceSpan::new_invalid())
     };
 
     ($v:tt (..)) => {
         $crate::Event::$v(rotext_core::events::BlockWithId {
             id: rotext_core::BlockId::new_invalid(),
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
         })
     };
     ($v:tt (.., id = $id:literal)) => {
         $crate::Event::$v(rotext_core::events::BlockWithId {
             id: rotext_core::BlockId::new($id),
+            span: rotext_core::SourceSpan::new_invalid(),
+            position: rotext_core::Position::new_invalid(),
         })
     };
 }
@@ -181,6 +363,8 @@ use super::*;
 #[allow(dead_code)]
 pub(super) struct CaseOptions<'a> {
     pub tag_name_map: TagNameMap<'a>,
+    pub code_block_rendering: crate::CodeBlockRendering,
+    pub footnote_id_prefix: &'static [u8],
     #[cfg(feature = "block-id")]
     pub should_include_block_id: bool,
 }
@@ -200,17 +384,26 @@ impl rotext_internal_test::support::Case for Case<'_> {
         let input = self.input.as_bytes();
 
         let tag_name_map = crate::TagNameMap::new_demo_instance_for_test();
+        let block_extension_map = fixtures::new_block_extension_map();
+        let inline_extension_map = fixtures::new_inline_extension_map();
         let compile_opts = crate::CompileOption {
             restrictions: crate::CompileRestrictions {
                 max_call_depth_in_document: 100,
             },
+            block_extension_map: &block_extension_map,
+            inline_extension_map: &inline_extension_map,
         };
         let compiled = crate::compile(input, &self.input_events, &compile_opts).unwrap();
 
+        let template_map = fixtures::new_template_map();
         let exec_opts = crate::ExecuteOptions {
             tag_name_map: &tag_name_map,
-            block_extension_map: &fixtures::new_block_extension_map(),
-            inline_extension_map: &fixtures::new_inline_extension_map(),
+            block_extension_map: &block_extension_map,
+            inline_extension_map: &inline_extension_map,
+            template_provider: Some(&template_map),
+            compile_options: &compile_opts,
+            code_block_rendering: self.options.code_block_rendering,
+            footnote_id_prefix: self.options.footnote_id_prefix,
             #[cfg(feature = "block-id")]
             should_include_block_ids: self.options.should_include_block_id,
         };
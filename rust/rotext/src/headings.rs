@@ -0,0 +1,383 @@
+use std::collections::{HashMap, HashSet};
+
+use rotext_core::{BlockId, Event, LineNumber};
+
+/// 标题的锚点。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadingAnchor {
+    /// 进入该标题的事件在事件列表中的位置。
+    pub index: usize,
+    /// 标题的级别，为 1 至 6。
+    pub level: u8,
+    /// 标题的 ID，在同一份文档中唯一，渲染为标题元素的 `id` 属性。
+    pub id: String,
+    /// 标题的文本，ID 即由此而来。
+    pub text: String,
+}
+
+/// 为 `parsed` 中的各个标题生成锚点。`parsed` 是属于 `Blend` 分组的事件。
+///
+/// ID 由标题的文本经 [`slugify`] 得来。若与先前的 ID 重复，或与旁注相关的 ID（`fn-N` 与
+/// `fnref-N`）形式相同，则依次尝试在其后加上 `-1`、`-2`…，直到不再如此为止。
+pub fn collect_heading_anchors(input: &[u8], parsed: &[Event]) -> Vec<HeadingAnchor> {
+    collect_heading_anchors_reserving_footnote_ids(input, parsed, b"")
+}
+
+/// 同 [collect_heading_anchors]，但旁注相关的 ID 带有前缀 `footnote_id_prefix`。
+pub fn collect_heading_anchors_reserving_footnote_ids(
+    input: &[u8],
+    parsed: &[Event],
+    footnote_id_prefix: &[u8],
+) -> Vec<HeadingAnchor> {
+    let mut anchors = vec![];
+    let mut seen_ids: HashSet<String> = HashSet::new();
+    // 各个 slug 下一次应从哪个后缀开始尝试，之前的后缀都已被占用。
+    let mut next_suffixes: HashMap<String, usize> = HashMap::new();
+
+    for (index, ev) in parsed.iter().enumerate() {
+        let Some(level) = heading_level(ev) else {
+            continue;
+        };
+
+        let text = heading_text(input, &parsed[index + 1..]);
+        let slug = slugify(&text);
+        let suffix = next_suffixes.entry(slug.clone()).or_insert(0);
+        let id = loop {
+            let id = match *suffix {
+                0 => slug.clone(),
+                n => format!("{slug}-{n}"),
+            };
+            *suffix += 1;
+            if !seen_ids.contains(&id) && !is_footnote_id(id.as_bytes(), footnote_id_prefix) {
+                break id;
+            }
+        };
+        seen_ids.insert(id.clone());
+
+        anchors.push(HeadingAnchor {
+            index,
+            level,
+            id,
+            text,
+        });
+    }
+
+    anchors
+}
+
+/// `id` 是否与带有前缀 `prefix` 的旁注相关的 ID 形式相同。
+fn is_footnote_id(id: &[u8], prefix: &[u8]) -> bool {
+    let Some(rest) = id.strip_prefix(prefix) else {
+        return false;
+    };
+    let Some(number) = rest
+        .strip_prefix(b"fn-")
+        .or_else(|| rest.strip_prefix(b"fnref-"))
+    else {
+        return false;
+    };
+    !number.is_empty() && number.iter().all(u8::is_ascii_digit)
+}
+
+/// 文档大纲中的一项，对应文档中的一个标题。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineItem {
+    /// 标题的级别，为 1 至 6。
+    pub level: u8,
+    /// 标题的文本。
+    pub text: String,
+    /// 标题的 ID，同 [`HeadingAnchor::id`]。
+    pub id: String,
+    pub block_id: BlockId,
+    /// 标题所在的行，来自退出该标题的事件。
+    pub start_line: LineNumber,
+    pub end_line: LineNumber,
+    /// 位于该标题之后、级别比该标题低的各个标题，直到遇到级别不低于该标题的标题为止。
+    pub children: Vec<OutlineItem>,
+}
+
+/// 生成 `parsed` 所对应文档的大纲。`parsed` 是属于 `Blend` 分组的事件。
+///
+/// 各项的嵌套只取决于标题级别的相对高低。例如，二级标题之后紧跟的四级标题会直接成为该二级
+/// 标题的子项。`parsed` 不完整、缺少退出某个标题的事件时，该标题会被略过。
+pub fn outline(input: &[u8], parsed: &[Event]) -> Vec<OutlineItem> {
+    outline_from_anchors(parsed, &collect_heading_anchors(input, parsed))
+}
+
+/// 同 [outline]，但使用已经生成的锚点。
+pub(crate) fn outline_from_anchors(
+    parsed: &[Event],
+    anchors: &[HeadingAnchor],
+) -> Vec<OutlineItem> {
+    let mut roots: Vec<OutlineItem> = vec![];
+
+    for anchor in anchors {
+        let block_id = match &parsed[anchor.index] {
+            Event::EnterHeading1(data)
+            | Event::EnterHeading2(data)
+            | Event::EnterHeading3(data)
+            | Event::EnterHeading4(data)
+            | Event::EnterHeading5(data)
+            | Event::EnterHeading6(data) => data.id,
+            _ => unreachable!(),
+        };
+        // 标题之中只有行内内容，因此其后的第一个退出块级的事件即退出该标题的事件。
+        let Some(Event::ExitBlock(exit)) = parsed[anchor.index + 1..]
+            .iter()
+            .find(|ev| matches!(ev, Event::ExitBlock(_)))
+        else {
+            continue;
+        };
+
+        insert_outline_item(&mut roots, OutlineItem {
+            level: anchor.level,
+            text: anchor.text.clone(),
+            id: anchor.id.clone(),
+            block_id,
+            start_line: exit.start_line,
+            end_line: exit.end_line,
+            children: vec![],
+        });
+    }
+
+    roots
+}
+
+fn insert_outline_item(siblings: &mut Vec<OutlineItem>, item: OutlineItem) {
+    match siblings.last_mut() {
+        Some(last) if last.level < item.level => insert_outline_item(&mut last.children, item),
+        _ => siblings.push(item),
+    }
+}
+
+/// 若 `ev` 是进入标题的事件，返回该标题的级别。
+pub fn heading_level(ev: &Event) -> Option<u8> {
+    match ev {
+        Event::EnterHeading1(_) => Some(1),
+        Event::EnterHeading2(_) => Some(2),
+        Event::EnterHeading3(_) => Some(3),
+        Event::EnterHeading4(_) => Some(4),
+        Event::EnterHeading5(_) => Some(5),
+        Event::EnterHeading6(_) => Some(6),
+        _ => None,
+    }
+}
+
+/// 将标题的文本转换为适合用作 ID 的形式：
+///
+/// - 字母与数字（包括汉字、假名等）保持不变，其中的大写字母转换为小写；
+/// - `-` 与 `_` 保持不变；
+/// - 连续的空白转换为单个 `-`；
+/// - 其余字符被移除。
+///
+/// 开头与结尾的 `-` 会被去掉。结果为空时，返回 `section`。
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut is_after_whitespace = false;
+    for char in text.chars() {
+        if char.is_whitespace() {
+            is_after_whitespace = true;
+            continue;
+        }
+        if !(char.is_alphanumeric() || char == '-' || char == '_') {
+            continue;
+        }
+        if is_after_whitespace && !slug.is_empty() {
+            slug.push('-');
+        }
+        is_after_whitespace = false;
+        slug.extend(char.to_lowercase());
+    }
+
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// 收集标题的文本。`evs` 始于标题的内容，止于（或越过）退出该标题的事件。
+///
+/// 注音文本与调用（连同其参数）不计入标题的文本。
+fn heading_text(input: &[u8], evs: &[Event]) -> String {
+    let mut text: Vec<u8> = vec![];
+    // 各层行内元素的内容是否不计入文本。
+    let mut stack: Vec<bool> = vec![];
+
+    for ev in evs {
+        let is_skipping = stack.last().copied().unwrap_or(false);
+        match ev {
+            Event::ExitBlock(_) => break,
+            Event::ExitInline(_) => {
+                stack.pop();
+            }
+            Event::EnterRubyText(_)
+            | Event::EnterCallOnTemplate(_)
+            | Event::EnterCallOnExtension(_)
+            | Event::EnterParameterFilling(_) => stack.push(true),
+            Event::EnterCodeSpan(_)
+            | Event::EnterEmphasis(_)
+            | Event::EnterStrong(_)
+            | Event::EnterStrikethrough(_)
+            | Event::EnterRuby(_)
+            | Event::EnterDicexp(_)
+            | Event::EnterAssigningDicexp(_)
+            | Event::EnterWikiLink(_) => stack.push(is_skipping),
+            _ if is_skipping => {}
+            Event::Text(content)
+            | Event::ExternalLink(content)
+            | Event::VerbatimEscaping(rotext_core::events::VerbatimEscaping { content, .. }) => {
+                text.extend(&input[content.clone()]);
+            }
+            Event::NewLine(_) => text.push(b' '),
+            _ => {}
+        }
+    }
+
+    String::from_utf8_lossy(&text).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_works() {
+        let table: &[(&str, &str)] = &[
+            ("转义", "转义"),
+            ("Hello World", "hello-world"),
+            ("  Hello   World  ", "hello-world"),
+            ("C++ & Rust!", "c-rust"),
+            ("foo_bar-baz", "foo_bar-baz"),
+            ("列表 Item 1", "列表-item-1"),
+            ("かな・カナ", "かなカナ"),
+            ("!!!", "section"),
+            ("", "section"),
+        ];
+
+        for (input, expected) in table {
+            assert_eq!(slugify(input), *expected, "input: {input:?}");
+        }
+    }
+
+    /// 标题事件的位置、标题的级别以及标题的 ID。
+    type ExpectedAnchor = (usize, u8, &'static str);
+
+    #[test]
+    fn collect_heading_anchors_works() {
+        let table: &[(&str, &[ExpectedAnchor])] = &[
+            ("= 转义 =", &[(0, 1, "转义")]),
+            ("== a ==\n\n== a ==\n\n== a-1 ==", &[
+                (0, 2, "a"),
+                (3, 2, "a-1"),
+                (6, 2, "a-1-1"),
+            ]),
+            ("== a ==\n\n== a-1 ==\n\n== a ==\n\n== a ==", &[
+                (0, 2, "a"),
+                (3, 2, "a-1"),
+                (6, 2, "a-2"),
+                (9, 2, "a-3"),
+            ]),
+            ("=== [[x|链接]]与[*加粗*] ===", &[(0, 3, "链接与加粗")]),
+            ("= [{#Span|不计入}]标题 =", &[(0, 1, "标题")]),
+            ("= [;注音:zhù yīn] =", &[(0, 1, "注音")]),
+            ("段落\n\n= [`code`] =", &[(3, 1, "code")]),
+            ("= fn 1 =\n\n= fnref 2 =\n\n= fn-x =", &[
+                (0, 1, "fn-1-1"),
+                (3, 1, "fnref-2-1"),
+                (6, 1, "fn-x"),
+            ]),
+        ];
+
+        for (input, expected) in table {
+            let parsed: Vec<_> = crate::parse(input.as_bytes())
+                .collect::<rotext_core::Result<_>>()
+                .unwrap();
+            let actual: Vec<_> = collect_heading_anchors(input.as_bytes(), &parsed)
+                .into_iter()
+                .map(|anchor| (anchor.index, anchor.level, anchor.id))
+                .collect();
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|(index, level, id)| (*index, *level, id.to_string()))
+                .collect();
+            assert_eq!(actual, expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn collect_heading_anchors_reserves_prefixed_footnote_ids() {
+        let input = "= doc fn 1 =\n\n= fn 1 =";
+        let parsed: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<rotext_core::Result<_>>()
+            .unwrap();
+        let ids: Vec<_> =
+            collect_heading_anchors_reserving_footnote_ids(input.as_bytes(), &parsed, b"doc-")
+                .into_iter()
+                .map(|anchor| anchor.id)
+                .collect();
+
+        assert_eq!(ids, vec!["doc-fn-1-1", "fn-1"]);
+    }
+
+    #[test]
+    fn outline_works() {
+        /// 以 `级别:文本` 表示各项，子项跟在括号中。
+        fn describe(items: &[OutlineItem]) -> String {
+            items
+                .iter()
+                .map(|item| {
+                    let mut desc = format!("{}:{}", item.level, item.text);
+                    if !item.children.is_empty() {
+                        desc.push_str(&format!("({})", describe(&item.children)));
+                    }
+                    desc
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        let table: &[(&str, &str)] = &[
+            ("段落", ""),
+            ("= 甲 =\n== 乙 ==\n= 丙 =", "1:甲(2:乙) 1:丙"),
+            ("== a ==\n==== b ====\n=== c ===", "2:a(4:b 3:c)"),
+            ("=== a ===\n== b ==\n= c =\n=== d ===", "3:a 2:b 1:c(3:d)"),
+            ("= a =\n> == 引用中的标题 ==", "1:a(2:引用中的标题)"),
+        ];
+
+        for (input, expected) in table {
+            let parsed: Vec<_> = crate::parse(input.as_bytes())
+                .collect::<rotext_core::Result<_>>()
+                .unwrap();
+            let actual = describe(&outline(input.as_bytes(), &parsed));
+            assert_eq!(actual, *expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn outline_skips_unterminated_headings() {
+        let input = "= a =\n== b ==";
+        let parsed: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<rotext_core::Result<_>>()
+            .unwrap();
+        let items = outline(input.as_bytes(), &parsed[..parsed.len() - 1]);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "a");
+        assert!(items[0].children.is_empty());
+    }
+
+    #[cfg(feature = "line-number")]
+    #[test]
+    fn outline_has_line_numbers() {
+        let input = "段落\n\n= 甲 =\n\n== 乙 ==";
+        let parsed: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<rotext_core::Result<_>>()
+            .unwrap();
+        let items = outline(input.as_bytes(), &parsed);
+
+        assert_eq!(items[0].start_line.value(), 3);
+        assert_eq!(items[0].children[0].start_line.value(), 5);
+    }
+}
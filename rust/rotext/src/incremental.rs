@@ -0,0 +1,637 @@
+//! 增量解析：输入被编辑后，只重新解析受到影响的顶层块级元素，其余的事件沿用先前的解析结果。
+
+use std::{collections::HashSet, ops::Range};
+
+use rotext_core::{
+    BlockEventStreamInlineSegmentMapper, BlockId, BlockParser, Event, LineNumber,
+    events::BlockWithId,
+};
+use rotext_utils::stack::VecStack;
+
+use crate::event_utils::{
+    TopLevelBlock, TopLevelBlockSplitter, block_id, block_id_entered, count_line_breaks,
+    eq_ignoring_block_ids, find_next_line_start, has_line_break, shift_event,
+};
+
+/// 对输入的一次编辑：旧输入中 `range` 处的内容被替换为了长度为 `new_len` 的新内容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub new_len: usize,
+}
+
+/// 一次增量解析的结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reparsed {
+    /// 重新解析所得的事件在更新后的事件列表（见 [ReparseState::events]）中的位置。渲染时只
+    /// 需要替换这部分事件对应的内容。
+    pub events: Range<usize>,
+    /// 不再存在的块级元素（包括嵌套在其中的块级元素）的 ID，按在文档中的先后排列。
+    pub removed_block_ids: Vec<BlockId>,
+    /// 新产生的块级元素（包括嵌套在其中的块级元素）的 ID，按在文档中的先后排列。
+    pub added_block_ids: Vec<BlockId>,
+}
+
+/// 增量解析的状态，即按顶层块级元素分段保存的、解析当前输入所得的事件。
+///
+/// 各段事件中的范围相对于所属顶层块级元素的开头，行号则相对于其开头所在的行，因此编辑之后
+/// 的各段无需移动。一次增量解析的开销与重新解析的内容的长度，以及位于编辑处之前的顶层块级
+/// 元素的个数（对每个元素只做常数次整数运算）成正比。
+#[derive(Debug, Clone)]
+pub struct ReparseState {
+    blocks: Vec<Block>,
+    /// 已经用过的最大的块级元素 ID。
+    last_block_id: usize,
+}
+
+/// 一个顶层块级元素（见 [TopLevelBlock]）。位于输入末尾、不属于任何顶层块级元素的事件（如
+/// 其后没有块级元素的块级附加信息）也视为一个元素。
+#[derive(Debug, Clone)]
+struct Block {
+    /// 本元素的开头与上一个元素的开头（对于第一个元素，为输入的开头）之间的距离。
+    distance: usize,
+    /// 本元素开头所在的行与上一个元素开头所在的行（对于第一个元素，为第 1 行）之间相差的行
+    /// 数。
+    line_distance: usize,
+    len: usize,
+    /// 解析器在确定本元素时至多读到的位置（不含）与本元素的开头之间的距离，不小于 `len`。
+    /// 见 [call_opening_lookahead_end]。
+    lookahead: usize,
+    /// 其中的范围相对于本元素的开头，本元素开头所在的行则记为第 1 行。
+    events: Vec<Event>,
+}
+
+/// 某个顶层块级元素在整份输入及事件列表中的位置。
+#[derive(Debug, Clone, Copy)]
+struct Located {
+    start: usize,
+    end: usize,
+    lookahead_end: usize,
+    line: usize,
+    events_start: usize,
+}
+
+/// 按需计算各顶层块级元素的 [Located]，只计算到用到的那一个为止。
+struct Locator<'a> {
+    blocks: &'a [Block],
+    located: Vec<Located>,
+}
+
+impl<'a> Locator<'a> {
+    fn new(blocks: &'a [Block]) -> Self {
+        Self {
+            blocks,
+            located: vec![],
+        }
+    }
+
+    fn get(&mut self, index: usize) -> Located {
+        while self.located.len() <= index {
+            let (start, line, events_start) = match self.located.last() {
+                Some(last) => (
+                    last.start,
+                    last.line,
+                    last.events_start + self.blocks[self.located.len() - 1].events.len(),
+                ),
+                None => (0, 1, 0),
+            };
+            let block = &self.blocks[self.located.len()];
+            let start = start + block.distance;
+            self.located.push(Located {
+                start,
+                end: start + block.len,
+                lookahead_end: start.saturating_add(block.lookahead),
+                line: line + block.line_distance,
+                events_start,
+            });
+        }
+        self.located[index]
+    }
+}
+
+impl ReparseState {
+    /// `events` 是由 [crate::parse] 解析 `input` 所得的、属于 `Blend` 分组的事件。
+    pub fn new(input: &[u8], events: &[Event]) -> Self {
+        let last_block_id = events
+            .iter()
+            .filter_map(block_id)
+            .map(|id| id.value())
+            .max()
+            .unwrap_or(0);
+        let mut splitter = TopLevelBlockSplitter::new();
+        let top_level_blocks: Vec<_> = (1..=events.len())
+            .filter_map(|len| splitter.push(&events[..len]))
+            .collect();
+
+        let mut blocks = vec![];
+        let mut builder = BlockBuilder::new(input, (0, 1), (0, 1));
+        for block in top_level_blocks {
+            blocks.push(builder.build(&events[block.events], block.source));
+        }
+        if let Some((index, start)) = splitter.pending() {
+            blocks.push(builder.build(&events[index..], start..input.len()));
+        }
+
+        Self {
+            blocks,
+            last_block_id,
+        }
+    }
+
+    /// 当前的事件，与解析当前输入所得的事件一致（块级元素的 ID 除外）。
+    pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
+        let (mut start, mut line) = (0, 1);
+        self.blocks.iter().flat_map(move |block| {
+            start += block.distance;
+            line += block.line_distance;
+            let (start, line) = (start, line);
+            block.events.iter().map(move |ev| {
+                let mut ev = ev.clone();
+                shift_event(&mut ev, start as isize, line as isize - 1);
+                ev
+            })
+        })
+    }
+
+    /// 在 `input` 由旧输入经过 `edit` 编辑而来后，更新状态，使其中的事件与解析 `input` 所得
+    /// 的事件一致（块级元素的 ID 除外）。
+    ///
+    /// 只有受到编辑影响的顶层块级元素会被重新解析。未发生变化的块级元素保留原先的 ID，新产
+    /// 生的块级元素则使用此前从未用过的 ID，因此 ID 不再按在文档中的先后递增。
+    pub fn reparse(&mut self, input: &[u8], edit: &Edit) -> crate::Result<Reparsed> {
+        let offset_delta = edit.new_len as isize - edit.range.len() as isize;
+        let shift = |old: usize| old.wrapping_add_signed(offset_delta);
+        let mut old = Locator::new(&self.blocks);
+
+        // 某个块级元素在何处结束可能取决于其后的内容，因此从受到编辑影响的第一个顶层块级元
+        // 素的前一个开始重新解析。重新解析须从紧随上一个顶层块级元素的那一行的开头开始，且
+        // 那一行与该元素之间只能有空白：块级元素的范围不包含其吞掉的后续行（如只有 `>` 的
+        // 行），这些行若被重新解析，会被当作新的块级元素。此外，上一个顶层块级元素不能在确定
+        // 时读到受到编辑影响的内容。
+        let mut first = (0..self.blocks.len())
+            .find(|index| old.get(*index).end >= edit.range.start)
+            .unwrap_or(self.blocks.len())
+            .saturating_sub(1);
+        let start = loop {
+            if first == 0 {
+                break 0;
+            }
+            let block_start = old.get(first).start;
+            let prev = old.get(first - 1);
+            match find_next_line_start(input, prev.end) {
+                Some(line_start)
+                    if prev.lookahead_end <= edit.range.start
+                        && line_start <= block_start
+                        && input[line_start..block_start]
+                            .iter()
+                            .all(u8::is_ascii_whitespace) =>
+                {
+                    break line_start;
+                }
+                _ => first -= 1,
+            }
+        };
+        // 位于重新解析之处以前的、最后一个顶层块级元素的开头及其所在的行。
+        let prev = match first {
+            0 => (0, 1),
+            _ => {
+                let located = old.get(first - 1);
+                (located.start, located.line)
+            }
+        };
+        let line = prev.1 + count_line_breaks(&input[prev.0..start]);
+
+        #[cfg(feature = "line-number")]
+        let line_number = LineNumber::new(line);
+        #[cfg(not(feature = "line-number"))]
+        let line_number = LineNumber::new();
+        let block_parser: BlockParser<VecStack<_>> = BlockParser::new(input).starting_at(
+            start,
+            line_number,
+            BlockId::new(self.last_block_id),
+        );
+        let parser: BlockEventStreamInlineSegmentMapper<_, VecStack<_>> =
+            BlockEventStreamInlineSegmentMapper::new(input, block_parser);
+
+        let mut new_events = vec![];
+        let mut new_blocks: Vec<TopLevelBlock> = vec![];
+        let mut splitter = TopLevelBlockSplitter::new();
+        // 重新解析到了哪个旧的顶层块级元素为止，其后的元素可以沿用。
+        let mut synced_until: Option<usize> = None;
+        // 与新解析出的最后一个顶层块级元素在相同之处结束的旧的顶层块级元素。
+        let mut matched: Option<usize> = None;
+        let mut candidate = first;
+        for ev in parser {
+            new_events.push(ev?);
+            let block = splitter.push(&new_events);
+            // 上一个元素结束后，新的一次解析可能吞掉旧的一次解析中下一个元素所在的行，因此
+            // 只有在新的一次解析的下一个顶层块级元素确实在相同之处开始时，才能沿用之后的元素。
+            if let Some(candidate) = matched.take() {
+                let next_start = match &block {
+                    Some(block) => block.source.start,
+                    None => splitter.pending().map_or(usize::MAX, |(_, start)| start),
+                };
+                if next_start == shift(old.get(candidate + 1).start) {
+                    new_events.pop();
+                    synced_until = Some(candidate);
+                    break;
+                }
+            }
+            let Some(block) = block else {
+                continue;
+            };
+            let end = block.source.end;
+            new_blocks.push(block);
+            if end < edit.range.start + edit.new_len {
+                continue;
+            }
+
+            // 新旧两次解析在相同的内容处结束了顶层块级元素，且其后的内容没有变化，因此之后
+            // 的解析结果必定相同。之后的第一个顶层块级元素须从新的一行开始，这样沿用的事件
+            // 中的列便不会发生变化；两者之间也只能有空白，理由同上。
+            while candidate < self.blocks.len() && {
+                let located = old.get(candidate);
+                located.end < edit.range.end || shift(located.end) < end
+            } {
+                candidate += 1;
+            }
+            if candidate + 1 >= self.blocks.len() {
+                continue;
+            }
+            // 两者的范围可能重叠（见 [crate::streaming::StreamingParser] 中的说明）。
+            let Some(gap) = input.get(end..shift(old.get(candidate + 1).start)) else {
+                continue;
+            };
+            if shift(old.get(candidate).end) == end
+                && has_line_break(gap)
+                && gap.iter().all(u8::is_ascii_whitespace)
+            {
+                matched = Some(candidate);
+            }
+        }
+
+        // 重新解析所得的、位于编辑之前的顶层块级元素若与原先的相同，则沿用原先的事件。
+        let mut region = vec![];
+        let mut builder = BlockBuilder::new(input, prev, (start, line));
+        let mut old_cursor = first;
+        for block in new_blocks {
+            let source = block.source.clone();
+            let mut new_block = builder.build(&new_events[block.events], source.clone());
+            let reusable = (old_cursor..self.blocks.len())
+                .map(|index| (index, old.get(index)))
+                .take_while(|(_, located)| located.end < edit.range.start)
+                .find(|(_, located)| (located.start..located.end) == source)
+                .map(|(index, _)| &self.blocks[index])
+                .filter(|old| eq_ignoring_block_ids(&old.events, &new_block.events));
+            if let Some(old) = reusable {
+                new_block.events = old.events.clone();
+            }
+            region.push(new_block);
+            while old_cursor < self.blocks.len() && old.get(old_cursor).end <= source.end {
+                old_cursor += 1;
+            }
+        }
+        if let (None, Some((index, start))) = (synced_until, splitter.pending()) {
+            region.push(builder.build(&new_events[index..], start..input.len()));
+        }
+
+        // 紧随重新解析的内容之后的、沿用的元素与上一个元素之间的距离。
+        let next_distance = synced_until.map(|index| {
+            let (last_start, last_line) = builder.prev;
+            let (next_start, next_line) = builder.locate(shift(old.get(index + 1).start));
+            (next_start - last_start, next_line - last_line)
+        });
+        let events_start = if self.blocks.is_empty() {
+            0
+        } else {
+            old.get(first).events_start
+        };
+        let replaced_end = match (synced_until, next_distance) {
+            (Some(index), Some((distance, line_distance))) => {
+                self.blocks[index + 1].distance = distance;
+                self.blocks[index + 1].line_distance = line_distance;
+                index + 1
+            }
+            _ => self.blocks.len(),
+        };
+
+        let old_ids: Vec<_> = self.blocks[first..replaced_end]
+            .iter()
+            .flat_map(|block| block.events.iter().filter_map(block_id_entered))
+            .collect();
+        let new_ids: Vec<_> = region
+            .iter()
+            .flat_map(|block| block.events.iter().filter_map(block_id_entered))
+            .collect();
+        let old_id_set: HashSet<_> = old_ids.iter().map(|id| id.value()).collect();
+        let new_id_set: HashSet<_> = new_ids.iter().map(|id| id.value()).collect();
+        if let Some(max) = new_id_set.iter().max() {
+            self.last_block_id = self.last_block_id.max(*max);
+        }
+
+        let region_len: usize = region.iter().map(|block| block.events.len()).sum();
+        self.blocks.splice(first..replaced_end, region);
+
+        Ok(Reparsed {
+            events: events_start..(events_start + region_len),
+            removed_block_ids: old_ids
+                .into_iter()
+                .filter(|id| !new_id_set.contains(&id.value()))
+                .collect(),
+            added_block_ids: new_ids
+                .into_iter()
+                .filter(|id| !old_id_set.contains(&id.value()))
+                .collect(),
+        })
+    }
+}
+
+/// 依次将顶层块级元素的事件转换为 [Block]。
+struct BlockBuilder<'a> {
+    input: &'a [u8],
+    /// 上一个元素的开头及其所在的行。
+    prev: (usize, usize),
+    /// 已知所在的行的某处，及其所在的行。用于计算之后的元素所在的行。
+    anchor: (usize, usize),
+}
+
+impl<'a> BlockBuilder<'a> {
+    fn new(input: &'a [u8], prev: (usize, usize), anchor: (usize, usize)) -> Self {
+        Self {
+            input,
+            prev,
+            anchor,
+        }
+    }
+
+    /// 返回位于 `anchor` 之后的 `position` 处，及其所在的行。
+    fn locate(&mut self, position: usize) -> (usize, usize) {
+        let (anchor, line) = self.anchor;
+        self.anchor = (
+            position,
+            line + count_line_breaks(&self.input[anchor..position]),
+        );
+        self.anchor
+    }
+
+    fn build(&mut self, events: &[Event], source: Range<usize>) -> Block {
+        let (start, line) = self.locate(source.start);
+        let lookahead = match events
+            .iter()
+            .find(|ev| !matches!(ev, Event::BlockAttachedInfo(_)))
+        {
+            Some(Event::EnterParagraph(BlockWithId { span, .. }))
+                if self.input[span.start()..].starts_with(b"{{") =>
+            {
+                call_opening_lookahead_end(self.input, span.start())
+                    .map_or(usize::MAX, |end| (end - start).max(source.len()))
+            }
+            _ => source.len(),
+        };
+        let events = events
+            .iter()
+            .map(|ev| {
+                let mut ev = ev.clone();
+                shift_event(&mut ev, -(start as isize), 1 - line as isize);
+                ev
+            })
+            .collect();
+        let block = Block {
+            distance: start - self.prev.0,
+            line_distance: line - self.prev.1,
+            len: source.len(),
+            lookahead,
+            events,
+        };
+        self.prev = (start, line);
+        block
+    }
+}
+
+/// 以 `{{` 开头的顶层段落来自未能构成调用的 `{{`。解析器在作出这一判断之前，会越过空行寻找
+/// 调用的名称，再越过空行寻找其后的 `||`、`??` 或 `}}`，因此可能读到段落之后很远的地方。
+///
+/// 返回解析器至多读到的位置（不含）。若可能读到输入的末尾，或其间出现了可能跨行的逐字文本
+/// 或注释，则返回 `None`。
+fn call_opening_lookahead_end(input: &[u8], opening: usize) -> Option<usize> {
+    let skip_whitespaces = |from: usize| {
+        from + input[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_whitespace())
+            .count()
+    };
+    let name_start = skip_whitespaces(opening + "{{".len());
+    let name_line_end = name_start
+        + input[name_start..]
+            .iter()
+            .take_while(|c| !matches!(c, b'\r' | b'\n'))
+            .count();
+    let end = skip_whitespaces(name_line_end) + "||".len();
+    (end <= input.len() && !input[opening..end].contains(&b'<')).then_some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{BLOCK_PIECES, Rng};
+
+    const INPUT: &str = "\
+= 标题 [*a*]\r\n\
+\n\
+foo\n\
+bar [[x|y]]\n\
+\n\
+> quote\n\
+> - [ ] item\n\
+\n\
+---\n\
+{&> .c}\n\
+{|\n\
+|| a || b\n\
+|}\n\
+\n\
+{{x\n\
+|| y\n\
+}}\n\
+\n\
+```\n\
+code\n\
+```\n\
+\n\
+<% c\n\
+\n\
+%>baz\n";
+
+    const INSERTIONS: &[&str] = &["", "\n", "\n\n", "a", "```\n", "{{x\n", "|}", "---\n", "> "];
+
+    fn parse(input: &str) -> Vec<Event> {
+        crate::parse(input.as_bytes())
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    /// 编辑 `input` 并进行增量解析，检查结果与重新完整解析时一致。
+    fn check(input: &str, state: &mut ReparseState, range: Range<usize>, text: &str) -> String {
+        let new_input = format!("{}{text}{}", &input[..range.start], &input[range.end..]);
+        let edit = Edit {
+            range: range.clone(),
+            new_len: text.len(),
+        };
+        let reparsed = state.reparse(new_input.as_bytes(), &edit).unwrap();
+
+        let events: Vec<_> = state.events().collect();
+        let expected = parse(&new_input);
+        assert!(
+            eq_ignoring_block_ids(&events, &expected),
+            "input: {input:?}, range: {range:?}, text: {text:?}",
+        );
+        let ids: Vec<_> = events.iter().filter_map(block_id_entered).collect();
+        let id_set: HashSet<_> = ids.iter().map(|id| id.value()).collect();
+        assert_eq!(id_set.len(), ids.len());
+        for id in &reparsed.added_block_ids {
+            assert!(id_set.contains(&id.value()));
+        }
+        for id in &reparsed.removed_block_ids {
+            assert!(!id_set.contains(&id.value()));
+        }
+
+        new_input
+    }
+
+    #[test]
+    fn reparse_matches_full_parse() {
+        let state = ReparseState::new(INPUT.as_bytes(), &parse(INPUT));
+        let boundaries: Vec<_> = (0..=INPUT.len())
+            .filter(|i| INPUT.is_char_boundary(*i))
+            .collect();
+
+        for (index, start) in boundaries.iter().enumerate() {
+            for end in boundaries[index..].iter().take(4) {
+                for text in INSERTIONS {
+                    check(INPUT, &mut state.clone(), *start..*end, text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reparse_works_repeatedly() {
+        let mut input = INPUT.to_string();
+        let mut state = ReparseState::new(input.as_bytes(), &parse(&input));
+
+        for (range, text) in [
+            (5..5, "b"),
+            (20..21, ""),
+            (0..0, "```\n"),
+            (0..4, ""),
+            (40..45, "{|\n"),
+            (30..30, "\r\n\r\n"),
+        ] {
+            input = check(&input, &mut state, range, text);
+        }
+    }
+
+    #[test]
+    fn reparse_handles_content_outside_block_spans() {
+        for (input, range, text) in [
+            ("> a\n>\nb\n", 8..8, "c"),
+            (";\n>\n-\n", 6..6, ""),
+            ("* \n---\n> ", 3..3, "# [= "),
+            ("= :\n>\n", 0..1, "# "),
+            ("; a\n<%%>{|x", 0..2, ""),
+        ] {
+            let mut state = ReparseState::new(input.as_bytes(), &parse(input));
+            check(input, &mut state, range, text);
+        }
+    }
+
+    #[test]
+    fn reparse_handles_call_opening_lookahead() {
+        for (input, range, text) in [
+            ("{{\n\n\n* ; \n\n%>", 11..11, "||: }}}"),
+            ("{{\n\nx\n\ny\n\nz", 5..5, "||"),
+        ] {
+            let mut state = ReparseState::new(input.as_bytes(), &parse(input));
+            check(input, &mut state, range, text);
+        }
+    }
+
+    #[test]
+    fn reparse_random_edits_matches_full_parse() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        for _ in 0..3000 {
+            let mut input = match rng.next(2) {
+                0 => rng.input(24),
+                _ => rng.input_from(BLOCK_PIECES, 16),
+            };
+            let mut state = ReparseState::new(input.as_bytes(), &parse(&input));
+            for _ in 0..3 {
+                let boundaries: Vec<_> = (0..=input.len())
+                    .filter(|i| input.is_char_boundary(*i))
+                    .collect();
+                let start = rng.next(boundaries.len());
+                let end = start + rng.next((boundaries.len() - start).min(4));
+                let text = rng.input(4);
+                input = check(
+                    &input,
+                    &mut state,
+                    boundaries[start]..boundaries[end],
+                    &text,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reparse_handles_trailing_attached_info() {
+        let mut input = "a\n\n{&> .c}".to_string();
+        let mut state = ReparseState::new(input.as_bytes(), &parse(&input));
+        for (range, text) in [(0..0, "b\n"), (12..12, "\nc"), (12..14, ""), (3..4, "")] {
+            input = check(&input, &mut state, range, text);
+        }
+    }
+
+    #[test]
+    fn reparse_reuses_unaffected_blocks() {
+        let input = "a\n\nb\n\nc\n\nd";
+        let mut state = ReparseState::new(input.as_bytes(), &parse(input));
+        let old_events: Vec<_> = state.events().collect();
+
+        let new_input = "a\n\nbb\n\nc\n\nd";
+        let reparsed = state
+            .reparse(new_input.as_bytes(), &Edit {
+                range: 4..4,
+                new_len: 1,
+            })
+            .unwrap();
+
+        let events: Vec<_> = state.events().collect();
+        let ids = |events: &[Event]| -> Vec<_> {
+            events
+                .iter()
+                .filter_map(block_id_entered)
+                .map(|id| id.value())
+                .collect()
+        };
+        assert_eq!(ids(&old_events), vec![1, 2, 3, 4]);
+        assert_eq!(ids(&events), vec![1, 6, 3, 4]);
+        assert_eq!(ids(&events[reparsed.events.clone()]), vec![1, 6]);
+        assert_eq!(
+            reparsed
+                .removed_block_ids
+                .iter()
+                .map(|id| id.value())
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(
+            reparsed
+                .added_block_ids
+                .iter()
+                .map(|id| id.value())
+                .collect::<Vec<_>>(),
+            vec![6]
+        );
+        assert_eq!(reparsed.events, 0..6);
+    }
+}
@@ -5,19 +5,32 @@
 
 pub mod compiling;
 pub mod executing;
+pub mod headings;
+#[cfg(all(feature = "block-id", feature = "source-span"))]
+pub mod incremental;
+pub mod plain_text;
+pub mod statistics;
+#[cfg(feature = "source-span")]
+pub mod streaming;
 
+#[cfg(feature = "source-span")]
+pub(crate) mod event_utils;
 pub(crate) mod utils;
 
-pub use rotext_core::{Error as ParseError, Event, Result};
+#[cfg(all(test, feature = "source-span"))]
+pub(crate) mod test_support;
+
+pub use rotext_core::{Error as ParseError, Event, Position, Result, diagnostics};
 
 pub use compiling::{
     CompiledItem, Error as CompilationError, NewCompileOptions as CompileOption,
     Restrictions as CompileRestrictions,
 };
-pub use executing::{NewExecutorOptions as ExecuteOptions, TagNameMap};
+pub use executing::{CodeBlockRendering, NewExecutorOptions as ExecuteOptions, TagNameMap};
 
 use rotext_core::{
-    BlockEventStreamInlineSegmentMapper, BlockParser, BlockStackEntry, InlineStackEntry,
+    BlockEventStreamInlineSegmentMapper, BlockParser, BlockStackEntry, DiagnosticSink,
+    InlineStackEntry,
 };
 
 use rotext_utils::stack::VecStack;
@@ -33,10 +46,37 @@ pub fn parse(
     BlockEventStreamInlineSegmentMapper::new(input, block_parser)
 }
 
+/// 同 [parse]，但会将解析时发现的问题报告给 `sink`，见 [diagnostics]。
+pub fn parse_with_diagnostics<'a>(
+    input: &'a [u8],
+    sink: DiagnosticSink<'a>,
+) -> BlockEventStreamInlineSegmentMapper<
+    'a,
+    BlockParser<'a, VecStack<BlockStackEntry>>,
+    VecStack<InlineStackEntry>,
+> {
+    let block_parser = BlockParser::new(input).with_diagnostic_sink(sink);
+
+    BlockEventStreamInlineSegmentMapper::new(input, block_parser).with_diagnostic_sink(sink)
+}
+
+/// 将整份输入视为单个段落进行解析，用于只含行内内容的场合。产出的事件以进入段落的事件开
+/// 始，以退出该段落的事件结束（输入为空白时则不产出任何事件）。
+pub fn parse_inline_only(
+    input: &[u8],
+) -> BlockEventStreamInlineSegmentMapper<
+    BlockParser<VecStack<BlockStackEntry>>,
+    VecStack<InlineStackEntry>,
+> {
+    let block_parser = BlockParser::new_inline_only(input);
+
+    BlockEventStreamInlineSegmentMapper::new(input, block_parser)
+}
+
 pub fn compile<'a>(
     input: &'a [u8],
     parsed: &[Event],
-    opts: &'a CompileOption,
+    opts: &'a CompileOption<'a>,
 ) -> compiling::Result<Vec<CompiledItem<'a>>> {
     let compiler = compiling::Compiler::new(opts);
     compiler.compile(input, parsed)
@@ -56,6 +96,9 @@ pub fn execute(
 
 #[cfg(test)]
 mod tests {
+    use std::{cell::RefCell, ops::Range};
+
+    use rotext_core::DiagnosticCode;
     use rotext_internal_test::{BlendContext, BlockContext, InlineContext};
 
     use rotext_utils::stack::VecStack;
@@ -77,4 +120,197 @@ mod tests {
         let ctx: BlendContext<VecStack<_>, VecStack<_>> = BlendContext::new();
         rotext_internal_test::suites::blend::run(&ctx);
     }
+
+    fn collect_diagnostics(input: &str) -> Vec<crate::diagnostics::Diagnostic> {
+        let diagnostics = RefCell::new(vec![]);
+        let sink = |diagnostic| diagnostics.borrow_mut().push(diagnostic);
+        crate::parse_with_diagnostics(input.as_bytes(), &sink)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        diagnostics.into_inner()
+    }
+
+    /// 诊断的种类及范围。
+    type ExpectedDiagnostic = (DiagnosticCode, Range<usize>);
+
+    #[test]
+    fn parse_with_diagnostics_reports_problems() {
+        use crate::diagnostics::DiagnosticCode::*;
+
+        let table: &[(&str, &[ExpectedDiagnostic])] = &[
+            ("[*a*] [[a]] {{a}} <`a`> <%a%>\n\n{|\n|}", &[]),
+            ("foo <`bar", &[(UnclosedVerbatimEscaping, 4..6)]),
+            ("foo\n<% bar", &[(UnclosedComment, 4..6)]),
+            ("[*a", &[(UnclosedInlineElement, 0..2)]),
+            ("[*[/a*]", &[(UnclosedInlineElement, 2..4)]),
+            ("[;a:b", &[(UnclosedInlineElement, 0..2)]),
+            ("a [``b", &[(UnclosedInlineElement, 2..5)]),
+            ("[=d100\nfoo", &[(UnclosedInlineElement, 0..2)]),
+            ("[{{x|a", &[(UnclosedInlineElement, 0..3)]),
+            ("[[x", &[(WikiLinkOpeningAsText, 0..2)]),
+            ("[[<`x", &[
+                (UnclosedVerbatimEscaping, 2..4),
+                (WikiLinkOpeningAsText, 0..2),
+            ]),
+            ("{{x", &[(UnclosedBlockCall, 0..2)]),
+            ("> {{x\n> || a\n\nb", &[(UnclosedBlockCall, 2..4)]),
+            ("{{x\nfoo", &[(BlockCallOpeningAsText, 0..2)]),
+            ("{{x <`foo", &[
+                (BlockCallOpeningAsText, 0..2),
+                (UnclosedVerbatimEscaping, 4..6),
+            ]),
+            ("{|\n|| a", &[(UnclosedTable, 0..2)]),
+        ];
+
+        for (input, expected) in table {
+            let actual: Vec<_> = collect_diagnostics(input)
+                .into_iter()
+                .map(|diagnostic| (diagnostic.code, diagnostic.range))
+                .collect();
+            assert_eq!(actual, *expected, "input: {input:?}");
+        }
+    }
+
+    #[cfg(feature = "line-number")]
+    #[test]
+    fn parse_with_diagnostics_reports_lines() {
+        let diagnostics = collect_diagnostics("a\r\nb\rc\n\n{{x||[*d\n\n{|");
+        let actual: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.line.value())
+            .collect();
+
+        assert_eq!(actual, vec![5, 7, 5]);
+    }
+
+    #[cfg(feature = "position")]
+    #[test]
+    fn parse_reports_positions() {
+        use crate::Event;
+
+        let input = "= 标题\r\n\n😀 [*a\n\n---";
+        let actual: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap()
+            .into_iter()
+            .filter_map(|ev| match ev {
+                Event::ExitBlock(data) => Some((data.start, data.end)),
+                Event::ThematicBreak(data) => Some((data.start, data.end)),
+                _ => None,
+            })
+            .map(|(start, end)| [start, end].map(|p| (p.line(), p.column(), p.utf16_column())))
+            .collect();
+        assert_eq!(actual, vec![
+            [(1, 1, 1), (1, 9, 5)],
+            [(3, 1, 1), (3, 9, 7)],
+            [(5, 1, 1), (5, 4, 4)],
+        ]);
+
+        let diagnostics = collect_diagnostics(input);
+        let actual: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let p = diagnostic.position;
+                (p.line(), p.column(), p.utf16_column())
+            })
+            .collect();
+        assert_eq!(actual, vec![(3, 6, 4)]);
+    }
+
+    #[cfg(feature = "position")]
+    #[test]
+    fn parse_reports_positions_on_entering() {
+        use crate::Event;
+        use rotext_core::events::{BlockWithId, ListItem, OrderedList};
+
+        let input = "😀\n\n> # 中\n>  {{x}}";
+        let actual: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap()
+            .into_iter()
+            .filter_map(|ev| match ev {
+                Event::EnterParagraph(BlockWithId { position, .. })
+                | Event::EnterBlockQuote(BlockWithId { position, .. })
+                | Event::EnterOrderedList(OrderedList { position, .. })
+                | Event::EnterListItem(ListItem { position, .. })
+                | Event::EnterCallOnTemplate(rotext_core::events::Call::Block {
+                    position, ..
+                }) => Some(position),
+                _ => None,
+            })
+            .map(|p| (p.line(), p.column(), p.utf16_column()))
+            .collect();
+        assert_eq!(actual, vec![
+            (1, 1, 1),
+            (3, 1, 1),
+            (3, 3, 3),
+            (3, 3, 3),
+            (3, 5, 5),
+            (4, 4, 4),
+        ]);
+    }
+
+    #[cfg(feature = "source-span")]
+    #[test]
+    fn parse_reports_source_spans() {
+        let table: &[(&str, &[&str])] = &[
+            ("= a [/b/] ==\nc", &[
+                "= ",
+                "[/",
+                "[/b/]",
+                "= a [/b/] ==",
+                "",
+                "c",
+            ]),
+            ("{|\n|| a\n|}", &["{|", "||", "", "a", "{|\n|| a\n|}"]),
+            ("{{x\n|| [*a", &[
+                "{{x",
+                "||",
+                "",
+                "[*",
+                "[*a",
+                "[*a",
+                "{{x\n|| [*a",
+            ]),
+            ("{{{p||\n[{{q|<`r`>}}]\n}}}", &[
+                "{{{p",
+                "||",
+                "",
+                "[{{q",
+                "|",
+                "[{{q|<`r`>}}]",
+                "[{{q|<`r`>}}]",
+                "{{{p||\n[{{q|<`r`>}}]\n}}}",
+            ]),
+            ("```\nx\n```\n---", &["```", "", "```\nx\n```", "---"]),
+            ("# a\n# b", &[
+                "# ", "# ", "", "a", "# a", "# ", "", "b", "# b", "# a\n# b",
+            ]),
+            ("> ; t\n> : d", &[
+                "> ",
+                "; ",
+                "; ",
+                "",
+                "t",
+                "; t",
+                ": ",
+                "",
+                "d",
+                ": d",
+                "; t\n> : d",
+                "> ; t\n> : d",
+            ]),
+        ];
+
+        for (input, expected) in table {
+            let actual: Vec<_> = crate::parse(input.as_bytes())
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap()
+                .into_iter()
+                .filter_map(|ev| ev.span())
+                .map(|span| &input[span.range()])
+                .collect();
+            assert_eq!(actual, *expected, "input: {input:?}");
+        }
+    }
 }
@@ -0,0 +1,309 @@
+use rotext_core::{
+    Event,
+    events::{Call, VerbatimEscaping},
+};
+
+/// 提取纯文本时对注音的处理方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RubyHandling {
+    /// 只保留被注音的文本。
+    #[default]
+    BaseOnly,
+    /// 只保留注音文本。
+    TextOnly,
+    /// 保留被注音的文本，并将注音文本置于其后的括号之中，如 `汉字(hàn zì)`。
+    BaseWithTextInParentheses,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlainTextOptions {
+    pub ruby_handling: RubyHandling,
+}
+
+/// 从 `parsed` 中提取纯文本，用于搜索索引、通知预览等场合。`parsed` 是属于 `Blend` 分组的
+/// 事件。
+///
+/// - 标记本身会被去掉，不同的块级元素（包括表格的单元格）之间以换行分隔；
+/// - 字符值引用会被展开为其所指代的字符；
+/// - 骰子表达式保留其源码，如 `[=d100]`；
+/// - 引用链接保留其源码，如 `>>TP.42`；
+/// - 调用的名称与逐字参数会被去掉，一般参数的内容则会被保留；
+/// - 附加信息会被去掉。
+pub fn extract_plain_text(input: &[u8], parsed: &[Event], opts: &PlainTextOptions) -> String {
+    let mut extractor = Extractor {
+        input,
+        opts,
+        text: String::new(),
+        stack: vec![],
+    };
+    for ev in parsed {
+        extractor.process_event(ev);
+    }
+
+    extractor.text.trim_end().to_string()
+}
+
+struct Extractor<'a> {
+    input: &'a [u8],
+    opts: &'a PlainTextOptions,
+    text: String,
+    stack: Vec<Frame>,
+}
+
+struct Frame {
+    kind: FrameKind,
+    /// 该层中的内容是否不计入纯文本。
+    is_skipping: bool,
+}
+
+#[derive(PartialEq, Eq)]
+enum FrameKind {
+    Block,
+    Inline,
+    Ruby,
+    RubyText,
+    Dicexp,
+    BlockCall,
+    InlineCall,
+}
+
+impl Extractor<'_> {
+    fn process_event(&mut self, ev: &Event) {
+        #[rotext_internal_macros::ensure_cases_for_event(
+            prefix = Event,
+            group = Blend,
+        )]
+        match ev {
+            Event::Raw(content) => {
+                if !self.is_skipping() {
+                    let char = decode_numeric_character_reference(&self.input[content.clone()]);
+                    self.text.push(char);
+                }
+            }
+            Event::Text(content)
+            | Event::ExternalLink(content)
+            | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                self.push_input_text(content.clone());
+            }
+            Event::NewLine(_) => self.push_str("\n"),
+            Event::RefLink(address) => {
+                self.push_str(">>");
+                self.push_input_text(address.clone());
+            }
+            Event::ThematicBreak(_)
+            | Event::IndicateTableCaption(_)
+            | Event::IndicateTableRow(_)
+            | Event::IndicateTableHeaderCell(_)
+            | Event::IndicateTableDataCell(_) => self.break_line(),
+            Event::EnterParagraph(_)
+            | Event::EnterHeading1(_)
+            | Event::EnterHeading2(_)
+            | Event::EnterHeading3(_)
+            | Event::EnterHeading4(_)
+            | Event::EnterHeading5(_)
+            | Event::EnterHeading6(_)
+            | Event::EnterBlockQuote(_)
+            | Event::EnterOrderedList(_)
+            | Event::EnterUnorderedList(_)
+            | Event::EnterListItem(_)
+            | Event::EnterDescriptionList(_)
+            | Event::EnterDescriptionTerm(_)
+            | Event::EnterDescriptionDetails(_)
+            | Event::EnterTable(_) => {
+                self.break_line();
+                self.push_frame(FrameKind::Block, false);
+            }
+            // 代码块的信息字符串不计入纯文本，直到到达代码部分为止。
+            Event::EnterCodeBlock(_) => {
+                self.break_line();
+                self.push_frame(FrameKind::Block, true);
+            }
+            Event::IndicateCodeBlockCode(_) => self.set_top_skipping(false),
+            // 调用的名称不计入纯文本，直到到达其一般参数为止。
+            Event::EnterCallOnTemplate(call)
+            | Event::EnterCallOnExtension(call)
+            | Event::EnterParameterFilling(call) => {
+                let kind = match call {
+                    Call::Block { .. } => FrameKind::BlockCall,
+                    Call::Inline { .. } => FrameKind::InlineCall,
+                };
+                self.push_frame(kind, true);
+            }
+            Event::IndicateCallNormalArgument(_) | Event::IndicateParameterFillingFallback(_) => {
+                self.set_top_skipping(false);
+                if self.stack.last().map(|frame| &frame.kind) == Some(&FrameKind::BlockCall) {
+                    self.break_line();
+                }
+            }
+            Event::IndicateCallVerbatimArgument(_) => self.set_top_skipping(true),
+            Event::BlockAttachedInfo(_) | Event::InlineAttachedInfo(_) => {}
+            Event::ExitBlock(_) => {
+                self.stack.pop();
+                self.break_line();
+            }
+            Event::EnterCodeSpan(_)
+            | Event::EnterEmphasis(_)
+            | Event::EnterStrong(_)
+            | Event::EnterStrikethrough(_)
+            | Event::EnterWikiLink(_) => self.push_frame(FrameKind::Inline, false),
+            Event::EnterRuby(_) => {
+                let is_skipping = self.opts.ruby_handling == RubyHandling::TextOnly;
+                self.push_frame(FrameKind::Ruby, is_skipping);
+            }
+            Event::EnterRubyText(_) => {
+                // 注音文本是否计入纯文本不取决于被注音的文本，而是取决于注音本身所在之处。
+                let is_outer_skipping =
+                    self.stack.len() >= 2 && self.stack[self.stack.len() - 2].is_skipping;
+                let is_skipping =
+                    is_outer_skipping || self.opts.ruby_handling == RubyHandling::BaseOnly;
+                self.stack.push(Frame {
+                    kind: FrameKind::RubyText,
+                    is_skipping,
+                });
+                if self.opts.ruby_handling == RubyHandling::BaseWithTextInParentheses {
+                    self.push_str("(");
+                }
+            }
+            Event::EnterDicexp(_) => {
+                self.push_str("[=");
+                self.push_frame(FrameKind::Dicexp, false);
+            }
+            Event::EnterAssigningDicexp(dicexp) => {
+                self.push_str("[@");
+                self.push_input_text(dicexp.name.clone());
+                self.push_str("=");
+                self.push_frame(FrameKind::Dicexp, false);
+            }
+            Event::ExitInline(_) => {
+                let closing = match self.stack.last().map(|frame| &frame.kind) {
+                    Some(FrameKind::Dicexp) => Some("]"),
+                    Some(FrameKind::RubyText)
+                        if self.opts.ruby_handling == RubyHandling::BaseWithTextInParentheses =>
+                    {
+                        Some(")")
+                    }
+                    _ => None,
+                };
+                if let Some(closing) = closing {
+                    self.push_str(closing);
+                }
+                self.stack.pop();
+            }
+        }
+    }
+
+    fn is_skipping(&self) -> bool {
+        self.stack.last().is_some_and(|frame| frame.is_skipping)
+    }
+
+    fn push_frame(&mut self, kind: FrameKind, is_skipping: bool) {
+        let is_skipping = self.is_skipping() || is_skipping;
+        self.stack.push(Frame { kind, is_skipping });
+    }
+
+    /// 设置最内层是否不计入纯文本，外层不计入时则总是不计入。
+    fn set_top_skipping(&mut self, is_skipping: bool) {
+        let is_outer_skipping =
+            self.stack.len() >= 2 && self.stack[self.stack.len() - 2].is_skipping;
+        if let Some(top) = self.stack.last_mut() {
+            top.is_skipping = is_outer_skipping || is_skipping;
+        }
+    }
+
+    fn push_input_text(&mut self, range: std::ops::Range<usize>) {
+        if !self.is_skipping() {
+            self.text
+                .push_str(&String::from_utf8_lossy(&self.input[range]));
+        }
+    }
+
+    fn push_str(&mut self, str: &str) {
+        if !self.is_skipping() {
+            self.text.push_str(str);
+        }
+    }
+
+    /// 确保之后的内容位于新的一行。
+    fn break_line(&mut self) {
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+        }
+    }
+}
+
+/// 展开数字字符值引用（如 `&#60;` 或 `&#x3c;`）。所指代的字符不存在时，返回 U+FFFD。
+pub(crate) fn decode_numeric_character_reference(raw: &[u8]) -> char {
+    let digits = &raw["&#".len()..raw.len() - ";".len()];
+    let code = match digits.first() {
+        Some(b'x' | b'X') => u32::from_str_radix(str_from_ascii(&digits[1..]), 16),
+        _ => str_from_ascii(digits).parse(),
+    };
+
+    code.ok()
+        .filter(|code| *code != 0)
+        .and_then(char::from_u32)
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+fn str_from_ascii(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(input: &str, ruby_handling: RubyHandling) -> String {
+        let parsed: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<rotext_core::Result<_>>()
+            .unwrap();
+        extract_plain_text(input.as_bytes(), &parsed, &PlainTextOptions {
+            ruby_handling,
+        })
+    }
+
+    #[test]
+    fn extract_plain_text_works() {
+        let table: &[(&str, &str)] = &[
+            ("", ""),
+            ("a\nb\n\nc", "a\nb\nc"),
+            ("= 标题 =\n正文[*加粗*]与[/斜体/]", "标题\n正文加粗与斜体"),
+            ("&#60;&#x3c;&#0;", "<<\u{FFFD}"),
+            ("投出了 [=d100]。", "投出了 [=d100]。"),
+            ("共有 [@_balls=d100] 颗", "共有 [@_balls=d100] 颗"),
+            (">>TP.42", ">>TP.42"),
+            (
+                "[[页面|链接]] https://example.com",
+                "链接 https://example.com",
+            ),
+            ("* a\n* b\n\n---\n\n> c", "a\nb\nc"),
+            (
+                "{|\n|+ 标题\n!! A !! B\n|-\n|| 1 || 2\n|}",
+                "标题\nA\nB\n1\n2",
+            ),
+            ("```rust\nfn main() {}\n```", "fn main() {}"),
+            ("{&> .foo}\n段落", "段落"),
+            ("{{#折叠|| `标题=总结||内容}}", "内容"),
+            ("文[{#刮开|剧透}]字", "文剧透字"),
+        ];
+
+        for (input, expected) in table {
+            let actual = extract(input, RubyHandling::BaseOnly);
+            assert_eq!(actual, *expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn extract_plain_text_handles_ruby() {
+        let input = "[;汉字:hàn zì]";
+        let table = [
+            (RubyHandling::BaseOnly, "汉字"),
+            (RubyHandling::TextOnly, "hàn zì"),
+            (RubyHandling::BaseWithTextInParentheses, "汉字(hàn zì)"),
+        ];
+
+        for (ruby_handling, expected) in table {
+            assert_eq!(extract(input, ruby_handling), expected, "{ruby_handling:?}");
+        }
+    }
+}
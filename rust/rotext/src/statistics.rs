@@ -0,0 +1,322 @@
+use rotext_core::{
+    BlockId, Event,
+    events::{Call, VerbatimEscaping},
+};
+
+use crate::plain_text::decode_numeric_character_reference;
+
+/// 一段内容的统计数据。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// 文本内容中可见字符（空白与控制字符以外的字符）的个数。
+    pub characters: usize,
+    /// 中日韩文字（汉字、假名与谚文）的字数，不含标点。
+    pub cjk_characters: usize,
+    /// 由字母与数字组成的（非中日韩文字的）单词的词数。单词内部的撇号不会将其分开。
+    pub latin_words: usize,
+    /// 链接（包括内部链接、外部链接与引用链接）的数量。
+    pub links: usize,
+    /// 调用（包括调用模板与调用扩展）的数量。
+    pub calls: usize,
+    pub tables: usize,
+    pub code_blocks: usize,
+}
+
+/// 一个块级元素的统计数据。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockStatistics {
+    pub id: BlockId,
+    /// 该块级元素（包括其本身）中的内容的统计数据。例如，表格的统计数据中，`tables` 至少
+    /// 为 1。
+    pub statistics: Statistics,
+}
+
+/// 一份文档的统计数据。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentStatistics {
+    /// 整份文档的统计数据。
+    pub document: Statistics,
+    /// 各个块级元素的统计数据，按块级元素在文档中开始的先后排列。
+    pub blocks: Vec<BlockStatistics>,
+}
+
+/// 统计 `parsed` 所对应文档的字数等数据，用于限制帖子长度等场合。`parsed` 是属于 `Blend`
+/// 分组的事件。
+///
+/// 字数与词数只取决于文本内容，标记本身、外部链接与引用链接的地址以及附加信息都不计入其中。
+pub fn collect_statistics(input: &[u8], parsed: &[Event]) -> DocumentStatistics {
+    let mut collector = Collector {
+        input,
+        stats: DocumentStatistics::default(),
+        open_blocks: vec![],
+        is_in_latin_word: false,
+    };
+    for ev in parsed {
+        collector.process_event(ev);
+    }
+    // `parsed` 不完整时，可能有尚未退出的块级元素。
+    while !collector.open_blocks.is_empty() {
+        collector.exit_block();
+    }
+
+    collector.stats
+}
+
+struct Collector<'a> {
+    input: &'a [u8],
+    stats: DocumentStatistics,
+    /// 尚未退出的各个块级元素在 `stats.blocks` 中的位置。计数时只更新最内层的元素，其统计
+    /// 数据在退出时再并入外层的元素。
+    open_blocks: Vec<usize>,
+    /// 上一个字符是否属于（非中日韩文字的）单词。
+    is_in_latin_word: bool,
+}
+
+impl Collector<'_> {
+    fn process_event(&mut self, ev: &Event) {
+        #[rotext_internal_macros::ensure_cases_for_event(
+            prefix = Event,
+            group = Blend,
+        )]
+        match ev {
+            Event::Raw(content) => {
+                let char = decode_numeric_character_reference(&self.input[content.clone()]);
+                self.count_char(char);
+            }
+            Event::Text(content) | Event::VerbatimEscaping(VerbatimEscaping { content, .. }) => {
+                for char in String::from_utf8_lossy(&self.input[content.clone()]).chars() {
+                    self.count_char(char);
+                }
+            }
+            Event::NewLine(_)
+            | Event::IndicateCodeBlockCode(_)
+            | Event::IndicateTableCaption(_)
+            | Event::IndicateTableRow(_)
+            | Event::IndicateTableHeaderCell(_)
+            | Event::IndicateTableDataCell(_)
+            | Event::IndicateCallNormalArgument(_)
+            | Event::IndicateCallVerbatimArgument(_)
+            | Event::IndicateParameterFillingFallback(_)
+            | Event::BlockAttachedInfo(_)
+            | Event::InlineAttachedInfo(_) => self.is_in_latin_word = false,
+            Event::ThematicBreak(data) => {
+                self.enter_block(data.id);
+                self.exit_block();
+            }
+            Event::EnterParagraph(data)
+            | Event::EnterHeading1(data)
+            | Event::EnterHeading2(data)
+            | Event::EnterHeading3(data)
+            | Event::EnterHeading4(data)
+            | Event::EnterHeading5(data)
+            | Event::EnterHeading6(data)
+            | Event::EnterBlockQuote(data)
+            | Event::EnterUnorderedList(data)
+            | Event::EnterDescriptionList(data)
+            | Event::EnterDescriptionTerm(data)
+            | Event::EnterDescriptionDetails(data) => self.enter_block(data.id),
+            Event::EnterOrderedList(data) => self.enter_block(data.id),
+            Event::EnterListItem(data) => self.enter_block(data.id),
+            Event::EnterCodeBlock(data) => {
+                self.enter_block(data.id);
+                self.count(|stats| stats.code_blocks += 1);
+            }
+            Event::EnterTable(data) => {
+                self.enter_block(data.id);
+                self.count(|stats| stats.tables += 1);
+            }
+            Event::EnterCallOnTemplate(call) | Event::EnterCallOnExtension(call) => {
+                if let Call::Block { id, .. } = call {
+                    self.enter_block(*id);
+                }
+                self.count(|stats| stats.calls += 1);
+            }
+            Event::EnterParameterFilling(call) => {
+                if let Call::Block { id, .. } = call {
+                    self.enter_block(*id);
+                }
+            }
+            Event::ExitBlock(_) => self.exit_block(),
+            Event::RefLink(_) | Event::ExternalLink(_) => {
+                self.is_in_latin_word = false;
+                self.count(|stats| stats.links += 1);
+            }
+            Event::EnterWikiLink(_) => self.count(|stats| stats.links += 1),
+            // 行内元素的边界不会将单词分开，如 `foo[*bar*]` 只算作一个单词。
+            Event::EnterCodeSpan(_)
+            | Event::EnterEmphasis(_)
+            | Event::EnterStrong(_)
+            | Event::EnterStrikethrough(_)
+            | Event::EnterRuby(_)
+            | Event::EnterRubyText(_)
+            | Event::EnterDicexp(_)
+            | Event::EnterAssigningDicexp(_)
+            | Event::ExitInline(_) => {}
+        }
+    }
+
+    fn enter_block(&mut self, id: BlockId) {
+        self.is_in_latin_word = false;
+        self.open_blocks.push(self.stats.blocks.len());
+        self.stats.blocks.push(BlockStatistics {
+            id,
+            statistics: Statistics::default(),
+        });
+    }
+
+    fn exit_block(&mut self) {
+        self.is_in_latin_word = false;
+        let Some(index) = self.open_blocks.pop() else {
+            return;
+        };
+        if let Some(parent) = self.open_blocks.last() {
+            let inner = self.stats.blocks[index].statistics.clone();
+            self.stats.blocks[*parent].statistics.add(&inner);
+        }
+    }
+
+    fn count_char(&mut self, char: char) {
+        if !char.is_whitespace() && !char.is_control() {
+            self.count(|stats| stats.characters += 1);
+        }
+        if is_cjk_character(char) {
+            self.is_in_latin_word = false;
+            self.count(|stats| stats.cjk_characters += 1);
+        } else if char.is_alphanumeric() {
+            if !self.is_in_latin_word {
+                self.is_in_latin_word = true;
+                self.count(|stats| stats.latin_words += 1);
+            }
+        } else if !(self.is_in_latin_word && matches!(char, '\'' | '’')) {
+            self.is_in_latin_word = false;
+        }
+    }
+
+    /// 将 `f` 作用于整份文档以及最内层的尚未退出的块级元素的统计数据。
+    fn count(&mut self, f: impl Fn(&mut Statistics)) {
+        f(&mut self.stats.document);
+        if let Some(index) = self.open_blocks.last() {
+            f(&mut self.stats.blocks[*index].statistics);
+        }
+    }
+}
+
+impl Statistics {
+    fn add(&mut self, other: &Statistics) {
+        self.characters += other.characters;
+        self.cjk_characters += other.cjk_characters;
+        self.latin_words += other.latin_words;
+        self.links += other.links;
+        self.calls += other.calls;
+        self.tables += other.tables;
+        self.code_blocks += other.code_blocks;
+    }
+}
+
+/// 判断 `char` 是否为中日韩文字，即汉字、假名或谚文。
+fn is_cjk_character(char: char) -> bool {
+    matches!(char,
+        // 汉字。
+        '\u{3005}' | '\u{3007}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2EBEF}'
+        | '\u{2F800}'..='\u{2FA1F}'
+        | '\u{30000}'..='\u{323AF}'
+        // 假名。
+        | '\u{3041}'..='\u{3096}'
+        | '\u{309D}'..='\u{309F}'
+        | '\u{30A1}'..='\u{30FA}'
+        | '\u{30FC}'..='\u{30FF}'
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{FF66}'..='\u{FF9D}'
+        // 谚文。
+        | '\u{1100}'..='\u{11FF}'
+        | '\u{3131}'..='\u{318E}'
+        | '\u{AC00}'..='\u{D7A3}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(input: &str) -> DocumentStatistics {
+        let parsed: Vec<_> = crate::parse(input.as_bytes())
+            .collect::<rotext_core::Result<_>>()
+            .unwrap();
+        collect_statistics(input.as_bytes(), &parsed)
+    }
+
+    #[test]
+    fn collect_statistics_counts_characters_and_words() {
+        // 输入，可见字符数，中日韩文字的字数，单词的词数。
+        let table: &[(&str, usize, usize, usize)] = &[
+            ("", 0, 0, 0),
+            ("你好，世界！", 6, 4, 0),
+            ("Hello, world!", 12, 0, 2),
+            ("用 Rust 写的 parser", 13, 3, 2),
+            ("ひらがな・カタカナ、한국어", 13, 11, 0),
+            ("don't stop", 9, 0, 2),
+            ("foo[*bar*] baz", 9, 0, 2),
+            ("foo\nbar", 6, 0, 2),
+            ("&#x4E2D;&#25991;&#65;", 3, 2, 1),
+            ("[*加粗*]与[`code`]", 7, 3, 1),
+            ("https://example.com 与 >>TP.42", 1, 1, 0),
+            ("{&> .foo}\n段落", 2, 2, 0),
+            ("```rust\nfn main() {}\n```", 14, 0, 3),
+            ("a\t&#x3000;&#x7F;b", 2, 0, 2),
+        ];
+
+        for (input, characters, cjk_characters, latin_words) in table {
+            let stats = collect(input).document;
+            assert_eq!(
+                (stats.characters, stats.cjk_characters, stats.latin_words),
+                (*characters, *cjk_characters, *latin_words),
+                "input: {input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn collect_statistics_counts_elements() {
+        let input = "[[页面]] https://example.com >>TP.42\n\n{|\n|| [{#Span|a}]\n|}\n\n```\ncode\n```\n\n{{#Div}}";
+        let stats = collect(input).document;
+
+        assert_eq!(stats.links, 3);
+        assert_eq!(stats.calls, 2);
+        assert_eq!(stats.tables, 1);
+        assert_eq!(stats.code_blocks, 1);
+    }
+
+    #[test]
+    fn collect_statistics_counts_per_block() {
+        let input = "= 标题 =\n\n> 引用 quote\n>\n> * 列表\n\n---";
+        let stats = collect(input);
+
+        let actual: Vec<_> = stats
+            .blocks
+            .iter()
+            .map(|block| {
+                (
+                    block.statistics.characters,
+                    block.statistics.cjk_characters,
+                    block.statistics.latin_words,
+                )
+            })
+            .collect();
+        // 标题、块引用、段落、无序列表、列表项、列表项中的段落、分割线。
+        assert_eq!(actual, vec![
+            (2, 2, 0),
+            (9, 4, 1),
+            (7, 2, 1),
+            (2, 2, 0),
+            (2, 2, 0),
+            (2, 2, 0),
+            (0, 0, 0)
+        ]);
+        assert_eq!(stats.document.characters, 11);
+        assert_eq!(stats.document.cjk_characters, 6);
+    }
+}
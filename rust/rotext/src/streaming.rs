@@ -0,0 +1,329 @@
+//! 流式解析：分段接收输入，每当有顶层块级元素确定下来时便将其产出，只缓存尚未确定的部分。
+
+use rotext_core::{BlockEventStreamInlineSegmentMapper, BlockId, BlockParser, Event, LineNumber};
+use rotext_utils::stack::VecStack;
+
+#[cfg(feature = "line-number")]
+use crate::event_utils::count_line_breaks;
+use crate::event_utils::{TopLevelBlock, TopLevelBlockSplitter, has_line_break};
+
+/// 缓存的输入短于此长度时，每次有新的一行时都重新解析。
+const MIN_LAZY_REPARSE_LEN: usize = 4096;
+
+/// 流式解析产出的一段内容，由完整的顶层块级元素组成。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// 这段内容的源码。
+    pub input: Vec<u8>,
+    /// 这段内容在整份输入中的开始处。
+    pub offset: usize,
+    /// 解析这段内容所得的、属于 `Blend` 分组的事件。其中的范围相对于 `input` 而言，行号与
+    /// 块级元素的 ID 则与解析整份输入时的一致。
+    pub events: Vec<Event>,
+}
+
+/// 分段接收输入的解析器。
+///
+/// 只解析已经完整的行。某个顶层块级元素之后、隔着空白出现了从新的一行开始的另一个顶层块
+/// 级元素时，前者便被视为已经确定。
+///
+/// 每次解析都从缓存的输入（即最后一个尚未确定的顶层块级元素）的开头开始。为避免单个很长的
+/// 块级元素（如代码块、表格，或未闭合的调用、注释）导致每次追加输入都重新解析整个元素，缓存
+/// 的输入较长时，只在其自上次解析以来增长了一半以上时才会重新解析。因此解析的总开销与输入
+/// 的长度成线性关系，代价是这种情况下元素确定之后可能要再等输入增长一些才会被产出。
+pub struct StreamingParser {
+    /// 尚未产出的输入。总是从某行的开头开始，且此前的输入由完整的顶层块级元素组成。
+    buffer: Vec<u8>,
+    /// `buffer` 在整份输入中的开始处。
+    offset: usize,
+    /// `buffer` 开头所在的行。
+    line: LineNumber,
+    /// 已产出的最后一个块级元素的 ID。
+    last_block_id: BlockId,
+    /// 上次未能产出任何内容的解析所解析的长度。
+    parsed_len: usize,
+}
+
+impl StreamingParser {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            buffer: vec![],
+            offset: 0,
+            line: LineNumber::new_universal(1),
+            last_block_id: BlockId::new_universal(0),
+            parsed_len: 0,
+        }
+    }
+
+    /// 追加一段输入。若有顶层块级元素因此确定下来，则将其产出。
+    pub fn push(&mut self, chunk: &[u8]) -> crate::Result<Option<Segment>> {
+        self.buffer.extend_from_slice(chunk);
+        // 没有新的一行开始时，不会有顶层块级元素确定下来。
+        if !has_line_break(chunk) {
+            return Ok(None);
+        }
+
+        self.parse(false)
+    }
+
+    /// 结束输入，产出剩余的内容。
+    pub fn finish(mut self) -> crate::Result<Option<Segment>> {
+        self.parse(true)
+    }
+
+    fn parse(&mut self, is_finished: bool) -> crate::Result<Option<Segment>> {
+        let input = if is_finished {
+            &self.buffer[..]
+        } else {
+            let complete_len = complete_lines_len(&self.buffer);
+            if complete_len <= self.parsed_len
+                || (self.parsed_len >= MIN_LAZY_REPARSE_LEN
+                    && complete_len < self.parsed_len + self.parsed_len / 2)
+            {
+                return Ok(None);
+            }
+            &self.buffer[..complete_len]
+        };
+
+        let block_parser: BlockParser<VecStack<_>> =
+            BlockParser::new(input).starting_at(0, self.line, self.last_block_id);
+        let parser: BlockEventStreamInlineSegmentMapper<_, VecStack<_>> =
+            BlockEventStreamInlineSegmentMapper::new(input, block_parser);
+
+        let mut events = vec![];
+        let mut splitter = TopLevelBlockSplitter::new();
+        let mut last_block: Option<TopLevelBlock> = None;
+        // 已确定的内容在 `events` 与 `buffer` 中的末尾。
+        let mut determined: Option<(usize, usize)> = None;
+        for ev in parser {
+            events.push(ev?);
+            let Some(block) = splitter.push(&events) else {
+                continue;
+            };
+            if let Some(prev) = last_block.replace(block.clone()) {
+                // 两者之间若有空白以外的内容（如注释），则其归属尚不确定。两者的范围也可能重叠
+                // （如前者因未闭合的调用而吞掉了后者开头所在的行），这时同样尚不确定。
+                let Some(gap) = input.get(prev.source.end..block.source.start) else {
+                    continue;
+                };
+                if has_line_break(gap) && gap.iter().all(u8::is_ascii_whitespace) {
+                    // 从下一个块级元素所在行的开头截断，以使其中的列不发生变化。
+                    let line_start = input[..block.source.start]
+                        .iter()
+                        .rposition(|c| matches!(c, b'\r' | b'\n'))
+                        .map_or(0, |index| index + 1);
+                    determined = Some((prev.events.end, line_start));
+                }
+            }
+        }
+        if is_finished {
+            determined = Some((events.len(), self.buffer.len()));
+        }
+
+        let Some((events_end, input_end)) = determined.filter(|(_, end)| *end > 0) else {
+            self.parsed_len = input.len();
+            return Ok(None);
+        };
+        self.parsed_len = 0;
+        events.truncate(events_end);
+        let input: Vec<u8> = self.buffer.drain(..input_end).collect();
+
+        #[cfg(feature = "line-number")]
+        {
+            self.line = LineNumber::new(self.line.value() + count_line_breaks(&input));
+        }
+        #[cfg(feature = "block-id")]
+        if let Some(id) = events
+            .iter()
+            .filter_map(crate::event_utils::block_id)
+            .map(|id| id.value())
+            .max()
+        {
+            self.last_block_id = BlockId::new(id);
+        }
+        let offset = self.offset;
+        self.offset += input_end;
+
+        Ok(Some(Segment {
+            input,
+            offset,
+            events,
+        }))
+    }
+}
+
+/// `buffer` 中由完整的行组成的部分的长度。位于末尾的 `\r` 之后可能还有 `\n`，因此不视为完
+/// 整的换行。
+fn complete_lines_len(buffer: &[u8]) -> usize {
+    let line_end = match buffer.iter().rposition(|c| matches!(c, b'\r' | b'\n')) {
+        Some(index) if buffer[index] == b'\r' && index + 1 == buffer.len() => buffer[..index]
+            .iter()
+            .rposition(|c| matches!(c, b'\r' | b'\n')),
+        index => index,
+    };
+    line_end.map_or(0, |index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_utils::shift_event;
+    use crate::test_support::Rng;
+
+    const INPUT: &str = "\
+= 标题 [*a*]\r\n\
+\n\
+foo\n\
+bar [[x|y]]\n\
+\n\
+> quote\n\
+> - [ ] item\n\
+\n\
+---\n\
+{&> .c}\n\
+{|\n\
+|| a || b\n\
+|}\n\
+{{x\n\
+|| y\n\
+}}\n\
+\n\
+```\n\
+code\n\
+```\n\
+<% c\n\
+\n\
+%>baz\n\
+{{x\n\
+\n\
+qux";
+
+    /// 将 `chunks` 依次交给流式解析器，返回其产出的内容以及将这些内容拼接起来所得的事件。
+    fn parse_in_chunks<'a>(
+        expected_input: &[u8],
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) -> (Vec<Segment>, Vec<Event>) {
+        let mut parser = StreamingParser::new();
+        let mut segments = vec![];
+        for chunk in chunks {
+            segments.extend(parser.push(chunk).unwrap());
+        }
+        segments.extend(parser.finish().unwrap());
+
+        let mut input: Vec<u8> = vec![];
+        let mut events = vec![];
+        for segment in &segments {
+            assert_eq!(segment.offset, input.len());
+            input.extend(&segment.input);
+            events.extend(segment.events.iter().cloned().map(|mut ev| {
+                shift_event(&mut ev, segment.offset as isize, 0);
+                ev
+            }));
+        }
+        assert_eq!(input, expected_input);
+
+        (segments, events)
+    }
+
+    #[test]
+    fn parse_in_chunks_matches_full_parse() {
+        let expected = crate::parse(INPUT.as_bytes())
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        let input = INPUT.as_bytes();
+
+        for size in 1..=8 {
+            let (_, actual) = parse_in_chunks(input, input.chunks(size));
+            assert_eq!(actual, expected, "size: {size}");
+        }
+        for at in 0..=input.len() {
+            let (_, actual) = parse_in_chunks(input, [&input[..at], &input[at..]]);
+            assert_eq!(actual, expected, "at: {at}");
+        }
+    }
+
+    #[test]
+    fn push_yields_determined_blocks() {
+        let mut parser = StreamingParser::new();
+        assert_eq!(parser.push(b"a\n").unwrap(), None);
+        assert_eq!(parser.push(b"\n").unwrap(), None);
+        let segment = parser.push(b"b\n").unwrap().unwrap();
+        assert_eq!(segment.input, b"a\n\n");
+        assert_eq!(segment.offset, 0);
+        assert_eq!(segment.events.len(), 3);
+
+        let segment = parser.finish().unwrap().unwrap();
+        assert_eq!(segment.input, b"b\n");
+        assert_eq!(segment.offset, 3);
+        assert_eq!(segment.events[1], Event::Text(0..1));
+    }
+
+    #[test]
+    fn push_does_not_split_comments() {
+        let mut parser = StreamingParser::new();
+        assert_eq!(parser.push(b"a\n<% c\n\n").unwrap(), None);
+        let segment = parser.push(b"%>b\n\nc\n").unwrap().unwrap();
+        assert_eq!(segment.input, b"a\n<% c\n\n%>b\n\n");
+        assert_eq!(segment.events.len(), 6);
+    }
+
+    #[test]
+    fn parse_overlapping_top_level_blocks_in_chunks_matches_full_parse() {
+        // 未闭合的调用使第一个列表的范围延伸到了第二个列表开头所在的行。
+        let input = b"* {{x\n#\n";
+        let expected = crate::parse(input)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+
+        let (_, actual) = parse_in_chunks(input, [&input[..]]);
+        assert_eq!(actual, expected);
+        for size in 1..=3 {
+            let (_, actual) = parse_in_chunks(input, input.chunks(size));
+            assert_eq!(actual, expected, "size: {size}");
+        }
+    }
+
+    #[test]
+    fn parse_long_block_in_chunks_matches_full_parse() {
+        let input = format!("a\n\n```\n{}```\n\nb\n\nc", "code\n".repeat(5000));
+        let input = input.as_bytes();
+        let expected = crate::parse(input)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+
+        let (segments, actual) = parse_in_chunks(input, input.chunks(5));
+        assert_eq!(actual, expected);
+        assert_eq!(segments[0].input, b"a\n\n");
+    }
+
+    #[test]
+    fn parse_random_input_in_chunks_matches_full_parse() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..3000 {
+            let input = rng.input(24);
+            let expected = crate::parse(input.as_bytes())
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+
+            let input = input.as_bytes();
+            let mut chunks = vec![];
+            let mut rest = input;
+            while !rest.is_empty() {
+                let (chunk, tail) = rest.split_at((1 + rng.next(6)).min(rest.len()));
+                chunks.push(chunk);
+                rest = tail;
+            }
+            let (_, actual) = parse_in_chunks(input, chunks.clone());
+            assert_eq!(
+                actual,
+                expected,
+                "chunks: {:?}",
+                chunks
+                    .iter()
+                    .map(|chunk| String::from_utf8_lossy(chunk))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
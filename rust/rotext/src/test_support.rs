@@ -0,0 +1,42 @@
+//! 测试用的辅助工具。
+
+/// 简单的伪随机数生成器（xorshift64）。
+pub(crate) struct Rng(pub(crate) u64);
+
+/// 拼接成随机输入的片段，涵盖了各种块级与行内的标记。
+pub(crate) const PIECES: &[&str] = &[
+    "\n", "\n", "\r\n", "\r", " ", "a", "中", "# ", "#", "> ", ">", "* ", "; ", ": ", "= ", "==",
+    "---", "```", "{|", "|}", "||", "!!", "{{", "}}", "{{{", "}}}", "[[", "]]", "<%", "%>", "<`",
+    "`>", "{&>", "{&<", ".x}", "[*", "*]", "$", "[= ",
+];
+
+/// 只含块级标记的片段。与 [PIECES] 相比，更容易拼出多个相邻的块级元素。
+#[cfg(feature = "block-id")]
+pub(crate) const BLOCK_PIECES: &[&str] = &[
+    "\n", "\n", "a", "> ", ">", "* ", "# ", "; ", ": ", "---", "{|", "|}", "||", "{{", "}}", "```",
+    "<%", "%>", "{&> .x}",
+];
+
+impl Rng {
+    /// 返回 `0..bound` 中的一个数。
+    pub(crate) fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+
+    /// 返回由不到 `max_pieces` 个 [PIECES] 中的片段拼接而成的输入。
+    pub(crate) fn input(&mut self, max_pieces: usize) -> String {
+        self.input_from(PIECES, max_pieces)
+    }
+
+    /// 返回由不到 `max_pieces` 个 `pieces` 中的片段拼接而成的输入。
+    pub(crate) fn input_from(&mut self, pieces: &[&str], max_pieces: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..self.next(max_pieces) {
+            input.push_str(pieces[self.next(pieces.len())]);
+        }
+        input
+    }
+}
@@ -1,3 +1,5 @@
+use rotext_core::{Event, attached_info, events::AttachedInfo};
+
 pub struct SequenceGenerator {
     next: usize,
 }
@@ -12,12 +14,102 @@ impl SequenceGenerator {
     }
 }
 
-#[cfg(feature = "block-id")]
 pub fn write_usize(buf: &mut Vec<u8>, n: usize) {
     let mut buffer = itoa::Buffer::new();
     buf.extend(buffer.format(n).as_bytes());
 }
 
+/// 若 `ev` 是块级或行内的附加信息，返回其承载的附加信息。
+pub fn as_attached_info(ev: &Event) -> Option<&AttachedInfo> {
+    match ev {
+        Event::BlockAttachedInfo(info) | Event::InlineAttachedInfo(info) => Some(info),
+        _ => None,
+    }
+}
+
+/// 将各附加信息中的类名以空格连接起来。没有任何类名时返回 `None`。
+pub fn join_attached_classes<'b>(
+    input: &[u8],
+    infos: impl IntoIterator<Item = &'b AttachedInfo>,
+) -> Option<Vec<u8>> {
+    let mut classes: Vec<u8> = Vec::new();
+    for info in infos {
+        for item in info.items(input) {
+            if let attached_info::Item::Class(class) = item {
+                if !classes.is_empty() {
+                    classes.push(b' ');
+                }
+                classes.extend(&input[class]);
+            }
+        }
+    }
+
+    if classes.is_empty() {
+        None
+    } else {
+        Some(classes)
+    }
+}
+
+/// 收集各附加信息中可以作为 HTML 属性输出的键值对，见 [is_renderable_attached_key]。同一
+/// 键出现多次时以最后一次为准。
+pub fn collect_attached_attributes<'b>(
+    input: &'b [u8],
+    infos: impl IntoIterator<Item = &'b AttachedInfo>,
+) -> Vec<(&'b [u8], &'b [u8])> {
+    let mut attrs: Vec<(&[u8], &[u8])> = Vec::new();
+    for info in infos {
+        for item in info.items(input) {
+            if let attached_info::Item::KeyValue { key, value } = item {
+                let (key, value) = (&input[key], &input[value]);
+                if !is_renderable_attached_key(key) {
+                    continue;
+                }
+                match attrs.iter_mut().find(|(k, _)| *k == key) {
+                    Some(attr) => attr.1 = value,
+                    None => attrs.push((key, value)),
+                }
+            }
+        }
+    }
+    attrs
+}
+
+/// 附加信息中的键是否可以作为 HTML 属性输出。允许的有 `title`、`lang`，以及由小写字母、
+/// 数字、`-` 与 `_` 组成的 `data-*`（`data-block-id` 除外）。其他的键（如 `colspan`）只
+/// 在特定的元素上有意义，由对应元素的渲染过程自行处理。
+pub fn is_renderable_attached_key(key: &[u8]) -> bool {
+    match key {
+        b"title" | b"lang" => true,
+        b"data-block-id" => false,
+        _ => key.strip_prefix(b"data-").is_some_and(|name| {
+            !name.is_empty()
+                && name
+                    .iter()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || b"-_".contains(c))
+        }),
+    }
+}
+
+/// 查找各附加信息中键为 `key` 的值。同一键出现多次时以最后一次为准。
+pub fn find_attached_value<'b>(
+    input: &'b [u8],
+    infos: impl IntoIterator<Item = &'b AttachedInfo>,
+    key: &[u8],
+) -> Option<&'b [u8]> {
+    let mut found = None;
+    for info in infos {
+        for item in info.items(input) {
+            if let attached_info::Item::KeyValue { key: k, value } = item {
+                if &input[k] == key {
+                    found = Some(&input[value]);
+                }
+            }
+        }
+    }
+    found
+}
+
 pub fn render_escaped_html_text(buf: &mut Vec<u8>, input: &[u8]) {
     for char in input {
         match *char {
@@ -29,6 +121,17 @@ pub fn render_escaped_html_text(buf: &mut Vec<u8>, input: &[u8]) {
     }
 }
 
+/// 与 [render_escaped_html_text] 不同，换行会被原样保留，用于 `pre` 之中的文本。
+pub fn render_escaped_preformatted_text(buf: &mut Vec<u8>, input: &[u8]) {
+    for char in input {
+        match *char {
+            b'<' => buf.extend(b"&lt;"),
+            b'&' => buf.extend(b"&amp;"),
+            char => buf.push(char),
+        }
+    }
+}
+
 pub fn render_escaped_double_quoted_attribute_value(buf: &mut Vec<u8>, input: &[u8]) {
     for char in input {
         match *char {
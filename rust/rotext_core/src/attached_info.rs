@@ -0,0 +1,378 @@
+//! 附加信息序列的解析。
+//!
+//! 附加信息序列由空白分隔的附加信息项组成，每一项为以下两者之一：
+//! - `.<name>`：类。
+//! - `<key>="<value>"`：键值对。值中不能包含 `"` 与换行。
+//!
+//! 附加信息序列不能跨行。
+
+use core::{num::NonZeroUsize, ops::Range};
+
+use crate::{
+    common::{is_valid_character_in_attached_info_name, m},
+    events::{OrderedList, OrderedListNumbering, TableCell},
+    internal_utils::string::{count_continuous_whitespaces, is_whitespace},
+    types::SourceSpan,
+};
+
+/// 附加信息序列中的一项。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// `.<name>`，与 HTML 中的 “类” 对应。范围不包含开头的 `.`。
+    Class(Range<usize>),
+    /// `<key>="<value>"`。`value` 的范围不包含两侧的 `"`。
+    KeyValue {
+        key: Range<usize>,
+        value: Range<usize>,
+    },
+}
+
+/// 从 `since` 处开始尝试解析附加信息序列，直到遇到作为闭合部分的 `closing`。
+///
+/// 成功时返回 `closing` 所在的位置。序列中至少要有一项。
+pub(crate) fn parse_sequence(input: &[u8], since: usize, closing: u8) -> Option<usize> {
+    parse_sequence_with(input, since, closing, |_| {})
+}
+
+/// 同 [parse_sequence]，并依次对解析出的各项调用 `f`。即使序列最终无效，此前解析出的项
+/// 也已经传给了 `f`。
+fn parse_sequence_with(
+    input: &[u8],
+    since: usize,
+    closing: u8,
+    mut f: impl FnMut(Item),
+) -> Option<usize> {
+    let mut items = Items {
+        input,
+        cursor: since,
+        closing,
+    };
+
+    let mut count = 0;
+    loop {
+        match items.parse_item() {
+            Ok(Some(item)) => {
+                f(item);
+                count += 1;
+            }
+            Ok(None) => break,
+            Err(()) => return None,
+        }
+    }
+    if count == 0 || input.get(items.cursor) != Some(&closing) {
+        return None;
+    }
+
+    Some(items.cursor)
+}
+
+/// 从 `since` 处开始（跳过空白后）读取连续的 `{&<…}` 槽位附加信息，以其中的每个键值对调
+/// 用 `f` 更新 `data`，并对每个有效的附加信息的内容所在的范围调用 `on_info`。
+///
+/// 每个附加信息只解析一遍：`f` 作用于 `data` 的副本，附加信息有效时才会替换 `data`。
+fn for_each_slot_key_value<T: Clone>(
+    input: &[u8],
+    mut since: usize,
+    data: &mut T,
+    f: impl Fn(&mut T, &[u8], &[u8]),
+    mut on_info: impl FnMut(Range<usize>),
+) {
+    loop {
+        since += count_continuous_whitespaces(input, since);
+        if !input[since.min(input.len())..].starts_with(b"{&<") {
+            break;
+        }
+        let start = since + "{&<".len();
+        let mut updated = data.clone();
+        let Some(end) = parse_sequence_with(input, start, m!('}'), |item| {
+            if let Item::KeyValue { key, value } = item {
+                f(&mut updated, &input[key], &input[value]);
+            }
+        }) else {
+            break;
+        };
+        *data = updated;
+        on_info(start..end);
+
+        since = end + "}".len();
+    }
+}
+
+/// 从 `since` 处开始（跳过空白后）读取连续的 `{&<…}` 槽位附加信息，从中取得表格单元格的
+/// 数据。同一键出现多次时以最后一次为准。各附加信息的范围见 [for_each_slot_key_value]。
+pub(crate) fn parse_table_cell(
+    input: &[u8],
+    since: usize,
+    span: SourceSpan,
+    on_info: impl FnMut(Range<usize>),
+) -> TableCell {
+    let mut cell = TableCell::new(span);
+
+    for_each_slot_key_value(
+        input,
+        since,
+        &mut cell,
+        |cell, key, value| match key {
+            b"colspan" => cell.colspan = parse_span(value, TableCell::MAX_COLSPAN),
+            b"rowspan" => cell.rowspan = parse_span(value, TableCell::MAX_ROWSPAN),
+            _ => {}
+        },
+        on_info,
+    );
+
+    cell
+}
+
+/// 从 `since` 处开始（跳过空白后）读取连续的 `{&<…}` 槽位附加信息，从中取得有序列表的
+/// 数据，填入 `list`。同一键出现多次时以最后一次为准。各附加信息的范围见
+/// [for_each_slot_key_value]。
+pub(crate) fn fill_ordered_list(
+    input: &[u8],
+    since: usize,
+    list: &mut OrderedList,
+    on_info: impl FnMut(Range<usize>),
+) {
+    for_each_slot_key_value(
+        input,
+        since,
+        list,
+        |list, key, value| match key {
+            b"start" => list.start = parse_non_negative_integer(value),
+            b"type" => {
+                list.numbering = match value {
+                    b"1" => Some(OrderedListNumbering::Decimal),
+                    b"a" => Some(OrderedListNumbering::LowerAlpha),
+                    b"A" => Some(OrderedListNumbering::UpperAlpha),
+                    b"i" => Some(OrderedListNumbering::LowerRoman),
+                    b"I" => Some(OrderedListNumbering::UpperRoman),
+                    _ => None,
+                }
+            }
+            _ => {}
+        },
+        on_info,
+    );
+}
+
+/// 只接受由 ASCII 数字组成、不超过 `max` 的正整数。
+fn parse_span(value: &[u8], max: usize) -> Option<NonZeroUsize> {
+    parse_non_negative_integer(value)
+        .filter(|n| *n <= max)
+        .and_then(NonZeroUsize::new)
+}
+
+/// 只接受由 ASCII 数字组成的非负整数。
+fn parse_non_negative_integer(value: &[u8]) -> Option<usize> {
+    if value.is_empty() || !value.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    core::str::from_utf8(value).ok()?.parse().ok()
+}
+
+/// 迭代一段（已经确认有效的）附加信息序列中的各项。
+pub struct Items<'a> {
+    input: &'a [u8],
+    cursor: usize,
+    closing: u8,
+}
+
+impl<'a> Items<'a> {
+    /// `content` 是 [parse_sequence] 确认有效的序列所在的范围。
+    pub fn new(input: &'a [u8], content: Range<usize>) -> Self {
+        Self {
+            input: &input[..content.end],
+            cursor: content.start,
+            // 截断后的输入中不会再出现闭合部分，因此这里是什么都无所谓。
+            closing: m!('}'),
+        }
+    }
+
+    /// 跳过开头的空白并解析一项。遇到闭合部分或输入结尾时返回 `Ok(None)`。
+    fn parse_item(&mut self) -> Result<Option<Item>, ()> {
+        while matches!(self.input.get(self.cursor), Some(c) if is_whitespace!(c)) {
+            self.cursor += 1;
+        }
+
+        let Some(&char) = self.input.get(self.cursor) else {
+            return Ok(None);
+        };
+        if char == self.closing {
+            return Ok(None);
+        }
+
+        let item = if char == m!('.') {
+            self.cursor += 1;
+            Item::Class(self.parse_name()?)
+        } else {
+            let key = self.parse_name()?;
+            if self.input.get(self.cursor) != Some(&m!('='))
+                || self.input.get(self.cursor + 1) != Some(&m!('"'))
+            {
+                return Err(());
+            }
+            self.cursor += r#"=""#.len();
+            let value_start = self.cursor;
+            loop {
+                match self.input.get(self.cursor) {
+                    Some(m!('"')) => break,
+                    None | Some(b'\r' | b'\n') => return Err(()),
+                    Some(_) => self.cursor += 1,
+                }
+            }
+            let value = value_start..self.cursor;
+            self.cursor += r#"""#.len();
+            Item::KeyValue { key, value }
+        };
+
+        // 各项之间需要以空白分隔。
+        match self.input.get(self.cursor) {
+            None => {}
+            Some(c) if is_whitespace!(c) || *c == self.closing => {}
+            Some(_) => return Err(()),
+        }
+
+        Ok(Some(item))
+    }
+
+    fn parse_name(&mut self) -> Result<Range<usize>, ()> {
+        let start = self.cursor;
+        while matches!(
+            self.input.get(self.cursor),
+            Some(&c) if is_valid_character_in_attached_info_name(c)
+        ) {
+            self.cursor += 1;
+        }
+
+        if self.cursor == start {
+            Err(())
+        } else {
+            Ok(start..self.cursor)
+        }
+    }
+}
+
+impl Iterator for Items<'_> {
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parse_item().ok().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroUsize;
+
+    use super::*;
+
+    fn cell(colspan: Option<usize>, rowspan: Option<usize>) -> TableCell {
+        TableCell {
+            colspan: colspan.and_then(NonZeroUsize::new),
+            rowspan: rowspan.and_then(NonZeroUsize::new),
+            span: SourceSpan::new_invalid(),
+        }
+    }
+
+    #[test]
+    fn parse_table_cell_works() {
+        for (input, expected) in [
+            (&b" a"[..], cell(None, None)),
+            (&b" {&< .foo} a"[..], cell(None, None)),
+            (&b" {&< colspan=\"2\"} a"[..], cell(Some(2), None)),
+            (
+                &b"{&< rowspan=\"3\" colspan=\"2\"}"[..],
+                cell(Some(2), Some(3)),
+            ),
+            (&b" {&< .foo}{&< rowspan=\"3\"} a"[..], cell(None, Some(3))),
+            (
+                &b" {&< colspan=\"2\"} {&< colspan=\"4\"}"[..],
+                cell(Some(4), None),
+            ),
+            (&b" {&> colspan=\"2\"} a"[..], cell(None, None)),
+            (&b" {&< colspan=\"2\" a"[..], cell(None, None)),
+            (&b" a {&< colspan=\"2\"}"[..], cell(None, None)),
+        ] {
+            assert_eq!(
+                parse_table_cell(input, 0, SourceSpan::new_invalid(), |_| {}),
+                expected
+            );
+        }
+    }
+
+    fn ordered_list(start: Option<usize>, numbering: Option<OrderedListNumbering>) -> OrderedList {
+        OrderedList {
+            id: crate::types::BlockId::new_invalid(),
+            start,
+            numbering,
+            span: SourceSpan::new_invalid(),
+            position: crate::types::Position::new_invalid(),
+        }
+    }
+
+    #[test]
+    fn fill_ordered_list_works() {
+        use OrderedListNumbering as N;
+
+        for (input, expected) in [
+            (&b" a"[..], ordered_list(None, None)),
+            (&b" {&< start=\"3\"} a"[..], ordered_list(Some(3), None)),
+            (
+                &b"{&< start=\"0\" type=\"i\"}"[..],
+                ordered_list(Some(0), Some(N::LowerRoman)),
+            ),
+            (
+                &b" {&< type=\"A\"}{&< type=\"I\"}"[..],
+                ordered_list(None, Some(N::UpperRoman)),
+            ),
+            (
+                &b" {&< start=\"-1\" type=\"x\"}"[..],
+                ordered_list(None, None),
+            ),
+            (&b" {&> start=\"3\"} a"[..], ordered_list(None, None)),
+        ] {
+            let mut list = ordered_list(None, None);
+            fill_ordered_list(input, 0, &mut list, |_| {});
+            assert_eq!(list, expected);
+        }
+    }
+
+    #[test]
+    fn parse_table_cell_rejects_non_positive_integers() {
+        for value in [
+            "0",
+            "-1",
+            "+1",
+            "1.5",
+            "",
+            " 2",
+            "a",
+            "99999999999999999999999",
+        ] {
+            let input = format!("{{&< colspan=\"{value}\"}}");
+            assert_eq!(
+                parse_table_cell(input.as_bytes(), 0, SourceSpan::new_invalid(), |_| {}),
+                cell(None, None)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_table_cell_accepts_spans_within_html_limits_only() {
+        for (input, expected) in [
+            (
+                "{&< colspan=\"1000\" rowspan=\"65534\"}",
+                cell(Some(1000), Some(65534)),
+            ),
+            ("{&< colspan=\"1001\" rowspan=\"65535\"}", cell(None, None)),
+            (
+                "{&< colspan=\"100000000000\" rowspan=\"2\"}",
+                cell(None, Some(2)),
+            ),
+        ] {
+            assert_eq!(
+                parse_table_cell(input.as_bytes(), 0, SourceSpan::new_invalid(), |_| {}),
+                expected
+            );
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(debug_assertions)]
 use crate::events::is_event_of;
 use crate::{
+    diagnostics::DiagnosticSink,
     events::{Event, ev},
     inline::{self},
     internal_utils::peekable::Peekable,
@@ -32,6 +33,7 @@ pub struct BlockEventStreamInlineSegmentMapper<
 > {
     input: &'a [u8],
     state: State<'a, TBlockParser, TInlineStack>,
+    diagnostic_sink: Option<DiagnosticSink<'a>>,
 }
 
 impl<
@@ -44,9 +46,17 @@ impl<
         Self {
             input,
             state: State::Normal(Some(block_parser)),
+            diagnostic_sink: None,
         }
     }
 
+    /// 将行内阶段解析时发现的问题报告给 `sink`，见 [crate::diagnostics]。块级阶段的问题
+    /// 需要另外通过 [crate::BlockParser::with_diagnostic_sink] 设置。
+    pub fn with_diagnostic_sink(mut self, sink: DiagnosticSink<'a>) -> Self {
+        self.diagnostic_sink = Some(sink);
+        self
+    }
+
     /// 返回的事件属于 `Blend` 分组。
     #[inline(always)]
     fn next(&mut self) -> Option<crate::Result<Event>> {
@@ -61,7 +71,7 @@ impl<
                     if next.is_block_event_that_opens_inline_phase() {
                         let block_parser = block_parser.take().unwrap();
                         let segment_stream = WhileInlineSegment::new(block_parser);
-                        let inline_parser = inline::Parser::new(self.input);
+                        let inline_parser = inline::Parser::new(self.input, self.diagnostic_sink);
                         self.state = State::ParsingInline {
                             inline_parser,
                             segment_stream: Some(Peekable::new(segment_stream)),
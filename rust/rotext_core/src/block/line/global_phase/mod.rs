@@ -8,6 +8,7 @@ use crate::{
         utils::move_cursor_over_line_break,
     },
     common::m,
+    diagnostics::DiagnosticCode,
     events::VerbatimEscaping,
     internal_utils::string::count_continuous_character,
     types::Tym,
@@ -34,8 +35,17 @@ pub fn parse<TCtx: CursorContext>(input: &[u8], ctx: &mut TCtx, first_char: u8)
 
     match input.get(ctx.cursor() + 1)? {
         m!('`') => {
+            let opening_start = ctx.cursor();
             ctx.move_cursor_forward("<`".len());
             let verbatim_escaping = parse_verbatim_escaping(input, ctx);
+            if verbatim_escaping.is_closed_forcedly {
+                let opening_length = "<`".len()
+                    + count_continuous_character(input, m!('`'), opening_start + "<`".len());
+                ctx.report_diagnostic(
+                    DiagnosticCode::UnclosedVerbatimEscaping,
+                    opening_start..(opening_start + opening_length),
+                );
+            }
             Some(Output::VerbatimEscaping(verbatim_escaping))
         }
         m!('%') => {
@@ -53,12 +63,11 @@ fn parse_verbatim_escaping<TCtx: CursorContext>(input: &[u8], ctx: &mut TCtx) ->
     let backticks = "`".len() + count;
 
     let mut start = ctx.cursor();
-    let has_leading_space = if let Some(&char) = input.get(start) {
+    // 开头的换行需要留给下面的循环处理，以便正确计算行号。
+    let has_leading_space = input.get(start) == Some(&b' ');
+    if has_leading_space {
         ctx.move_cursor_forward(1);
-        char == b' '
-    } else {
-        false
-    };
+    }
 
     let mut continuous_backticks = 0;
     while let Some(char) = input.get(ctx.cursor()).copied() {
@@ -110,6 +119,7 @@ fn parse_verbatim_escaping<TCtx: CursorContext>(input: &[u8], ctx: &mut TCtx) ->
 }
 
 fn parse_comment<TCtx: CursorContext>(input: &[u8], ctx: &mut TCtx) {
+    let opening = (ctx.cursor() - "<%".len())..ctx.cursor();
     let mut depth = 1;
 
     while depth > 0 {
@@ -139,6 +149,10 @@ fn parse_comment<TCtx: CursorContext>(input: &[u8], ctx: &mut TCtx) {
             _ => ctx.move_cursor_forward(1),
         }
     }
+
+    if depth > 0 {
+        ctx.report_diagnostic(DiagnosticCode::UnclosedComment, opening);
+    }
 }
 
 pub fn process_verbatim_escaping<TCtx: YieldContext>(
@@ -358,4 +358,44 @@ mod for_fn_parse_verbatim_escaping {
             },
         );
     }
+
+    #[test]
+    fn it_works_with_line_break_at_start() {
+        test(
+            b"\rfoo`>",
+            VerbatimEscaping {
+                content: 0..4,
+                is_closed_forcedly: false,
+                line_after: LineNumber::new_universal(2),
+            },
+            MockCursorContext {
+                cursor: 6,
+                current_line: LineNumber::new_universal(2),
+            },
+        );
+        test(
+            b"\r\nfoo`>",
+            VerbatimEscaping {
+                content: 0..5,
+                is_closed_forcedly: false,
+                line_after: LineNumber::new_universal(2),
+            },
+            MockCursorContext {
+                cursor: 7,
+                current_line: LineNumber::new_universal(2),
+            },
+        );
+        test(
+            b"\n",
+            VerbatimEscaping {
+                content: 0..1,
+                is_closed_forcedly: true,
+                line_after: LineNumber::new_universal(2),
+            },
+            MockCursorContext {
+                cursor: 1,
+                current_line: LineNumber::new_universal(2),
+            },
+        );
+    }
 }
@@ -19,7 +19,10 @@ use super::{CommonEnd, ParseCommonEndOutput, global_phase, parse_common_end};
 pub struct EndCondition {
     pub on_atx_closing: Option<AtxClosing>,
     pub on_table_related: Option<TableRelated>,
-    pub on_call_related: bool,
+    pub on_call_related: Option<CallRelated>,
+    /// 为真时，`||` 不会被视为 [End::DoublePipes]，而是普通的文本。用于参数填充的
+    /// fallback 之中（且其中没有更内层的表格或调用）。
+    pub is_double_pipes_literal: bool,
     pub on_description_definition_opening: bool,
     pub matching: Option<Matching>,
 }
@@ -38,6 +41,11 @@ pub struct TableRelated {
     pub is_caption_applicable: bool,
 }
 #[derive(Debug, Clone)]
+pub struct CallRelated {
+    /// 最内层的调用是否其实是参数填充。若是，闭合部分为 `}}}` 而非 `}}`。
+    pub is_parameter_filling: bool,
+}
+#[derive(Debug, Clone)]
 pub enum Matching {
     CallName,
     CallArgumentIndicator,
@@ -494,14 +502,16 @@ fn parse_braced_element_related_end<TCtx: CursorContext>(
         }
     }
 
-    if end_condition.on_call_related {
-        let end = call::parse_end(input, ctx, first_char).map(End::CallRelated);
+    if let Some(end_condition) = &end_condition.on_call_related {
+        let end = call::parse_end(input, ctx, first_char, end_condition.is_parameter_filling)
+            .map(End::CallRelated);
         if end.is_some() {
             return end;
         }
     }
 
-    if (end_condition.on_table_related.is_some() || end_condition.on_call_related)
+    if (end_condition.on_table_related.is_some() || end_condition.on_call_related.is_some())
+        && !end_condition.is_double_pipes_literal
         && first_char == m!('|')
         && input.get(ctx.cursor() + 1) == Some(&m!('|'))
     {
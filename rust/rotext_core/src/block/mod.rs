@@ -15,11 +15,12 @@ pub use stack_wrapper::StackEntry;
 use crate::{
     Event,
     common::m,
-    events::{ThematicBreak, ev},
+    diagnostics::{DiagnosticCode, DiagnosticReporter, DiagnosticSink},
+    events::{CallArgument, TableCell, ThematicBreak, ev},
     internal_utils::string::{
         count_continuous_character, count_continuous_whitespaces, is_whitespace,
     },
-    types::{Stack, TYM_UNIT, Tym, cast_tym},
+    types::{BlockId, LineNumber, Position, SourceSpan, Stack, TYM_UNIT, Tym, cast_tym},
 };
 
 use state::{
@@ -40,7 +41,7 @@ use types::{CursorContext, YieldContext};
 pub struct Parser<'a, TStack: Stack<StackEntry>> {
     input: &'a [u8],
     state: State,
-    inner: ParserInner<TStack>,
+    inner: ParserInner<'a, TStack>,
     /// 其实只有在 `state` 为 [Expecting::ItemLikeOpening] 时有效，但将
     /// [ItemLikesState] 作为 [Expecting::ItemLikeOpening] 的字段并不是很可行。之后有
     /// 函数既需要传入 `&mut state`，又需要知道 `expecting` 的值是什么，而现在的
@@ -54,10 +55,36 @@ pub struct Parser<'a, TStack: Stack<StackEntry>> {
 
 impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
     pub fn new(input: &'a [u8]) -> Self {
+        Self::new_ex(input, false)
+    }
+
+    /// 创建一个将整份输入视为单个段落的解析器，用于只含行内内容的场合（如行内嵌入包含的模
+    /// 板）。除逐字转义与注释外，不会解析其他块级结构，空行也不会分隔段落。
+    pub fn new_inline_only(input: &'a [u8]) -> Self {
+        Self::new_ex(input, true)
+    }
+
+    /// 将解析时发现的问题报告给 `sink`，见 [crate::diagnostics]。
+    pub fn with_diagnostic_sink(mut self, sink: DiagnosticSink<'a>) -> Self {
+        self.inner
+            .set_diagnostic_reporter(DiagnosticReporter::new(self.input, Some(sink)));
+        self
+    }
+
+    /// 从 `cursor` 处而非输入的开头开始解析，用于增量解析等只需要解析输入的一部分的场合。
+    ///
+    /// `cursor` 须位于某行的开头，且此前的内容须恰好由完整的顶层块级元素组成，`line` 为该行
+    /// 的行号。产出的块级元素的 ID 从 `last_block_id` 之后开始分配。
+    pub fn starting_at(mut self, cursor: usize, line: LineNumber, last_block_id: BlockId) -> Self {
+        self.inner.set_start(cursor, line, last_block_id);
+        self
+    }
+
+    fn new_ex(input: &'a [u8], is_inline_only: bool) -> Self {
         Self {
             input,
             state: Expecting::ItemLikeOpening.into(),
-            inner: ParserInner::new(),
+            inner: ParserInner::new(input, is_inline_only, DiagnosticReporter::new(input, None)),
             item_likes_state: ItemLikesState::ProcessingNew,
 
             #[cfg(debug_assertions)]
@@ -84,6 +111,12 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
         };
 
         loop {
+            if self.inner.is_inline_only() && !matches!(expecting, Expecting::LeafContent) {
+                expecting = Expecting::LeafContent;
+                self.state = expecting.into();
+                continue;
+            }
+
             match expecting {
                 Expecting::ItemLikeOpening => {
                     if !self
@@ -128,6 +161,14 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
                         )
                         .map(|tym| cast_tym!(tym));
                     }
+                    if self.inner.is_inline_only() {
+                        break terminal::paragraph::enter_if_not_blank(
+                            self.input,
+                            &mut self.state,
+                            &mut self.inner,
+                            0,
+                        );
+                    }
                     break terminal::parse_opening_and_process(
                         self.input,
                         &mut self.state,
@@ -141,6 +182,7 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
     }
 
     fn exit(
+        input: &[u8],
         inner: &mut ParserInner<TStack>,
         item_likes_state: &mut ItemLikesState,
         exiting: &mut Exiting,
@@ -192,12 +234,16 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
             }
             ExitingUntil::TopIsAwareOfDoublePipes => {
                 if inner.stack.top_is_table() {
+                    let span =
+                        SourceSpan::new_universal(inner.cursor() - "||".len(), inner.cursor());
                     exiting.and_then = Some(ExitingAndThen::YieldAndExpectBracedOpening(ev!(
                         Block,
-                        IndicateTableDataCell
+                        IndicateTableDataCell(TableCell::new(span))
                     )));
                     (true, false)
                 } else if inner.stack.top_is_call() {
+                    // 参数填充只有一个 fallback，其中的 `||` 只是普通的文本，不会来到这里。
+                    debug_assert!(!inner.stack.innermost_call_is_parameter_filling());
                     exiting.and_then =
                         Some(ExitingAndThen::PushLeafCallArgumentBeginningAndExpectBracedOpening);
                     (true, false)
@@ -217,8 +263,37 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
                 StackEntry::ItemLikeContainer(stack_entry) => {
                     item_like::exit_container(inner, stack_entry)?
                 }
-                StackEntry::Table(stack_entry) => braced::table::exit(inner, stack_entry)?,
-                StackEntry::Call(stack_entry) => braced::call::exit(inner, stack_entry)?,
+                StackEntry::Table(stack_entry) => {
+                    if is_done && matches!(exiting.until, ExitingUntil::TopIsTable { .. }) {
+                        // 闭合部分刚被消耗。
+                        inner.extend_span_end(inner.cursor());
+                    } else {
+                        let start = stack_entry.opening_start;
+                        inner.report_diagnostic(
+                            DiagnosticCode::UnclosedTable,
+                            start..(start + "{|".len()),
+                        );
+                    }
+                    braced::table::exit(inner, stack_entry)?
+                }
+                StackEntry::Call(stack_entry) => {
+                    if is_done && matches!(exiting.until, ExitingUntil::TopIsCall { .. }) {
+                        // 闭合部分刚被消耗。
+                        inner.extend_span_end(inner.cursor());
+                    } else {
+                        let start = stack_entry.opening_start;
+                        let opening_len = if stack_entry.is_parameter_filling {
+                            "{{{".len()
+                        } else {
+                            "{{".len()
+                        };
+                        inner.report_diagnostic(
+                            DiagnosticCode::UnclosedBlockCall,
+                            start..(start + opening_len),
+                        );
+                    }
+                    braced::call::exit(inner, stack_entry)?
+                }
             }
         } else {
             TYM_UNIT.into()
@@ -232,7 +307,11 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
                     item_like,
                 } => {
                     let tym_a = if let Some(container) = container {
-                        let ev = container.make_enter_event();
+                        let ev = item_like::fill_ordered_list_data(
+                            input,
+                            inner,
+                            container.make_enter_event(),
+                        );
                         inner.stack.push_item_like_container(container)?;
                         inner.r#yield(ev)
                     } else {
@@ -249,10 +328,17 @@ impl<'a, TStack: Stack<StackEntry>> Parser<'a, TStack> {
                 ExitingAndThen::ExpectBracedOpening => {
                     (TYM_UNIT.into(), Some(Expecting::BracedOpening.into()))
                 }
-                ExitingAndThen::YieldAndExpectBracedOpening(ev) => (
-                    inner.r#yield(ev).into(),
-                    Some(Expecting::BracedOpening.into()),
-                ),
+                ExitingAndThen::ExpectNewItemLikeOpening => {
+                    *item_likes_state = ItemLikesState::ProcessingNew;
+                    (TYM_UNIT.into(), Some(Expecting::ItemLikeOpening.into()))
+                }
+                ExitingAndThen::YieldAndExpectBracedOpening(ev) => {
+                    let ev = braced::table::fill_cell_data(input, inner, ev);
+                    (
+                        inner.r#yield(ev).into(),
+                        Some(Expecting::BracedOpening.into()),
+                    )
+                }
                 ExitingAndThen::PushLeafCallArgumentBeginningAndExpectBracedOpening => {
                     let leaf = LeafCallArgumentBeginning {
                         shallow_snapshot: inner.take_shallow_snapshot(),
@@ -289,18 +375,21 @@ impl<TStack: Stack<StackEntry>> Iterator for Parser<'_, TStack> {
             }
 
             let result: crate::Result<Tym<5>> = match &mut self.state {
-                State::Exiting(exiting) => {
-                    match Self::exit(&mut self.inner, &mut self.item_likes_state, exiting) {
-                        Ok((tym, state)) => {
-                            if let Some(state) = state {
-                                self.state = state;
-                            }
-                            Ok(tym)
+                State::Exiting(exiting) => match Self::exit(
+                    self.input,
+                    &mut self.inner,
+                    &mut self.item_likes_state,
+                    exiting,
+                ) {
+                    Ok((tym, state)) => {
+                        if let Some(state) = state {
+                            self.state = state;
                         }
-                        Err(err) => Err(err),
+                        Ok(tym)
                     }
-                    .map(|tym| cast_tym!(tym))
+                    Err(err) => Err(err),
                 }
+                .map(|tym| cast_tym!(tym)),
                 State::Ended => {
                     break None;
                 }
@@ -338,11 +427,19 @@ impl<TStack: Stack<StackEntry>> Iterator for Parser<'_, TStack> {
                             )
                         }
                         ToApplyShallowSnapshotAndThen::ProcessCallUnnamedArgument => {
+                            let indicator_start = self.inner.cursor() - "||".len();
                             if matches!(self.input.get(self.inner.cursor()), Some(m!('`'))) {
                                 self.inner.move_cursor_forward("`".len());
+                                let argument = CallArgument {
+                                    name: None,
+                                    span: SourceSpan::new_universal(
+                                        indicator_start,
+                                        self.inner.cursor(),
+                                    ),
+                                };
                                 let tym_a = self
                                     .inner
-                                    .r#yield(ev!(Block, IndicateCallVerbatimArgument(None)));
+                                    .r#yield(ev!(Block, IndicateCallVerbatimArgument(argument)));
                                 let tym_b = terminal::call_verbatim_argument_value::enter(
                                     &mut self.state,
                                     &mut self.inner,
@@ -350,9 +447,16 @@ impl<TStack: Stack<StackEntry>> Iterator for Parser<'_, TStack> {
                                 Ok(tym_a.add(tym_b).into())
                             } else {
                                 self.state = Expecting::BracedOpening.into();
+                                let argument = CallArgument {
+                                    name: None,
+                                    span: SourceSpan::new_universal(
+                                        indicator_start,
+                                        self.inner.cursor(),
+                                    ),
+                                };
                                 Ok(self
                                     .inner
-                                    .r#yield(ev!(Block, IndicateCallNormalArgument(None)))
+                                    .r#yield(ev!(Block, IndicateCallNormalArgument(argument)))
                                     .into())
                             }
                         }
@@ -386,25 +490,59 @@ pub mod item_like {
         use GeneralItemLike as I;
         use ItemLikeContainer as G;
 
+        let opening_start = inner.cursor();
         match first_char {
             m!('>') if is_indeed_opening_and_consume_if_true(input, inner) => {
-                process_greater_than_opening(inner, item_likes_state).map(|tym| cast_tym!(tym))
+                process_greater_than_opening(inner, item_likes_state, opening_start)
+                    .map(|tym| cast_tym!(tym))
             }
             m!('#') if is_indeed_opening_and_consume_if_true(input, inner) => {
-                process_general_opening(state, inner, item_likes_state, G::OL, I::LI)
-                    .map(|tym| cast_tym!(tym))
+                process_general_opening(
+                    input,
+                    state,
+                    inner,
+                    item_likes_state,
+                    G::OL,
+                    I::LI,
+                    opening_start,
+                )
+                .map(|tym| cast_tym!(tym))
             }
             m!('*') if is_indeed_opening_and_consume_if_true(input, inner) => {
-                process_general_opening(state, inner, item_likes_state, G::UL, I::LI)
-                    .map(|tym| cast_tym!(tym))
+                process_general_opening(
+                    input,
+                    state,
+                    inner,
+                    item_likes_state,
+                    G::UL,
+                    I::LI,
+                    opening_start,
+                )
+                .map(|tym| cast_tym!(tym))
             }
             m!(';') if is_indeed_opening_and_consume_if_true(input, inner) => {
-                process_general_opening(state, inner, item_likes_state, G::DL, I::DT)
-                    .map(|tym| cast_tym!(tym))
+                process_general_opening(
+                    input,
+                    state,
+                    inner,
+                    item_likes_state,
+                    G::DL,
+                    I::DT,
+                    opening_start,
+                )
+                .map(|tym| cast_tym!(tym))
             }
             m!(':') if is_indeed_opening_and_consume_if_true(input, inner) => {
-                process_general_opening(state, inner, item_likes_state, G::DL, I::DD)
-                    .map(|tym| cast_tym!(tym))
+                process_general_opening(
+                    input,
+                    state,
+                    inner,
+                    item_likes_state,
+                    G::DL,
+                    I::DD,
+                    opening_start,
+                )
+                .map(|tym| cast_tym!(tym))
             }
             _ => match item_likes_state {
                 ItemLikesState::MatchingLastLine(matching_last_line) => {
@@ -413,12 +551,18 @@ pub mod item_like {
                             n: matching_last_line.processed_item_likes(),
                             should_also_exit_containee_in_last_container: false,
                         },
-                        ExitingAndThen::ExpectBracedOpening,
+                        ExitingAndThen::ExpectNewItemLikeOpening,
                     )
                     .into();
                     Ok(TYM_UNIT.into())
                 }
                 ItemLikesState::ProcessingNew => {
+                    // 附加信息之后仍可以是列表之类的构建，因此不改变状态。
+                    if let Some(tym) =
+                        braced::attached_info::parse_and_process(input, inner, first_char)
+                    {
+                        return Ok(tym.into());
+                    }
                     *state = State::Expecting(Expecting::BracedOpening);
                     braced::parse_opening_and_process(input, state, inner, first_char)
                         .map(|tym| cast_tym!(tym))
@@ -430,6 +574,7 @@ pub mod item_like {
     fn process_greater_than_opening<TStack: Stack<StackEntry>>(
         inner: &mut ParserInner<TStack>,
         item_likes_state: &mut ItemLikesState,
+        opening_start: usize,
     ) -> crate::Result<Tym<1>> {
         let tym = match item_likes_state {
             ItemLikesState::MatchingLastLine(matching_last_line) => {
@@ -442,13 +587,14 @@ pub mod item_like {
             }
             ItemLikesState::ProcessingNew => {
                 let id = inner.pop_block_id();
-                inner
-                    .stack
-                    .push_item_like_container(StackEntryItemLikeContainer {
-                        meta: Meta::new(id, inner.current_line()),
-                        r#type: ItemLikeContainer::BlockQuote,
-                    })?;
-                inner.r#yield(ev!(Block, EnterBlockQuote(id.into())))
+                let opening = SourceSpan::new_universal(opening_start, inner.cursor());
+                let stack_entry = StackEntryItemLikeContainer {
+                    meta: Meta::new(id, inner.current_line(), opening),
+                    r#type: ItemLikeContainer::BlockQuote,
+                };
+                let ev = stack_entry.make_enter_event();
+                inner.stack.push_item_like_container(stack_entry)?;
+                inner.r#yield(ev)
             }
         };
 
@@ -456,12 +602,19 @@ pub mod item_like {
     }
 
     fn process_general_opening<TStack: Stack<StackEntry>>(
+        input: &[u8],
         state: &mut State,
         inner: &mut ParserInner<TStack>,
         item_likes_state: &mut ItemLikesState,
         container: ItemLikeContainer,
         item_like: GeneralItemLike,
+        opening_start: usize,
     ) -> crate::Result<Tym<2>> {
+        let checked = match item_like {
+            GeneralItemLike::LI => consume_task_marker_if_present(input, inner),
+            GeneralItemLike::DT | GeneralItemLike::DD => None,
+        };
+
         let tym = match item_likes_state {
             ItemLikesState::MatchingLastLine(matching_last_line) => {
                 let stack_entry = matching_last_line.first_unprocessed_item_like(&inner.stack);
@@ -477,7 +630,12 @@ pub mod item_like {
                         },
                         ExitingAndThen::EnterItemLikeAndExpectItemLike {
                             container: None,
-                            item_like: make_stack_entry_from_general_item_like(item_like, inner),
+                            item_like: make_stack_entry_from_general_item_like(
+                                item_like,
+                                checked,
+                                inner,
+                                opening_start,
+                            ),
                         },
                     )
                     .into()
@@ -489,9 +647,16 @@ pub mod item_like {
                         },
                         ExitingAndThen::EnterItemLikeAndExpectItemLike {
                             container: Some(make_stack_entry_from_item_like_container(
-                                container, inner,
+                                container,
+                                inner,
+                                opening_start,
                             )),
-                            item_like: make_stack_entry_from_general_item_like(item_like, inner),
+                            item_like: make_stack_entry_from_general_item_like(
+                                item_like,
+                                checked,
+                                inner,
+                                opening_start,
+                            ),
                         },
                     )
                     .into();
@@ -500,13 +665,19 @@ pub mod item_like {
             }
             ItemLikesState::ProcessingNew => {
                 let tym_a = {
-                    let stack_entry = make_stack_entry_from_item_like_container(container, inner);
-                    let ev = stack_entry.make_enter_event();
+                    let stack_entry =
+                        make_stack_entry_from_item_like_container(container, inner, opening_start);
+                    let ev = fill_ordered_list_data(input, inner, stack_entry.make_enter_event());
                     inner.stack.push_item_like_container(stack_entry)?;
                     inner.r#yield(ev)
                 };
                 let tym_b = {
-                    let stack_entry = make_stack_entry_from_general_item_like(item_like, inner);
+                    let stack_entry = make_stack_entry_from_general_item_like(
+                        item_like,
+                        checked,
+                        inner,
+                        opening_start,
+                    );
                     let ev = stack_entry.make_enter_event();
                     inner.stack.push_item_like(stack_entry)?;
                     inner.r#yield(ev)
@@ -519,6 +690,25 @@ pub mod item_like {
         Ok(tym)
     }
 
+    /// 若 `ev` 是进入有序列表的事件，则预先查看紧随其第一个列表项指示标记（位于光标之
+    /// 后）的槽位附加信息，将其中的 `start` 与 `type` 填入事件。附加信息本身仍会在之后作
+    /// 为列表项的槽位附加信息产出，届时沿用这里的解析结果。
+    pub(super) fn fill_ordered_list_data<TStack: Stack<StackEntry>>(
+        input: &[u8],
+        inner: &mut ParserInner<TStack>,
+        ev: Event,
+    ) -> Event {
+        match ev {
+            ev!(Block, EnterOrderedList(mut list)) => {
+                crate::attached_info::fill_ordered_list(input, inner.cursor(), &mut list, |info| {
+                    inner.record_looked_ahead_attached_info(info)
+                });
+                ev!(Block, EnterOrderedList(list))
+            }
+            ev => ev,
+        }
+    }
+
     fn is_indeed_opening_and_consume_if_true<TStack: Stack<StackEntry>>(
         input: &[u8],
         inner: &mut ParserInner<TStack>,
@@ -533,22 +723,52 @@ pub mod item_like {
         true
     }
 
+    /// 若光标处是任务列表项的标记（`[ ]`、`[x]` 或 `[X]`，其后须为空白或行尾），则消耗该
+    /// 标记（及其后的一个空白），返回其是否已勾选。
+    fn consume_task_marker_if_present<TStack: Stack<StackEntry>>(
+        input: &[u8],
+        inner: &mut ParserInner<TStack>,
+    ) -> Option<bool> {
+        let cursor = inner.cursor();
+        let checked = match input.get(cursor..cursor + "[ ]".len())? {
+            b"[ ]" => false,
+            b"[x]" | b"[X]" => true,
+            _ => return None,
+        };
+        match input.get(cursor + "[ ]".len()) {
+            Some(c) if is_whitespace!(c) => inner.move_cursor_forward("[ ] ".len()),
+            None | Some(b'\r' | b'\n') => inner.move_cursor_forward("[ ]".len()),
+            _ => return None,
+        }
+
+        Some(checked)
+    }
+
+    /// 指示标记从 `opening_start` 开始，到 `inner.cursor()` 为止。
     pub fn make_stack_entry_from_general_item_like<TStack: Stack<StackEntry>>(
         item_like: GeneralItemLike,
+        checked: Option<bool>,
         inner: &mut ParserInner<TStack>,
+        opening_start: usize,
     ) -> StackEntryItemLike {
+        let opening = SourceSpan::new_universal(opening_start, inner.cursor());
         StackEntryItemLike {
-            meta: Meta::new(inner.pop_block_id(), inner.current_line()),
+            meta: Meta::new(inner.pop_block_id(), inner.current_line(), opening),
             r#type: item_like,
+            checked,
         }
     }
 
+    /// 容器本身没有开启部分，以其第一个项目的指示标记（从 `opening_start` 开始，到
+    /// `inner.cursor()` 为止）作为开启部分。
     fn make_stack_entry_from_item_like_container<TStack: Stack<StackEntry>>(
         item_like: ItemLikeContainer,
         inner: &mut ParserInner<TStack>,
+        opening_start: usize,
     ) -> StackEntryItemLikeContainer {
+        let opening = SourceSpan::new_universal(opening_start, inner.cursor());
         StackEntryItemLikeContainer {
-            meta: Meta::new(inner.pop_block_id(), inner.current_line()),
+            meta: Meta::new(inner.pop_block_id(), inner.current_line(), opening),
             r#type: item_like,
         }
     }
@@ -557,7 +777,8 @@ pub mod item_like {
         inner: &mut ParserInner<TStack>,
         stack_entry: StackEntryItemLikeContainer,
     ) -> crate::Result<Tym<1>> {
-        let tym = inner.r#yield(stack_entry.make_exit_event(inner.current_line()));
+        let tym =
+            inner.r#yield(stack_entry.make_exit_event(inner.current_line(), inner.span_end()));
 
         Ok(tym)
     }
@@ -566,7 +787,8 @@ pub mod item_like {
         inner: &mut ParserInner<TStack>,
         stack_entry: StackEntryItemLike,
     ) -> crate::Result<Tym<1>> {
-        let tym = inner.r#yield(stack_entry.make_exit_event(inner.current_line()));
+        let tym =
+            inner.r#yield(stack_entry.make_exit_event(inner.current_line(), inner.span_end()));
 
         Ok(tym)
     }
@@ -584,10 +806,18 @@ pub mod braced {
         match first_char {
             m!('{') => match input.get(inner.cursor() + 1) {
                 Some(m!('|')) => {
+                    let opening_start = inner.cursor();
                     inner.move_cursor_forward("{|".len());
-                    table::enter(state, inner).map(|tym| cast_tym!(tym))
+                    table::enter(state, inner, opening_start).map(|tym| cast_tym!(tym))
                 }
                 Some(m!('{')) => {
+                    if input.get(inner.cursor() + 2) == Some(&m!('{')) {
+                        if let Some(result) =
+                            parameter_filling::parse_opening_and_process(input, state, inner)
+                        {
+                            return result.map(|tym| cast_tym!(tym));
+                        }
+                    }
                     inner.stack.push_leaf(
                         LeafPotentialCallBeginning {
                             shallow_snapshot: inner.take_shallow_snapshot(),
@@ -598,6 +828,14 @@ pub mod braced {
                     inner.move_cursor_forward("{{".len());
                     Ok(TYM_UNIT.into())
                 }
+                Some(m!('&')) => {
+                    if let Some(tym) = attached_info::parse_and_process(input, inner, first_char) {
+                        Ok(tym.into())
+                    } else {
+                        terminal::paragraph::enter_if_not_blank(input, state, inner, 1)
+                            .map(|tym| cast_tym!(tym))
+                    }
+                }
                 _ => terminal::paragraph::enter_if_not_blank(input, state, inner, 1)
                     .map(|tym| cast_tym!(tym)),
             },
@@ -620,18 +858,130 @@ pub mod braced {
         first_char == m!('|') && second_char == m!('|')
     }
 
+    pub mod parameter_filling {
+        use crate::internal_utils::string::trim_end;
+
+        use super::*;
+
+        /// 尝试将位于 `inner.cursor()` 处的内容作为参数填充的开启部分（`{{{` 名称，以及
+        /// `||` 或作为闭合部分的 `}}}`）解析。名称不能跨行。失败时不做任何改动，返回
+        /// `None`。
+        pub fn parse_opening_and_process<TStack: Stack<StackEntry>>(
+            input: &[u8],
+            state: &mut State,
+            inner: &mut ParserInner<TStack>,
+        ) -> Option<crate::Result<Tym<2>>> {
+            debug_assert!(input[inner.cursor()..].starts_with(b"{{{"));
+
+            let opening_start = inner.cursor();
+            let mut name_start = inner.cursor() + "{{{".len();
+            name_start += count_continuous_whitespaces(input, name_start);
+            let mut cursor = name_start;
+            let (name, has_fallback) = loop {
+                match input.get(cursor)? {
+                    b'\r' | b'\n' => return None,
+                    m!('|') if input.get(cursor + 1) == Some(&m!('|')) => {
+                        let name = trim_end(input, name_start..cursor);
+                        cursor += "||".len();
+                        break (name, true);
+                    }
+                    m!('}') if input[cursor..].starts_with(b"}}}") => {
+                        let name = trim_end(input, name_start..cursor);
+                        cursor += "}}}".len();
+                        break (name, false);
+                    }
+                    _ => cursor += 1,
+                }
+            };
+            if name.is_empty() {
+                return None;
+            }
+            inner.set_cursor(cursor);
+
+            let id = inner.pop_block_id();
+            let line = inner.current_line();
+            let opening = SourceSpan::new_universal(opening_start, name.end);
+            let stack_entry = StackEntryCall {
+                meta: Meta::new(id, line, opening),
+                is_parameter_filling: true,
+                opening_start,
+            };
+            let tym_a = inner.r#yield(stack_entry.make_parameter_filling_enter_event(name));
+
+            let tym_b = if has_fallback {
+                *state = Expecting::BracedOpening.into();
+                if let Err(err) = inner.stack.push_call(stack_entry) {
+                    return Some(Err(err));
+                }
+                let span = SourceSpan::new_universal(cursor - "||".len(), cursor);
+                inner.r#yield(ev!(Block, IndicateParameterFillingFallback(span)))
+            } else {
+                inner.r#yield(stack_entry.make_exit_event(line, cursor))
+            };
+
+            Some(Ok(tym_a.add(tym_b)))
+        }
+    }
+
+    pub mod attached_info {
+        use crate::events::{AttachedInfo, AttachedInfoTarget};
+
+        use super::*;
+
+        /// 尝试将位于 `inner.cursor()` 处的内容作为块级附加信息解析。成功时产出对应
+        /// 的事件，并将光标移至闭合部分之后；失败时不做任何改动，返回 `None`。
+        pub fn parse_and_process<TStack: Stack<StackEntry>>(
+            input: &[u8],
+            inner: &mut ParserInner<TStack>,
+            first_char: u8,
+        ) -> Option<Tym<1>> {
+            if first_char != m!('{') || input.get(inner.cursor() + 1) != Some(&m!('&')) {
+                return None;
+            }
+            let target = match input.get(inner.cursor() + 2) {
+                Some(m!('>')) => AttachedInfoTarget::Following,
+                Some(m!('<')) if inner.is_at_slot_beginning() => AttachedInfoTarget::Slot,
+                _ => return None,
+            };
+
+            let start = inner.cursor() + "{&>".len();
+            let end = match target {
+                AttachedInfoTarget::Slot => inner.take_looked_ahead_attached_info_end(start),
+                AttachedInfoTarget::Following => None,
+            };
+            let end = match end {
+                Some(end) => end,
+                None => crate::attached_info::parse_sequence(input, start, m!('}'))?,
+            };
+            inner.set_cursor(end + "}".len());
+
+            let tym = inner.r#yield(ev!(
+                Block,
+                BlockAttachedInfo(AttachedInfo {
+                    target,
+                    content: start..end,
+                })
+            ));
+
+            Some(tym)
+        }
+    }
+
     pub mod table {
         use super::*;
 
         pub(super) fn enter<TStack: Stack<StackEntry>>(
             state: &mut State,
             inner: &mut ParserInner<TStack>,
+            opening_start: usize,
         ) -> crate::Result<Tym<1>> {
             *state = Expecting::BracedOpening.into();
 
             let id = inner.pop_block_id();
+            let opening = SourceSpan::new_universal(opening_start, inner.cursor());
             let stack_entry = StackEntryTable {
-                meta: Meta::new(id, inner.current_line()),
+                meta: Meta::new(id, inner.current_line(), opening),
+                opening_start,
             };
             let ev = stack_entry.make_enter_event();
             inner.stack.push_table(stack_entry)?;
@@ -648,7 +998,9 @@ pub mod braced {
             HeaderCellIndicator,
         }
         impl TableRelatedEnd {
-            pub fn process(self, state: &mut State) -> Tym<0> {
+            /// `cursor` 位于刚被消耗的闭合部分或指示标记之后。
+            pub fn process(self, state: &mut State, cursor: usize) -> Tym<0> {
+                let span = SourceSpan::new_universal(cursor - 2, cursor);
                 *state = match self {
                     TableRelatedEnd::Closing => Exiting::new(
                         ExitingUntil::TopIsTable {
@@ -663,7 +1015,7 @@ pub mod braced {
                         },
                         ExitingAndThen::YieldAndExpectBracedOpening(ev!(
                             Block,
-                            IndicateTableCaption
+                            IndicateTableCaption(span)
                         )),
                     )
                     .into(),
@@ -671,7 +1023,10 @@ pub mod braced {
                         ExitingUntil::TopIsTable {
                             should_also_exit_table: false,
                         },
-                        ExitingAndThen::YieldAndExpectBracedOpening(ev!(Block, IndicateTableRow)),
+                        ExitingAndThen::YieldAndExpectBracedOpening(ev!(
+                            Block,
+                            IndicateTableRow(span)
+                        )),
                     )
                     .into(),
                     TableRelatedEnd::HeaderCellIndicator => Exiting::new(
@@ -680,7 +1035,7 @@ pub mod braced {
                         },
                         ExitingAndThen::YieldAndExpectBracedOpening(ev!(
                             Block,
-                            IndicateTableHeaderCell
+                            IndicateTableHeaderCell(TableCell::new(span))
                         )),
                     )
                     .into(),
@@ -714,6 +1069,31 @@ pub mod braced {
             Some(end)
         }
 
+        /// 若 `ev` 是表格单元格的指示事件，则预先查看紧随其后（位于光标之后）的槽位附加信
+        /// 息，将其中的 `colspan` 与 `rowspan` 填入事件。附加信息本身仍会在之后产出，届时
+        /// 沿用这里的解析结果。
+        pub fn fill_cell_data<TStack: Stack<StackEntry>>(
+            input: &[u8],
+            inner: &mut ParserInner<TStack>,
+            ev: Event,
+        ) -> Event {
+            let cursor = inner.cursor();
+            let mut parse = |span| {
+                crate::attached_info::parse_table_cell(input, cursor, span, |info| {
+                    inner.record_looked_ahead_attached_info(info)
+                })
+            };
+            match ev {
+                ev!(Block, IndicateTableHeaderCell(cell)) => {
+                    ev!(Block, IndicateTableHeaderCell(parse(cell.span)))
+                }
+                ev!(Block, IndicateTableDataCell(cell)) => {
+                    ev!(Block, IndicateTableDataCell(parse(cell.span)))
+                }
+                ev => ev,
+            }
+        }
+
         pub fn is_end(first_char: u8, second_char: u8) -> bool {
             match first_char {
                 m!('|') => matches!(second_char, m!('}') | m!('+') | m!('-')),
@@ -726,7 +1106,8 @@ pub mod braced {
             inner: &mut ParserInner<TStack>,
             stack_entry: StackEntryTable,
         ) -> crate::Result<Tym<1>> {
-            let tym = inner.r#yield(stack_entry.make_exit_event(inner.current_line()));
+            let tym =
+                inner.r#yield(stack_entry.make_exit_event(inner.current_line(), inner.span_end()));
 
             Ok(tym)
         }
@@ -753,12 +1134,17 @@ pub mod braced {
             inner: &mut ParserInner<TStack>,
             is_extension: bool,
             name: Range<usize>,
+            opening_start: usize,
         ) -> crate::Result<Tym<1>> {
             *state = Expecting::BracedOpening.into();
 
             let id = inner.pop_block_id();
+            // 参数的指示标记可能位于之后的行，不计入开启部分。
+            let opening = SourceSpan::new_universal(opening_start, name.end);
             let stack_entry = StackEntryCall {
-                meta: Meta::new(id, inner.current_line()),
+                meta: Meta::new(id, inner.current_line(), opening),
+                is_parameter_filling: false,
+                opening_start,
             };
             let ev = stack_entry.make_enter_event(is_extension, name);
             inner.stack.push_call(stack_entry)?;
@@ -771,14 +1157,19 @@ pub mod braced {
             inner: &mut ParserInner<TStack>,
             is_extension: bool,
             name: Range<usize>,
+            opening_start: usize,
         ) -> Tym<2> {
             let id = inner.pop_block_id();
             let line = inner.current_line();
+            let opening = SourceSpan::new_universal(opening_start, name.end);
             let stack_entry = StackEntryCall {
-                meta: Meta::new(id, line),
+                meta: Meta::new(id, line, opening),
+                is_parameter_filling: false,
+                opening_start,
             };
             let tym_a = inner.r#yield(stack_entry.make_enter_event(is_extension, name));
-            let tym_b = inner.r#yield(stack_entry.make_exit_event(line));
+            // 闭合部分刚被消耗。
+            let tym_b = inner.r#yield(stack_entry.make_exit_event(line, inner.cursor()));
 
             tym_a.add(tym_b)
         }
@@ -803,30 +1194,43 @@ pub mod braced {
             }
         }
 
+        /// `is_parameter_filling` 为真时，闭合部分为参数填充的 `}}}`。
         pub fn parse_end<TCtx: CursorContext>(
             input: &[u8],
             ctx: &mut TCtx,
             first_char: u8,
+            is_parameter_filling: bool,
         ) -> Option<CallRelatedEnd> {
-            let &second_char = input.get(ctx.cursor() + 1)?;
-            let end = if first_char == m!('}') && second_char == m!('}') {
-                CallRelatedEnd::Closing
-            } else {
+            let closing: &[u8] = if is_parameter_filling { b"}}}" } else { b"}}" };
+            if first_char != m!('}') || !input[ctx.cursor()..].starts_with(closing) {
                 return None;
-            };
-            ctx.move_cursor_forward(2);
-            Some(end)
+            }
+            ctx.move_cursor_forward(closing.len());
+            Some(CallRelatedEnd::Closing)
         }
 
         pub fn is_end(first_char: u8, second_char: u8) -> bool {
             first_char == m!('}') && second_char == m!('}')
         }
 
+        pub fn make_call_related_end_condition<TStack: Stack<StackEntry>>(
+            inner: &ParserInner<TStack>,
+        ) -> Option<line::normal::CallRelated> {
+            if inner.stack.calls_in_stack() > 0 {
+                Some(line::normal::CallRelated {
+                    is_parameter_filling: inner.stack.innermost_call_is_parameter_filling(),
+                })
+            } else {
+                None
+            }
+        }
+
         pub fn exit<TStack: Stack<StackEntry>>(
             inner: &mut ParserInner<TStack>,
             stack_entry: StackEntryCall,
         ) -> crate::Result<Tym<1>> {
-            let tym = inner.r#yield(stack_entry.make_exit_event(inner.current_line()));
+            let tym =
+                inner.r#yield(stack_entry.make_exit_event(inner.current_line(), inner.span_end()));
 
             Ok(tym)
         }
@@ -849,7 +1253,7 @@ mod terminal {
                 let count = 1 + count_continuous_character(input, m!('-'), inner.cursor() + 1);
                 if count >= 3 {
                     inner.move_cursor_forward(count);
-                    thematic_break::process(inner).map(|tym| cast_tym!(tym))
+                    thematic_break::process(inner, count).map(|tym| cast_tym!(tym))
                 } else {
                     paragraph::enter_if_not_blank(input, state, inner, count)
                         .map(|tym| cast_tym!(tym))
@@ -928,6 +1332,7 @@ mod terminal {
 
         pub fn process<TStack: Stack<StackEntry>>(
             inner: &mut ParserInner<TStack>,
+            dashes: usize,
         ) -> crate::Result<Tym<1>> {
             let id = inner.pop_block_id();
             let tym = inner.r#yield(ev!(
@@ -935,6 +1340,9 @@ mod terminal {
                 ThematicBreak(ThematicBreak {
                     id,
                     line: inner.current_line(),
+                    span: SourceSpan::new_universal(inner.cursor() - dashes, inner.cursor()),
+                    start: Position::new_unresolved(),
+                    end: Position::new_unresolved(),
                 })
             ));
 
@@ -952,8 +1360,9 @@ mod terminal {
             level: usize,
         ) -> crate::Result<Tym<1>> {
             let id = inner.pop_block_id();
+            let opening = SourceSpan::new_universal(inner.cursor() - (level + 1), inner.cursor());
             let leaf = LeafHeading {
-                meta: Meta::new(id, inner.current_line()),
+                meta: Meta::new(id, inner.current_line(), opening),
                 level,
                 has_content_before: false,
             };
@@ -979,7 +1388,10 @@ mod terminal {
                         count: leaf.level,
                     }),
                     on_table_related: braced::table::make_table_related_end_condition(inner, false),
-                    on_call_related: inner.stack.calls_in_stack() > 0,
+                    on_call_related: braced::call::make_call_related_end_condition(inner),
+                    is_double_pipes_literal: inner
+                        .stack
+                        .innermost_table_or_call_is_parameter_filling(),
                     ..Default::default()
                 },
                 if inner.current_expecting.spaces_before() > 0 {
@@ -996,14 +1408,20 @@ mod terminal {
                 content.start -= inner.current_expecting.spaces_before();
             }
 
-            let tym_a = if !content.is_empty() {
+            let has_content = !content.is_empty();
+            let tym_a = if has_content {
                 inner.r#yield(ev!(Block, __Unparsed(content)))
             } else {
                 TYM_UNIT.into()
             };
 
             let tym_b = match end {
-                line::normal::End::Eof | line::normal::End::NewLine(_) => exit(inner, leaf),
+                line::normal::End::Eof | line::normal::End::NewLine(_) => {
+                    // 使标题的范围包括可能存在的 ATX 闭合部分。
+                    #[cfg(feature = "source-span")]
+                    inner.extend_span_end(trim_end_of_consumed_line(input, inner.cursor()));
+                    exit(inner, leaf)
+                }
                 line::normal::End::VerbatimEscaping(verbatim_escaping) => {
                     leaf.has_content_before = true;
                     inner.stack.push_leaf(leaf.into());
@@ -1011,7 +1429,7 @@ mod terminal {
                 }
                 line::normal::End::TableRelated(table_related_end) => {
                     let tym_a = exit(inner, leaf);
-                    let tym_b = table_related_end.process(state);
+                    let tym_b = table_related_end.process(state, inner.cursor());
 
                     tym_a.add(tym_b)
                 }
@@ -1027,7 +1445,12 @@ mod terminal {
 
                     tym_a.add(tym_b)
                 }
-                line::normal::End::None => TYM_UNIT.into(),
+                line::normal::End::None => {
+                    // 注释之后还可能有标题的内容。
+                    leaf.has_content_before |= has_content;
+                    inner.stack.push_leaf(leaf.into());
+                    TYM_UNIT.into()
+                }
                 line::normal::End::DescriptionDefinitionOpening
                 | line::normal::End::Matched
                 | line::normal::End::MatchedCallName { .. }
@@ -1042,11 +1465,26 @@ mod terminal {
             Ok(tym_a.add(tym_b))
         }
 
+        /// 返回刚被消耗的一行（`cursor` 位于其后）在去掉末尾的换行与空白后的末尾。
+        #[cfg(feature = "source-span")]
+        fn trim_end_of_consumed_line(input: &[u8], mut cursor: usize) -> usize {
+            if cursor > 0 && input[cursor - 1] == b'\n' {
+                cursor -= 1;
+            }
+            if cursor > 0 && input[cursor - 1] == b'\r' {
+                cursor -= 1;
+            }
+            while cursor > 0 && is_whitespace!(input[cursor - 1]) {
+                cursor -= 1;
+            }
+            cursor
+        }
+
         pub fn exit<TStack: Stack<StackEntry>>(
             inner: &mut ParserInner<TStack>,
             leaf: LeafHeading,
         ) -> Tym<1> {
-            inner.r#yield(leaf.make_exit_event(inner.current_line()))
+            inner.r#yield(leaf.make_exit_event(inner.current_line(), inner.span_end()))
         }
     }
 
@@ -1060,8 +1498,9 @@ mod terminal {
             backticks: usize,
         ) -> crate::Result<Tym<1>> {
             let id = inner.pop_block_id();
+            let opening = SourceSpan::new_universal(inner.cursor() - backticks, inner.cursor());
             let leaf = LeafCodeBlock {
-                meta: Meta::new(id, inner.current_line()),
+                meta: Meta::new(id, inner.current_line(), opening),
                 backticks,
                 indent: inner.current_expecting.spaces_before(),
                 state: LeafCodeBlockState::InInfoString,
@@ -1100,7 +1539,8 @@ mod terminal {
                             leaf.state = LeafCodeBlockState::InCode(
                                 LeafVerbatimParseState::AtFirstLineBeginning,
                             );
-                            inner.r#yield(ev!(Block, IndicateCodeBlockCode))
+                            let span = SourceSpan::new_universal(inner.cursor(), inner.cursor());
+                            inner.r#yield(ev!(Block, IndicateCodeBlockCode(span)))
                         }
                         line::verbatim::End::VerbatimEscaping(verbatim_escaping) => {
                             line::global_phase::process_verbatim_escaping(inner, verbatim_escaping)
@@ -1176,6 +1616,8 @@ mod terminal {
                             line::global_phase::process_verbatim_escaping(inner, verbatim_escaping)
                         }
                         line::verbatim::End::Fence => {
+                            // 闭合部分刚被消耗。
+                            inner.extend_span_end(inner.cursor());
                             exit_when_indicator_already_yielded(inner, leaf)
                         }
                         line::verbatim::End::None => {
@@ -1197,7 +1639,7 @@ mod terminal {
             inner: &mut ParserInner<TStack>,
             leaf: LeafCodeBlock,
         ) -> Tym<1> {
-            inner.r#yield(leaf.make_exit_event(inner.current_line()))
+            inner.r#yield(leaf.make_exit_event(inner.current_line(), inner.span_end()))
         }
 
         pub fn exit<TStack: Stack<StackEntry>>(
@@ -1205,7 +1647,8 @@ mod terminal {
             leaf: LeafCodeBlock,
         ) -> Tym<2> {
             let tym_a = if matches!(leaf.state, LeafCodeBlockState::InInfoString) {
-                inner.r#yield(ev!(Block, IndicateCodeBlockCode))
+                let span = SourceSpan::new_universal(inner.span_end(), inner.span_end());
+                inner.r#yield(ev!(Block, IndicateCodeBlockCode(span)))
             } else {
                 TYM_UNIT.into()
             };
@@ -1239,6 +1682,7 @@ mod terminal {
             // [line::normal::parse] 的过程中可能会涉及到逐字转义，导致行数增加，因此
             // 需要提前取得行数。
             let line_start = inner.current_line();
+            let start = inner.cursor();
 
             let has_just_entered_table = inner.has_just_entered_table();
             let (content, mut end) = line::normal::parse(
@@ -1249,7 +1693,10 @@ mod terminal {
                         inner,
                         has_just_entered_table,
                     ),
-                    on_call_related: inner.stack.calls_in_stack() > 0,
+                    on_call_related: braced::call::make_call_related_end_condition(inner),
+                    is_double_pipes_literal: inner
+                        .stack
+                        .innermost_table_or_call_is_parameter_filling(),
                     on_description_definition_opening: inner.stack.top_is_description_term(),
                     ..Default::default()
                 },
@@ -1259,7 +1706,7 @@ mod terminal {
             let tym_ab = if !content.is_empty() || end.is_verbatim_escaping() {
                 let id = inner.pop_block_id();
                 let leaf = LeafParagraph {
-                    meta: Meta::new(id, line_start),
+                    meta: Meta::new(id, line_start, SourceSpan::new_universal(start, start)),
                     new_line: end.try_take_new_line(),
                 };
                 let ev = leaf.make_enter_event();
@@ -1277,12 +1724,13 @@ mod terminal {
                 TYM_UNIT.into()
             };
 
-            let tym_c = process_normal_end(end, state, inner)?;
+            let tym_c = process_normal_end(input, end, state, inner)?;
 
             Ok(tym_ab.add(tym_c))
         }
 
         fn process_normal_end<TStack: Stack<StackEntry>>(
+            input: &[u8],
             end: line::normal::End,
             state: &mut State,
             inner: &mut ParserInner<TStack>,
@@ -1300,7 +1748,7 @@ mod terminal {
                     cast_tym!(tym)
                 }
                 line::normal::End::TableRelated(table_related_end) => {
-                    let tym = table_related_end.process(state);
+                    let tym = table_related_end.process(state, inner.cursor());
                     cast_tym!(tym)
                 }
                 line::normal::End::CallRelated(call_related_end) => {
@@ -1328,9 +1776,17 @@ mod terminal {
 
                     // 进入 DD
                     let tym_c = {
+                        // `::` 之后可能还有一个已被消耗的空白。
+                        let opening_start = if input[..inner.cursor()].ends_with(b"::") {
+                            inner.cursor() - "::".len()
+                        } else {
+                            inner.cursor() - ":: ".len()
+                        };
                         let stack_entry = item_like::make_stack_entry_from_general_item_like(
                             GeneralItemLike::DD,
+                            None,
                             inner,
+                            opening_start,
                         );
                         let ev = stack_entry.make_enter_event();
                         inner.stack.push_item_like(stack_entry)?;
@@ -1364,7 +1820,10 @@ mod terminal {
                 inner,
                 line::normal::EndCondition {
                     on_table_related: braced::table::make_table_related_end_condition(inner, false),
-                    on_call_related: inner.stack.calls_in_stack() > 0,
+                    on_call_related: braced::call::make_call_related_end_condition(inner),
+                    is_double_pipes_literal: inner
+                        .stack
+                        .innermost_table_or_call_is_parameter_filling(),
                     on_description_definition_opening: inner.stack.top_is_description_term(),
                     ..Default::default()
                 },
@@ -1375,8 +1834,10 @@ mod terminal {
                 },
             );
 
-            let is_still_in_paragraph =
-                !content.is_empty() || leaf.new_line.is_none() || end.is_verbatim_escaping();
+            let is_still_in_paragraph = !content.is_empty()
+                || leaf.new_line.is_none()
+                || end.is_verbatim_escaping()
+                || inner.is_inline_only();
             let tym_ab = if is_still_in_paragraph {
                 let tym_a = if let Some(new_line) = leaf.new_line {
                     inner.r#yield(ev!(Block, NewLine(new_line)))
@@ -1402,7 +1863,7 @@ mod terminal {
                 exit(inner, leaf).into()
             };
 
-            let tym_c = process_normal_end(end, state, inner)?;
+            let tym_c = process_normal_end(input, end, state, inner)?;
 
             Ok(tym_ab.add(tym_c))
         }
@@ -1411,7 +1872,7 @@ mod terminal {
             inner: &mut ParserInner<TStack>,
             leaf: LeafParagraph,
         ) -> Tym<1> {
-            inner.r#yield(leaf.make_exit_event(inner.current_line()))
+            inner.r#yield(leaf.make_exit_event(inner.current_line(), inner.span_end()))
         }
     }
 
@@ -1424,6 +1885,7 @@ mod terminal {
             inner: &mut ParserInner<TStack>,
             leaf: LeafPotentialCallBeginning,
         ) -> crate::Result<Tym<2>> {
+            let opening_start = leaf.shallow_snapshot.cursor();
             let (_content, end) = line::normal::parse(
                 input,
                 inner,
@@ -1449,10 +1911,11 @@ mod terminal {
                     extra_matched,
                 } => match extra_matched {
                     line::normal::MatchedCallNameExtraMatched::CallClosing => {
-                        braced::call::enter_and_exit(inner, is_extension, range)
+                        braced::call::enter_and_exit(inner, is_extension, range, opening_start)
                     }
                     line::normal::MatchedCallNameExtraMatched::ArgumentIndicator => {
-                        let tym = braced::call::enter(state, inner, is_extension, range)?;
+                        let tym =
+                            braced::call::enter(state, inner, is_extension, range, opening_start)?;
                         inner.stack.push_leaf(
                             LeafCallArgumentBeginning {
                                 shallow_snapshot: inner.take_shallow_snapshot(),
@@ -1478,13 +1941,23 @@ mod terminal {
                 },
                 line::normal::End::MatchedCallClosing => {
                     let name_part = leaf.name_part.unwrap();
-                    braced::call::enter_and_exit(inner, name_part.is_extension, name_part.name)
+                    braced::call::enter_and_exit(
+                        inner,
+                        name_part.is_extension,
+                        name_part.name,
+                        opening_start,
+                    )
                 }
                 line::normal::End::MatchedCallArgumentIndicator => {
                     let name_part = leaf.name_part.unwrap();
 
-                    let tym =
-                        braced::call::enter(state, inner, name_part.is_extension, name_part.name)?;
+                    let tym = braced::call::enter(
+                        state,
+                        inner,
+                        name_part.is_extension,
+                        name_part.name,
+                        opening_start,
+                    )?;
                     inner.stack.push_leaf(
                         LeafCallArgumentBeginning {
                             shallow_snapshot: inner.take_shallow_snapshot(),
@@ -1514,7 +1987,7 @@ mod terminal {
                 line::normal::End::Eof
                 | line::normal::End::Mismatched
                 | line::normal::End::VerbatimEscaping(_) => {
-                    *state = exit_for_mismatch(leaf);
+                    *state = exit_for_mismatch(inner, leaf);
                     TYM_UNIT.into()
                 }
                 line::normal::End::TableRelated(_)
@@ -1530,7 +2003,16 @@ mod terminal {
             Ok(tym)
         }
 
-        fn exit_for_mismatch(leaf: LeafPotentialCallBeginning) -> State {
+        fn exit_for_mismatch<TStack: Stack<StackEntry>>(
+            inner: &mut ParserInner<TStack>,
+            leaf: LeafPotentialCallBeginning,
+        ) -> State {
+            let opening_start = leaf.shallow_snapshot.cursor();
+            inner.report_diagnostic(
+                DiagnosticCode::BlockCallOpeningAsText,
+                opening_start..(opening_start + "{{".len()),
+            );
+
             ToApplyShallowSnapshot {
                 shallow_snapshot: leaf.shallow_snapshot,
                 and_then: ToApplyShallowSnapshotAndThen::TryParseAsParagraph,
@@ -1544,13 +2026,23 @@ mod terminal {
             can_still_form_if_applicable: bool,
         ) -> (Tym<2>, Option<State>) {
             if can_still_form_if_applicable && leaf.name_part.is_some() {
+                let opening_start = leaf.shallow_snapshot.cursor();
+                inner.report_diagnostic(
+                    DiagnosticCode::UnclosedBlockCall,
+                    opening_start..(opening_start + "{{".len()),
+                );
                 let name_part = leaf.name_part.unwrap();
                 (
-                    braced::call::enter_and_exit(inner, name_part.is_extension, name_part.name),
+                    braced::call::enter_and_exit(
+                        inner,
+                        name_part.is_extension,
+                        name_part.name,
+                        opening_start,
+                    ),
                     None,
                 )
             } else {
-                (TYM_UNIT.into(), Some(exit_for_mismatch(leaf)))
+                (TYM_UNIT.into(), Some(exit_for_mismatch(inner, leaf)))
             }
         }
     }
@@ -1595,7 +2087,8 @@ mod terminal {
                             *state = exit_for_mismatch(leaf);
                             TYM_UNIT.into()
                         } else {
-                            exit_for_match(state, inner, is_verbatim, range)
+                            let indicator_start = leaf.shallow_snapshot.cursor() - "||".len();
+                            exit_for_match(state, inner, is_verbatim, range, indicator_start)
                         }
                     } else {
                         inner.stack.push_leaf(
@@ -1613,8 +2106,15 @@ mod terminal {
                     }
                 }
                 line::normal::End::Matched => {
+                    let indicator_start = leaf.shallow_snapshot.cursor() - "||".len();
                     let name_part = leaf.name_part.unwrap();
-                    exit_for_match(state, inner, name_part.is_verbatim, name_part.name)
+                    exit_for_match(
+                        state,
+                        inner,
+                        name_part.is_verbatim,
+                        name_part.name,
+                        indicator_start,
+                    )
                 }
                 line::normal::End::None | line::normal::End::NewLine(_) => {
                     inner.stack.push_leaf(leaf.into());
@@ -1656,14 +2156,19 @@ mod terminal {
             inner: &mut ParserInner<TStack>,
             is_verbatim: bool,
             range: Range<usize>,
+            indicator_start: usize,
         ) -> Tym<1> {
+            let argument = CallArgument {
+                name: Some(range),
+                span: SourceSpan::new_universal(indicator_start, inner.cursor()),
+            };
             if is_verbatim {
-                let tym_a = inner.r#yield(ev!(Block, IndicateCallVerbatimArgument(Some(range))));
+                let tym_a = inner.r#yield(ev!(Block, IndicateCallVerbatimArgument(argument)));
                 let tym_b = terminal::call_verbatim_argument_value::enter(state, inner);
                 tym_a.add(tym_b)
             } else {
                 *state = Expecting::BracedOpening.into();
-                inner.r#yield(ev!(Block, IndicateCallNormalArgument(Some(range))))
+                inner.r#yield(ev!(Block, IndicateCallNormalArgument(argument)))
             }
         }
 
@@ -1,10 +1,19 @@
+use core::ops::Range;
+
 use crate::{
     Event,
-    events::ev,
+    diagnostics::{DiagnosticCode, DiagnosticReporter},
+    events::{AttachedInfo, AttachedInfoTarget, ev},
     internal_utils::array_queue::ArrayQueue,
     types::{BlockId, LineNumber, Stack, Tym},
 };
 
+#[cfg(feature = "position")]
+use crate::{
+    events::{BlockWithId, Call, ExitBlock, ListItem, OrderedList, ThematicBreak},
+    internal_utils::position_resolver::PositionResolver,
+};
+
 #[cfg(feature = "block-id")]
 use super::utils::BlockIdGenerator;
 use super::{
@@ -13,8 +22,10 @@ use super::{
 };
 
 const MAX_TO_YIELD: usize = 5;
+/// 至多记录多少个预先查看过的槽位附加信息，超出的部分之后仍会被再次解析。
+const MAX_LOOKED_AHEAD_ATTACHED_INFOS: usize = 4;
 
-pub struct ParserInner<TStack: Stack<StackEntry>> {
+pub struct ParserInner<'a, TStack: Stack<StackEntry>> {
     cursor: usize,
 
     current_line: LineNumber,
@@ -31,6 +42,26 @@ pub struct ParserInner<TStack: Stack<StackEntry>> {
     pub current_expecting: CurrentExpecting,
 
     has_just_entered_table: bool,
+    /// 是否位于槽位的开头，即可以在此处放置块级槽位附加信息。
+    is_at_slot_beginning: bool,
+    /// 是否将整份输入视为单个段落，不解析其他任何块级结构。
+    is_inline_only: bool,
+    /// 为表格单元格、有序列表预先查看槽位附加信息时确认有效的各个附加信息的内容所在的范
+    /// 围。之后产出这些附加信息时直接沿用，不必再次解析。
+    looked_ahead_attached_infos: ArrayQueue<MAX_LOOKED_AHEAD_ATTACHED_INFOS, Range<usize>>,
+
+    diagnostic_reporter: DiagnosticReporter<'a>,
+    /// 回退到浅快照后，逐字转义与注释可能会被再次解析。位于此前的逐字转义与注释的诊断已经报
+    /// 告过，不再重复报告。
+    global_phase_diagnosed_until: usize,
+
+    /// 已产出的事件所涉及的源码的末尾，用于确定强制退出的元素的范围。
+    #[cfg(feature = "source-span")]
+    span_end: usize,
+
+    /// 用于为块级事件填入位置。
+    #[cfg(feature = "position")]
+    position_resolver: PositionResolver<'a>,
 }
 
 #[derive(Debug, Clone)]
@@ -39,9 +70,18 @@ pub struct ParserInnerShallowSnapshot {
     current_line: LineNumber,
     current_expecting: CurrentExpecting,
 }
+impl ParserInnerShallowSnapshot {
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+}
 
-impl<TStack: Stack<StackEntry>> ParserInner<TStack> {
-    pub fn new() -> Self {
+impl<'a, TStack: Stack<StackEntry>> ParserInner<'a, TStack> {
+    pub fn new(
+        #[allow(unused_variables)] input: &'a [u8],
+        is_inline_only: bool,
+        diagnostic_reporter: DiagnosticReporter<'a>,
+    ) -> Self {
         Self {
             cursor: 0,
             current_line: LineNumber::new_universal(1),
@@ -51,6 +91,15 @@ impl<TStack: Stack<StackEntry>> ParserInner<TStack> {
             block_id_generator: BlockIdGenerator::new(),
             current_expecting: CurrentExpecting::new(),
             has_just_entered_table: false,
+            is_at_slot_beginning: false,
+            is_inline_only,
+            looked_ahead_attached_infos: ArrayQueue::new(),
+            diagnostic_reporter,
+            global_phase_diagnosed_until: 0,
+            #[cfg(feature = "source-span")]
+            span_end: 0,
+            #[cfg(feature = "position")]
+            position_resolver: PositionResolver::new(input),
         }
     }
 
@@ -96,9 +145,85 @@ impl<TStack: Stack<StackEntry>> ParserInner<TStack> {
     pub fn has_just_entered_table(&mut self) -> bool {
         self.has_just_entered_table
     }
+
+    pub fn is_at_slot_beginning(&self) -> bool {
+        self.is_at_slot_beginning
+    }
+
+    pub fn is_inline_only(&self) -> bool {
+        self.is_inline_only
+    }
+
+    /// 记录预先查看时确认有效的槽位附加信息，`content` 是其内容所在的范围。
+    pub fn record_looked_ahead_attached_info(&mut self, content: Range<usize>) {
+        if self.looked_ahead_attached_infos.len() < MAX_LOOKED_AHEAD_ATTACHED_INFOS {
+            self.looked_ahead_attached_infos.push_back(content);
+        }
+    }
+
+    /// 若内容始于 `start` 的附加信息已在预先查看时确认有效，返回其内容的末尾。位于 `start`
+    /// 之前的记录已经不再需要，会被一并丢弃。
+    pub fn take_looked_ahead_attached_info_end(&mut self, start: usize) -> Option<usize> {
+        while self.looked_ahead_attached_infos.len() > 0 {
+            let content = self.looked_ahead_attached_infos.get(0).unwrap();
+            if content.start > start {
+                return None;
+            }
+            let content = self.looked_ahead_attached_infos.pop_front().unwrap();
+            if content.start == start {
+                return Some(content.end);
+            }
+        }
+        None
+    }
+
+    /// 已产出的事件所涉及的源码的末尾。未启用 `source-span` 特性时恒为 0。
+    pub fn span_end(&self) -> usize {
+        #[cfg(feature = "source-span")]
+        {
+            self.span_end
+        }
+        #[cfg(not(feature = "source-span"))]
+        {
+            0
+        }
+    }
+
+    /// 用于产出事件以外的途径消耗了源码（如闭合部分）的场合。
+    pub fn extend_span_end(&mut self, #[allow(unused_variables)] end: usize) {
+        #[cfg(feature = "source-span")]
+        {
+            self.span_end = self.span_end.max(end);
+        }
+    }
+
+    /// 见 [super::Parser::starting_at]。
+    pub fn set_start(
+        &mut self,
+        cursor: usize,
+        line: LineNumber,
+        #[allow(unused_variables)] last_block_id: BlockId,
+    ) {
+        self.cursor = cursor;
+        self.current_line = line;
+        #[cfg(feature = "block-id")]
+        {
+            self.block_id_generator = BlockIdGenerator::new_after(last_block_id);
+        }
+        #[cfg(feature = "source-span")]
+        {
+            self.span_end = cursor;
+        }
+        #[cfg(feature = "position")]
+        self.position_resolver.set_line_start(cursor, line.value());
+    }
+
+    pub fn set_diagnostic_reporter(&mut self, diagnostic_reporter: DiagnosticReporter<'a>) {
+        self.diagnostic_reporter = diagnostic_reporter;
+    }
 }
 
-impl<TStack: Stack<StackEntry>> CursorContext for ParserInner<TStack> {
+impl<TStack: Stack<StackEntry>> CursorContext for ParserInner<'_, TStack> {
     fn cursor(&self) -> usize {
         self.cursor
     }
@@ -121,12 +246,127 @@ impl<TStack: Stack<StackEntry>> CursorContext for ParserInner<TStack> {
             self.stack.reset_current_line_for_new_line();
         }
     }
+
+    fn report_diagnostic(&mut self, code: DiagnosticCode, range: Range<usize>) {
+        if matches!(
+            code,
+            DiagnosticCode::UnclosedVerbatimEscaping | DiagnosticCode::UnclosedComment
+        ) {
+            if range.start < self.global_phase_diagnosed_until {
+                return;
+            }
+            self.global_phase_diagnosed_until = range.start + 1;
+        }
+
+        self.diagnostic_reporter.report(code, range);
+    }
 }
-impl<TStack: Stack<StackEntry>> YieldContext for ParserInner<TStack> {
+impl<TStack: Stack<StackEntry>> YieldContext for ParserInner<'_, TStack> {
     /// `ev` 是属于 `Block` 分组的事件。
     #[must_use]
-    fn r#yield(&mut self, ev_to_yield: Event) -> Tym<1> {
+    fn r#yield(&mut self, #[allow(unused_mut)] mut ev_to_yield: Event) -> Tym<1> {
         self.has_just_entered_table = matches!(ev_to_yield, ev!(Block, EnterTable(..)));
+        self.is_at_slot_beginning = matches!(
+            ev_to_yield,
+            ev!(Block, EnterBlockQuote(..))
+                | ev!(Block, EnterListItem(..))
+                | ev!(Block, EnterDescriptionTerm(..))
+                | ev!(Block, EnterDescriptionDetails(..))
+                | ev!(Block, IndicateTableCaption(..))
+                | ev!(Block, IndicateTableHeaderCell(..))
+                | ev!(Block, IndicateTableDataCell(..))
+                | ev!(Block, IndicateCallNormalArgument(..))
+                | ev!(Block, IndicateParameterFillingFallback(..))
+                | ev!(
+                    Block,
+                    BlockAttachedInfo(AttachedInfo {
+                        target: AttachedInfoTarget::Slot,
+                        ..
+                    })
+                )
+        );
+
+        #[cfg(feature = "source-span")]
+        {
+            let end = match &ev_to_yield {
+                ev!(Block, __Unparsed(content)) | ev!(Block, Text(content)) => Some(content.end),
+                // 逐字转义的闭合部分刚被消耗。
+                ev!(Block, VerbatimEscaping(_)) => Some(self.cursor),
+                ev!(Block, BlockAttachedInfo(AttachedInfo { content, .. })) => {
+                    Some(content.end + "}".len())
+                }
+                ev => ev.span().map(|span| span.end()),
+            };
+            if let Some(end) = end {
+                self.extend_span_end(end);
+            }
+        }
+
+        #[cfg(feature = "position")]
+        if let ev!(
+            Block,
+            ExitBlock(ExitBlock {
+                span,
+                start,
+                end,
+                ..
+            })
+        )
+        | ev!(
+            Block,
+            ThematicBreak(ThematicBreak {
+                span,
+                start,
+                end,
+                ..
+            })
+        ) = &mut ev_to_yield
+        {
+            *start = self.position_resolver.position_at(span.start());
+            *end = self.position_resolver.position_at(span.end());
+        } else if let ev!(Block, EnterParagraph(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterHeading1(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterHeading2(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterHeading3(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterHeading4(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterHeading5(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterHeading6(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterBlockQuote(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterOrderedList(OrderedList { span, position, .. }))
+        | ev!(
+            Block,
+            EnterUnorderedList(BlockWithId { span, position, .. })
+        )
+        | ev!(Block, EnterListItem(ListItem { span, position, .. }))
+        | ev!(
+            Block,
+            EnterDescriptionList(BlockWithId { span, position, .. })
+        )
+        | ev!(
+            Block,
+            EnterDescriptionTerm(BlockWithId { span, position, .. })
+        )
+        | ev!(
+            Block,
+            EnterDescriptionDetails(BlockWithId { span, position, .. })
+        )
+        | ev!(Block, EnterCodeBlock(BlockWithId { span, position, .. }))
+        | ev!(Block, EnterTable(BlockWithId { span, position, .. }))
+        | ev!(
+            Block,
+            EnterCallOnTemplate(Call::Block { span, position, .. })
+        )
+        | ev!(
+            Block,
+            EnterCallOnExtension(Call::Block { span, position, .. })
+        )
+        | ev!(
+            Block,
+            EnterParameterFilling(Call::Block { span, position, .. })
+        ) = &mut ev_to_yield
+        {
+            *position = self.position_resolver.position_at(span.start());
+        }
 
         self.to_yield.push_back(ev_to_yield);
 
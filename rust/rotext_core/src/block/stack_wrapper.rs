@@ -2,8 +2,8 @@ use core::ops::Range;
 
 use crate::{
     Event,
-    events::{Call, ExitBlock, NewLine, ev},
-    types::{BlockId, LineNumber, Stack},
+    events::{BlockWithId, Call, ExitBlock, ListItem, NewLine, ev},
+    types::{BlockId, LineNumber, Position, SourceSpan, Stack},
 };
 
 use super::parser_inner::ParserInnerShallowSnapshot;
@@ -98,6 +98,33 @@ impl<TStack: Stack<StackEntry>> StackWrapper<TStack> {
         matches!(self.stack.as_slice().last(), Some(StackEntry::Call(_)))
     }
 
+    /// 栈中最内层的 call entry 是否是参数填充。
+    pub fn innermost_call_is_parameter_filling(&self) -> bool {
+        self.stack
+            .as_slice()
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                StackEntry::Call(call) => Some(call.is_parameter_filling),
+                _ => None,
+            })
+            .unwrap_or(false)
+    }
+
+    /// 栈中最内层的表格或 call entry 是否是参数填充。若是，其中的 `||` 不起分隔作用。
+    pub fn innermost_table_or_call_is_parameter_filling(&self) -> bool {
+        self.stack
+            .as_slice()
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                StackEntry::Table(_) => Some(false),
+                StackEntry::Call(call) => Some(call.is_parameter_filling),
+                _ => None,
+            })
+            .unwrap_or(false)
+    }
+
     /// 向栈中推入一个 item-like entry。
     ///
     /// 调用者应保证 `self.leaf` 为 `None`。
@@ -209,20 +236,33 @@ pub struct StackEntryItemLike {
     pub meta: Meta,
 
     pub r#type: GeneralItemLike,
+    /// 仅对 [GeneralItemLike::LI] 有意义，见 [crate::events::ListItem::checked]。
+    pub checked: Option<bool>,
 }
 impl StackEntryItemLike {
     /// 返回的事件属于 `Block` 分组。
     pub fn make_enter_event(&self) -> Event {
         match self.r#type {
-            GeneralItemLike::LI => ev!(Block, EnterListItem(self.meta.id.into())),
-            GeneralItemLike::DT => ev!(Block, EnterDescriptionTerm(self.meta.id.into())),
-            GeneralItemLike::DD => ev!(Block, EnterDescriptionDetails(self.meta.id.into())),
+            GeneralItemLike::LI => ev!(
+                Block,
+                EnterListItem(ListItem {
+                    id: self.meta.id,
+                    checked: self.checked,
+                    span: self.meta.opening,
+                    position: Position::new_unresolved(),
+                })
+            ),
+            GeneralItemLike::DT => ev!(Block, EnterDescriptionTerm(self.meta.make_block_with_id())),
+            GeneralItemLike::DD => ev!(
+                Block,
+                EnterDescriptionDetails(self.meta.make_block_with_id())
+            ),
         }
     }
 
-    /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
@@ -235,36 +275,49 @@ impl StackEntryItemLikeContainer {
     /// 返回的事件属于 `Block` 分组。
     pub fn make_enter_event(&self) -> Event {
         match self.r#type {
-            ItemLikeContainer::BlockQuote => ev!(Block, EnterBlockQuote(self.meta.id.into())),
-            ItemLikeContainer::OL => ev!(Block, EnterOrderedList(self.meta.id.into())),
-            ItemLikeContainer::UL => ev!(Block, EnterUnorderedList(self.meta.id.into())),
-            ItemLikeContainer::DL => ev!(Block, EnterDescriptionList(self.meta.id.into())),
+            ItemLikeContainer::BlockQuote => {
+                ev!(Block, EnterBlockQuote(self.meta.make_block_with_id()))
+            }
+            ItemLikeContainer::OL => ev!(
+                Block,
+                EnterOrderedList(self.meta.make_block_with_id().into())
+            ),
+            ItemLikeContainer::UL => ev!(Block, EnterUnorderedList(self.meta.make_block_with_id())),
+            ItemLikeContainer::DL => {
+                ev!(Block, EnterDescriptionList(self.meta.make_block_with_id()))
+            }
         }
     }
 
-    /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
 pub struct StackEntryTable {
     pub meta: Meta,
+    /// 开启部分（`{|`）开始的位置，用于报告诊断。
+    pub opening_start: usize,
 }
 impl StackEntryTable {
     /// 返回的事件属于 `Block` 分组。
     pub fn make_enter_event(&self) -> Event {
-        ev!(Block, EnterTable(self.meta.id.into()))
+        ev!(Block, EnterTable(self.meta.make_block_with_id()))
     }
 
-    /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
+/// 除了调用以外，参数填充（`{{{…}}}`）也以此表示。
 pub struct StackEntryCall {
     pub meta: Meta,
+    pub is_parameter_filling: bool,
+    /// 开启部分（`{{` 或 `{{{`）开始的位置，用于报告诊断。
+    pub opening_start: usize,
 }
 impl StackEntryCall {
     /// 返回的事件属于 `Block` 分组。
@@ -272,6 +325,8 @@ impl StackEntryCall {
         let call = Call::Block {
             id: self.meta.id,
             name,
+            span: self.meta.opening,
+            position: Position::new_unresolved(),
         };
         if is_extension {
             ev!(Block, EnterCallOnExtension(call))
@@ -281,8 +336,22 @@ impl StackEntryCall {
     }
 
     /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    pub fn make_parameter_filling_enter_event(&self, name: Range<usize>) -> Event {
+        debug_assert!(self.is_parameter_filling);
+        ev!(
+            Block,
+            EnterParameterFilling(Call::Block {
+                id: self.meta.id,
+                name,
+                span: self.meta.opening,
+                position: Position::new_unresolved(),
+            })
+        )
+    }
+
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
@@ -290,20 +359,37 @@ impl StackEntryCall {
 pub struct Meta {
     id: BlockId,
     line_start: LineNumber,
+    /// 开启部分所在的范围。
+    opening: SourceSpan,
 }
 impl Meta {
-    pub fn new(id: BlockId, line_start: LineNumber) -> Self {
-        Self { id, line_start }
+    pub fn new(id: BlockId, line_start: LineNumber, opening: SourceSpan) -> Self {
+        Self {
+            id,
+            line_start,
+            opening,
+        }
+    }
+
+    fn make_block_with_id(&self) -> BlockWithId {
+        BlockWithId {
+            id: self.id,
+            span: self.opening,
+            position: Position::new_unresolved(),
+        }
     }
 
     /// 返回的事件属于 `Block` 分组。
-    fn make_exit_event(self, line_end: LineNumber) -> Event {
+    fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
         ev!(
             Block,
             ExitBlock(ExitBlock {
                 id: self.id,
                 start_line: self.line_start,
                 end_line: line_end,
+                span: self.opening.with_end(span_end),
+                start: Position::new_unresolved(),
+                end: Position::new_unresolved(),
             })
         )
     }
@@ -382,12 +468,12 @@ pub struct LeafParagraph {
 impl LeafParagraph {
     /// 返回的事件属于 `Block` 分组。
     pub fn make_enter_event(&self) -> Event {
-        ev!(Block, EnterParagraph(self.meta.id.into()))
+        ev!(Block, EnterParagraph(self.meta.make_block_with_id()))
     }
 
-    /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
@@ -403,19 +489,19 @@ impl LeafHeading {
     /// 返回的事件属于 `Block` 分组。
     pub fn make_enter_event(&self) -> Event {
         match self.level {
-            1 => ev!(Block, EnterHeading1(self.meta.id.into())),
-            2 => ev!(Block, EnterHeading2(self.meta.id.into())),
-            3 => ev!(Block, EnterHeading3(self.meta.id.into())),
-            4 => ev!(Block, EnterHeading4(self.meta.id.into())),
-            5 => ev!(Block, EnterHeading5(self.meta.id.into())),
-            6 => ev!(Block, EnterHeading6(self.meta.id.into())),
+            1 => ev!(Block, EnterHeading1(self.meta.make_block_with_id())),
+            2 => ev!(Block, EnterHeading2(self.meta.make_block_with_id())),
+            3 => ev!(Block, EnterHeading3(self.meta.make_block_with_id())),
+            4 => ev!(Block, EnterHeading4(self.meta.make_block_with_id())),
+            5 => ev!(Block, EnterHeading5(self.meta.make_block_with_id())),
+            6 => ev!(Block, EnterHeading6(self.meta.make_block_with_id())),
             _ => unreachable!(),
         }
     }
 
-    /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
@@ -436,12 +522,12 @@ pub enum LeafCodeBlockState {
 impl LeafCodeBlock {
     /// 返回的事件属于 `Block` 分组。
     pub fn make_enter_event(&self) -> Event {
-        ev!(Block, EnterCodeBlock(self.meta.id.into()))
+        ev!(Block, EnterCodeBlock(self.meta.make_block_with_id()))
     }
 
-    /// 返回的事件属于 `Block` 分组。
-    pub fn make_exit_event(self, line_end: LineNumber) -> Event {
-        self.meta.make_exit_event(line_end)
+    /// 返回的事件属于 `Block` 分组。`span_end` 是整个元素的末尾。
+    pub fn make_exit_event(self, line_end: LineNumber, span_end: usize) -> Event {
+        self.meta.make_exit_event(line_end, span_end)
     }
 }
 
@@ -67,6 +67,8 @@ pub enum ExitingAndThen {
         item_like: StackEntryItemLike,
     },
     ExpectBracedOpening,
+    /// 当前行余下的部分，在附加信息之后仍可以是新的列表之类的构建。
+    ExpectNewItemLikeOpening,
     /// 包含属于 `Block` 分组的事件。
     YieldAndExpectBracedOpening(Event),
     PushLeafCallArgumentBeginningAndExpectBracedOpening,
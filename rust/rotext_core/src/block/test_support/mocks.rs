@@ -1,4 +1,6 @@
-use crate::{block::types::CursorContext, types::LineNumber};
+use core::ops::Range;
+
+use crate::{block::types::CursorContext, diagnostics::DiagnosticCode, types::LineNumber};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct MockCursorContext {
@@ -25,4 +27,6 @@ impl CursorContext for MockCursorContext {
     fn increase_current_line(&mut self, _is_significant: bool) {
         self.current_line.increase()
     }
+
+    fn report_diagnostic(&mut self, _code: DiagnosticCode, _range: Range<usize>) {}
 }
@@ -1,5 +1,8 @@
+use core::ops::Range;
+
 use crate::{
     Event,
+    diagnostics::DiagnosticCode,
     types::{LineNumber, Tym},
 };
 
@@ -14,6 +17,9 @@ pub trait CursorContext {
     /// 字转义中，换行不会有如此影响，因此应传入 `false`；而通常情况下的换行会有
     /// 如此影响，因此应传入 `true`。
     fn increase_current_line(&mut self, is_significant: bool);
+
+    /// 报告诊断，见 [crate::diagnostics]。
+    fn report_diagnostic(&mut self, code: DiagnosticCode, range: Range<usize>);
 }
 pub trait YieldContext {
     /// `ev` 是属于 `Block` 分组的事件。
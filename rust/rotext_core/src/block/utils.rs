@@ -19,6 +19,11 @@ impl BlockIdGenerator {
         Self(0)
     }
 
+    /// 之后产出的 ID 从 `last` 之后开始。
+    pub fn new_after(last: BlockId) -> Self {
+        Self(last.value())
+    }
+
     #[cfg(feature = "block-id")]
     pub fn pop(&mut self) -> BlockId {
         self.0 += 1;
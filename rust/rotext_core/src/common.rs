@@ -18,3 +18,15 @@ pub fn is_valid_character_in_name(char: u8) -> bool {
 pub fn is_valid_character_in_argument_name(char: u8) -> bool {
     !is_markup(char)
 }
+
+/// 附加信息中的类名与键名允许的字符。只允许 ASCII 字母、数字、`-` 与 `_`，以保证其可以
+/// 原样作为 HTML 的类名与属性名输出。
+pub fn is_valid_character_in_attached_info_name(char: u8) -> bool {
+    matches!(char, m!('-') | m!('_')) || char.is_ascii_alphanumeric()
+}
+
+/// 赋值骰子表达式中变量名允许的字符。这是对 “Rotext 变量标识符” 的宽松近似：除了
+/// 非标记字符外，还允许 `_`、`.` 与 `@`。
+pub fn is_valid_character_in_variable_name(char: u8) -> bool {
+    matches!(char, m!('_') | m!('.') | m!('@')) || !(is_markup(char) || char.is_ascii_whitespace())
+}
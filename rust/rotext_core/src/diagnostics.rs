@@ -0,0 +1,106 @@
+use core::ops::Range;
+
+#[cfg(feature = "line-number")]
+use crate::internal_utils::position_resolver::PositionResolver;
+use crate::types::{LineNumber, Position};
+
+/// 解析时发现的问题。这些问题不会中断解析，但很可能出于书写失误，可供编辑器等提示用户。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    /// 问题所在的范围（以字节计），为出问题的那个构造的开启部分。
+    pub range: Range<usize>,
+    /// `range` 开始处所在的行。
+    pub line: LineNumber,
+    /// `range` 开始处所在的位置。
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticCode {
+    /// 逐字转义（`` <` ``）直到输入结束都没有闭合，被强制闭合。
+    UnclosedVerbatimEscaping,
+    /// 注释（`<%`）直到输入结束都没有闭合。
+    UnclosedComment,
+    /// 行内元素（如 `[*`、`[[…|`、`[{…|`）没有闭合，在所在的段落、标题或外层元素结束时被强
+    /// 制闭合。
+    UnclosedInlineElement,
+    /// 块级调用（`{{`）或参数填充（`{{{`）没有闭合，在外层结构或文档结束时被强制闭合。
+    UnclosedBlockCall,
+    /// 表格（`{|`）没有闭合，在外层结构或文档结束时被强制闭合。
+    UnclosedTable,
+    /// `[[` 未能构成内部链接，被视为文本。
+    WikiLinkOpeningAsText,
+    /// 位于块级的 `{{` 未能构成调用，被视为文本。
+    BlockCallOpeningAsText,
+}
+
+impl DiagnosticCode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticCode::UnclosedVerbatimEscaping => "UnclosedVerbatimEscaping",
+            DiagnosticCode::UnclosedComment => "UnclosedComment",
+            DiagnosticCode::UnclosedInlineElement => "UnclosedInlineElement",
+            DiagnosticCode::UnclosedBlockCall => "UnclosedBlockCall",
+            DiagnosticCode::UnclosedTable => "UnclosedTable",
+            DiagnosticCode::WikiLinkOpeningAsText => "WikiLinkOpeningAsText",
+            DiagnosticCode::BlockCallOpeningAsText => "BlockCallOpeningAsText",
+        }
+    }
+}
+
+/// 接收诊断的函数。诊断大体按照被发现的先后报告，不保证按照所在位置排序。
+pub type DiagnosticSink<'a> = &'a dyn Fn(Diagnostic);
+
+/// 向 [DiagnosticSink] 报告诊断，并负责计算诊断所在的行与位置。
+pub(crate) struct DiagnosticReporter<'a> {
+    sink: Option<DiagnosticSink<'a>>,
+    #[cfg(feature = "line-number")]
+    position_resolver: PositionResolver<'a>,
+}
+
+impl<'a> DiagnosticReporter<'a> {
+    pub fn new(
+        #[allow(unused_variables)] input: &'a [u8],
+        sink: Option<DiagnosticSink<'a>>,
+    ) -> Self {
+        Self {
+            sink,
+            #[cfg(feature = "line-number")]
+            position_resolver: PositionResolver::new(input),
+        }
+    }
+
+    pub fn report(&mut self, code: DiagnosticCode, range: Range<usize>) {
+        let Some(sink) = self.sink else {
+            return;
+        };
+
+        let line = self.line_at(range.start);
+        let position = self.position_at(range.start);
+        sink(Diagnostic {
+            code,
+            range,
+            line,
+            position,
+        });
+    }
+
+    #[cfg(feature = "line-number")]
+    fn line_at(&mut self, position: usize) -> LineNumber {
+        LineNumber::new(self.position_resolver.line_at(position).0)
+    }
+    #[cfg(not(feature = "line-number"))]
+    fn line_at(&mut self, _position: usize) -> LineNumber {
+        LineNumber::new()
+    }
+
+    #[cfg(feature = "position")]
+    fn position_at(&mut self, position: usize) -> Position {
+        self.position_resolver.position_at(position)
+    }
+    #[cfg(not(feature = "position"))]
+    fn position_at(&mut self, _position: usize) -> Position {
+        Position::new()
+    }
+}
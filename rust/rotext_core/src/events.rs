@@ -1,6 +1,9 @@
-use core::ops::Range;
+use core::{num::NonZeroUsize, ops::Range};
 
-use crate::types::{BlockId, LineNumber};
+use crate::{
+    attached_info,
+    types::{BlockId, LineNumber, Position, SourceSpan},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -41,18 +44,24 @@ pub enum EventType {
     IndicateTableDataCell = 34,
     IndicateCallNormalArgument = 43,
     IndicateCallVerbatimArgument = 44,
+    EnterParameterFilling = 45,
+    IndicateParameterFillingFallback = 46,
+    BlockAttachedInfo = 51,
     ExitBlock = 99,
 
     // 在行内阶段产出。
     RefLink = 101,
-    Dicexp = 102,
+    ExternalLink = 103,
     EnterCodeSpan = 111,
     EnterEmphasis = 116,
     EnterStrong = 112,
     EnterStrikethrough = 113,
     EnterRuby = 114,
     EnterRubyText = 115,
+    EnterDicexp = 117,
+    EnterAssigningDicexp = 118,
     EnterWikiLink = 121,
+    InlineAttachedInfo = 131,
     ExitInline = 199,
 }
 
@@ -132,13 +141,13 @@ pub enum Event {
     EnterBlockQuote(BlockWithId) = EventType::EnterBlockQuote as u8,
     /// 进入有序列表
     #[groups(Block | Blend)]
-    EnterOrderedList(BlockWithId) = EventType::EnterOrderedList as u8,
+    EnterOrderedList(OrderedList) = EventType::EnterOrderedList as u8,
     /// 进入无序列表
     #[groups(Block | Blend)]
     EnterUnorderedList(BlockWithId) = EventType::EnterUnorderedList as u8,
     /// 进入列表项
     #[groups(Block | Blend)]
-    EnterListItem(BlockWithId) = EventType::EnterListItem as u8,
+    EnterListItem(ListItem) = EventType::EnterListItem as u8,
     /// 进入描述列表
     #[groups(Block | Blend)]
     EnterDescriptionList(BlockWithId) = EventType::EnterDescriptionList as u8,
@@ -160,29 +169,39 @@ pub enum Event {
     /// 进入调用扩展。
     #[groups(Block | Inline | Blend)]
     EnterCallOnExtension(Call) = EventType::EnterCallOnExtension as u8,
+    /// 进入参数填充。所含名称为参数的名称。
+    #[groups(Block | Inline | Blend)]
+    EnterParameterFilling(Call) = EventType::EnterParameterFilling as u8,
 
-    /// 指示到达代码块的代码部分。
+    /// 指示到达代码块的代码部分。所含范围为空，位于代码部分的开头。
     #[groups(Block | Blend)]
-    IndicateCodeBlockCode = EventType::IndicateCodeBlockCode as u8,
+    IndicateCodeBlockCode(SourceSpan) = EventType::IndicateCodeBlockCode as u8,
     /// 指示到达表格标题。
     #[groups(Block | Blend)]
-    IndicateTableCaption = EventType::IndicateTableCaption as u8,
+    IndicateTableCaption(SourceSpan) = EventType::IndicateTableCaption as u8,
     /// 指示到达（新）表格行。
     #[groups(Block | Blend)]
-    IndicateTableRow = EventType::IndicateTableRow as u8,
+    IndicateTableRow(SourceSpan) = EventType::IndicateTableRow as u8,
     /// 指示到达（新）表格头部单元格。
     #[groups(Block | Blend)]
-    IndicateTableHeaderCell = EventType::IndicateTableHeaderCell as u8,
+    IndicateTableHeaderCell(TableCell) = EventType::IndicateTableHeaderCell as u8,
     /// 指示到达（新）表格数据单元格。
     #[groups(Block | Blend)]
-    IndicateTableDataCell = EventType::IndicateTableDataCell as u8,
+    IndicateTableDataCell(TableCell) = EventType::IndicateTableDataCell as u8,
     /// 指示到达（新）调用的一般（非逐字）参数。
     #[groups(Block | Inline | Blend)]
-    IndicateCallNormalArgument(Option<Range<usize>>) = EventType::IndicateCallNormalArgument as u8,
+    IndicateCallNormalArgument(CallArgument) = EventType::IndicateCallNormalArgument as u8,
     /// 指示到达（新）调用的逐字参数。
     #[groups(Block | Inline | Blend)]
-    IndicateCallVerbatimArgument(Option<Range<usize>>) =
-        EventType::IndicateCallVerbatimArgument as u8,
+    IndicateCallVerbatimArgument(CallArgument) = EventType::IndicateCallVerbatimArgument as u8,
+    /// 指示到达参数填充的 fallback。
+    #[groups(Block | Inline | Blend)]
+    IndicateParameterFillingFallback(SourceSpan) =
+        EventType::IndicateParameterFillingFallback as u8,
+
+    /// 块级附加信息。
+    #[groups(Block | Blend)]
+    BlockAttachedInfo(AttachedInfo) = EventType::BlockAttachedInfo as u8,
 
     /// 退出一层块级的 “进入…”。
     #[groups(Block | Blend)]
@@ -191,36 +210,50 @@ pub enum Event {
     /// 引用链接。
     #[groups(Inline | Blend)]
     RefLink(Range<usize>) = EventType::RefLink as u8,
-    /// Dicexp。
+    /// 外部链接。所含范围为自动识别出的 URL。
     #[groups(Inline | Blend)]
-    Dicexp(Range<usize>) = EventType::Dicexp as u8,
+    ExternalLink(Range<usize>) = EventType::ExternalLink as u8,
 
     /// 进入行内代码。
     #[groups(Inline | Blend)]
-    EnterCodeSpan = EventType::EnterCodeSpan as u8,
+    EnterCodeSpan(SourceSpan) = EventType::EnterCodeSpan as u8,
     /// 进入字体强调（`em`）。
     #[groups(Inline | Blend)]
-    EnterEmphasis = EventType::EnterEmphasis as u8,
+    EnterEmphasis(SourceSpan) = EventType::EnterEmphasis as u8,
     /// 进入加粗强调（`strong`）。
     #[groups(Inline | Blend)]
-    EnterStrong = EventType::EnterStrong as u8,
+    EnterStrong(SourceSpan) = EventType::EnterStrong as u8,
     /// 进入删除线。
     #[groups(Inline | Blend)]
-    EnterStrikethrough = EventType::EnterStrikethrough as u8,
+    EnterStrikethrough(SourceSpan) = EventType::EnterStrikethrough as u8,
     /// 进入注音。
     #[groups(Inline | Blend)]
-    EnterRuby = EventType::EnterRuby as u8,
+    EnterRuby(SourceSpan) = EventType::EnterRuby as u8,
     /// 进入注音文本。
     #[groups(Inline | Blend)]
-    EnterRubyText = EventType::EnterRubyText as u8,
+    EnterRubyText(SourceSpan) = EventType::EnterRubyText as u8,
+    /// 进入 Dicexp。其内容由 `Text` 与 `NewLine` 组成。
+    ///
+    /// 不兼容的变更：本事件取代了先前的 `Dicexp` 事件。先前 `Dicexp` 所含的范围即为整个表
+    /// 达式，如今表达式由本事件与对应的 `ExitInline` 之间的 `Text` 与 `NewLine` 组成，以支
+    /// 持跨行的表达式。
+    #[groups(Inline | Blend)]
+    EnterDicexp(SourceSpan) = EventType::EnterDicexp as u8,
+    /// 进入赋值骰子表达式。所含范围为被赋值的变量名，其内容同 `EnterDicexp`。
+    #[groups(Inline | Blend)]
+    EnterAssigningDicexp(AssigningDicexp) = EventType::EnterAssigningDicexp as u8,
 
     // 进入Wiki链接。
     #[groups(Inline | Blend)]
-    EnterWikiLink(Range<usize>) = EventType::EnterWikiLink as u8,
+    EnterWikiLink(WikiLink) = EventType::EnterWikiLink as u8,
+
+    /// 行内附加信息。
+    #[groups(Inline | Blend)]
+    InlineAttachedInfo(AttachedInfo) = EventType::InlineAttachedInfo as u8,
 
     /// 退出一层行内的 “进入…”。
     #[groups(Inline | Blend)]
-    ExitInline = EventType::ExitInline as u8,
+    ExitInline(SourceSpan) = EventType::ExitInline as u8,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -238,10 +271,74 @@ pub struct NewLine {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BlockWithId {
     pub id: BlockId,
+    /// 开启部分所在的范围。各种列表本身没有开启部分，范围同其第一个项目的指示标记；段落没
+    /// 有开启部分，范围为空，位于其开头。整个元素所在的范围见对应的 [ExitBlock::span]。
+    pub span: SourceSpan,
+    /// `span` 开头所在的位置。
+    pub position: Position,
 }
-impl From<BlockId> for BlockWithId {
-    fn from(value: BlockId) -> Self {
-        Self { id: value }
+
+/// 列表项的数据。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListItem {
+    pub id: BlockId,
+    /// 为 `Some` 时，该列表项是任务列表项（以 `[ ]` 或 `[x]` 开头），值为其是否已勾选。
+    pub checked: Option<bool>,
+    /// 列表项指示标记（包括任务列表项的标记）所在的范围。
+    pub span: SourceSpan,
+    /// `span` 开头所在的位置。
+    pub position: Position,
+}
+
+/// 有序列表的数据。除 `id` 外，均来自紧随其第一个列表项指示标记的槽位附加信息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedList {
+    pub id: BlockId,
+    /// 第一项的序号（`start`）。值不是非负整数时视为未指定。
+    pub start: Option<usize>,
+    /// 序号的样式（`type`）。
+    pub numbering: Option<OrderedListNumbering>,
+    /// 同其第一个列表项指示标记所在的范围。
+    pub span: SourceSpan,
+    /// `span` 开头所在的位置。
+    pub position: Position,
+}
+impl From<BlockWithId> for OrderedList {
+    fn from(value: BlockWithId) -> Self {
+        Self {
+            id: value.id,
+            start: None,
+            numbering: None,
+            span: value.span,
+            position: value.position,
+        }
+    }
+}
+
+/// 有序列表序号的样式，与 HTML 中 `ol` 元素的 `type` 属性的取值一一对应。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderedListNumbering {
+    /// `1`：阿拉伯数字。
+    Decimal,
+    /// `a`：小写字母。
+    LowerAlpha,
+    /// `A`：大写字母。
+    UpperAlpha,
+    /// `i`：小写罗马数字。
+    LowerRoman,
+    /// `I`：大写罗马数字。
+    UpperRoman,
+}
+impl OrderedListNumbering {
+    /// 返回对应的 HTML `type` 属性的值。
+    pub fn as_type_attribute_value(&self) -> &'static [u8] {
+        match self {
+            OrderedListNumbering::Decimal => b"1",
+            OrderedListNumbering::LowerAlpha => b"a",
+            OrderedListNumbering::UpperAlpha => b"A",
+            OrderedListNumbering::LowerRoman => b"i",
+            OrderedListNumbering::UpperRoman => b"I",
+        }
     }
 }
 
@@ -249,19 +346,111 @@ impl From<BlockId> for BlockWithId {
 pub struct ThematicBreak {
     pub id: BlockId,
     pub line: LineNumber,
+    pub span: SourceSpan,
+    /// `span` 开头所在的位置。
+    pub start: Position,
+    /// `span` 末尾所在的位置。
+    pub end: Position,
 }
 
+/// 表格单元格的数据。除 `span` 外，均来自紧随单元格指示标记的槽位附加信息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableCell {
+    /// 单元格横跨的列数（`colspan`）。值不是不超过 [Self::MAX_COLSPAN] 的正整数时视为
+    /// 未指定。
+    pub colspan: Option<NonZeroUsize>,
+    /// 单元格纵跨的行数（`rowspan`）。值不是不超过 [Self::MAX_ROWSPAN] 的正整数时视为
+    /// 未指定。
+    pub rowspan: Option<NonZeroUsize>,
+    /// 单元格指示标记所在的范围。
+    pub span: SourceSpan,
+}
+impl TableCell {
+    /// HTML 中 `colspan` 的最大值。
+    pub const MAX_COLSPAN: usize = 1000;
+    /// HTML 中 `rowspan` 的最大值。
+    pub const MAX_ROWSPAN: usize = 65534;
+
+    pub fn new(span: SourceSpan) -> Self {
+        Self {
+            colspan: None,
+            rowspan: None,
+            span,
+        }
+    }
+}
+
+/// `span` 为开启部分所在的范围，止于名称的末尾。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Call {
-    Block { id: BlockId, name: Range<usize> },
-    Inline { name: Range<usize> },
+    Block {
+        id: BlockId,
+        name: Range<usize>,
+        span: SourceSpan,
+        /// `span` 开头所在的位置。
+        position: Position,
+    },
+    Inline {
+        name: Range<usize>,
+        span: SourceSpan,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallArgument {
+    /// 参数的名称。为 `None` 时，该参数没有名称。
+    pub name: Option<Range<usize>>,
+    /// 参数指示标记所在的范围，包括名称以及其后的 `=`。
+    pub span: SourceSpan,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssigningDicexp {
+    /// 被赋值的变量名。
+    pub name: Range<usize>,
+    pub span: SourceSpan,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikiLink {
+    pub address: Range<usize>,
+    /// 开启部分所在的范围，包括地址以及其后的 `|` 或闭合部分。
+    pub span: SourceSpan,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachedInfo {
+    pub target: AttachedInfoTarget,
+    /// 附加信息序列所在的范围，不包含开启部分与闭合部分。
+    pub content: Range<usize>,
+}
+impl AttachedInfo {
+    pub fn items<'a>(&self, input: &'a [u8]) -> attached_info::Items<'a> {
+        attached_info::Items::new(input, self.content.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachedInfoTarget {
+    /// 附加到位于其后的那个元素之上（`{&>` 或 `[&>`）。
+    Following,
+    /// 附加到其所处的槽位之上（`{&<` 或 `[&<`）。
+    Slot,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExitBlock {
     pub id: BlockId,
     pub start_line: LineNumber,
+    /// 解析器确定元素结束时所在的行。元素之后的空行等也可能计入其中，因此可能晚于 `end` 所
+    /// 在的行（如对于 `a\n\nb` 中的第一个段落，`end_line` 为 3，`end` 则位于第 1 行）。需要
+    /// 元素内容实际止于哪一行时，应使用 `end`。
     pub end_line: LineNumber,
+    pub span: SourceSpan,
+    /// `span` 开头所在的位置。
+    pub start: Position,
+    /// `span` 末尾所在的位置。
+    pub end: Position,
 }
 
 impl Event {
@@ -284,13 +473,26 @@ impl Event {
             | Event::Text(content)
             | Event::EnterCallOnTemplate(Call::Block { name: content, .. })
             | Event::EnterCallOnExtension(Call::Block { name: content, .. })
-            | Event::EnterCallOnTemplate(Call::Inline { name: content })
-            | Event::EnterCallOnExtension(Call::Inline { name: content })
-            | Event::IndicateCallNormalArgument(Some(content))
-            | Event::IndicateCallVerbatimArgument(Some(content))
+            | Event::EnterCallOnTemplate(Call::Inline { name: content, .. })
+            | Event::EnterCallOnExtension(Call::Inline { name: content, .. })
+            | Event::EnterParameterFilling(Call::Block { name: content, .. })
+            | Event::EnterParameterFilling(Call::Inline { name: content, .. })
+            | Event::IndicateCallNormalArgument(CallArgument {
+                name: Some(content),
+                ..
+            })
+            | Event::IndicateCallVerbatimArgument(CallArgument {
+                name: Some(content),
+                ..
+            })
+            | Event::BlockAttachedInfo(AttachedInfo { content, .. })
+            | Event::InlineAttachedInfo(AttachedInfo { content, .. })
             | Event::RefLink(content)
-            | Event::Dicexp(content)
-            | Event::EnterWikiLink(content) => &input[content.clone()],
+            | Event::ExternalLink(content)
+            | Event::EnterAssigningDicexp(AssigningDicexp { name: content, .. })
+            | Event::EnterWikiLink(WikiLink {
+                address: content, ..
+            }) => &input[content.clone()],
             Event::NewLine(_)
             | Event::ThematicBreak(_)
             | Event::EnterParagraph(_)
@@ -309,21 +511,23 @@ impl Event {
             | Event::EnterDescriptionDetails(_)
             | Event::EnterCodeBlock(_)
             | Event::EnterTable(_)
-            | Event::IndicateCodeBlockCode
-            | Event::IndicateTableCaption
-            | Event::IndicateTableRow
-            | Event::IndicateTableHeaderCell
-            | Event::IndicateTableDataCell
-            | Event::IndicateCallNormalArgument(None)
-            | Event::IndicateCallVerbatimArgument(None)
+            | Event::IndicateCodeBlockCode(_)
+            | Event::IndicateTableCaption(_)
+            | Event::IndicateTableRow(_)
+            | Event::IndicateTableHeaderCell(_)
+            | Event::IndicateTableDataCell(_)
+            | Event::IndicateCallNormalArgument(CallArgument { name: None, .. })
+            | Event::IndicateCallVerbatimArgument(CallArgument { name: None, .. })
+            | Event::IndicateParameterFillingFallback(_)
             | Event::ExitBlock(_)
-            | Event::EnterCodeSpan
-            | Event::EnterEmphasis
-            | Event::EnterStrong
-            | Event::EnterStrikethrough
-            | Event::EnterRuby
-            | Event::EnterRubyText
-            | Event::ExitInline => return None,
+            | Event::EnterCodeSpan(_)
+            | Event::EnterEmphasis(_)
+            | Event::EnterStrong(_)
+            | Event::EnterStrikethrough(_)
+            | Event::EnterRuby(_)
+            | Event::EnterRubyText(_)
+            | Event::EnterDicexp(_)
+            | Event::ExitInline(_) => return None,
         };
 
         Some(result)
@@ -358,6 +562,63 @@ impl Event {
 }
 
 impl Event {
+    /// 返回进入、指示与退出事件（以及分割线）所含的范围，见 [SourceSpan]。对于其他事件，
+    /// 返回 `None`。
+    pub fn span(&self) -> Option<SourceSpan> {
+        let span = match self {
+            Event::ThematicBreak(ThematicBreak { span, .. })
+            | Event::EnterParagraph(BlockWithId { span, .. })
+            | Event::EnterHeading1(BlockWithId { span, .. })
+            | Event::EnterHeading2(BlockWithId { span, .. })
+            | Event::EnterHeading3(BlockWithId { span, .. })
+            | Event::EnterHeading4(BlockWithId { span, .. })
+            | Event::EnterHeading5(BlockWithId { span, .. })
+            | Event::EnterHeading6(BlockWithId { span, .. })
+            | Event::EnterBlockQuote(BlockWithId { span, .. })
+            | Event::EnterOrderedList(OrderedList { span, .. })
+            | Event::EnterUnorderedList(BlockWithId { span, .. })
+            | Event::EnterListItem(ListItem { span, .. })
+            | Event::EnterDescriptionList(BlockWithId { span, .. })
+            | Event::EnterDescriptionTerm(BlockWithId { span, .. })
+            | Event::EnterDescriptionDetails(BlockWithId { span, .. })
+            | Event::EnterCodeBlock(BlockWithId { span, .. })
+            | Event::EnterTable(BlockWithId { span, .. })
+            | Event::EnterCallOnTemplate(Call::Block { span, .. } | Call::Inline { span, .. })
+            | Event::EnterCallOnExtension(Call::Block { span, .. } | Call::Inline { span, .. })
+            | Event::EnterParameterFilling(Call::Block { span, .. } | Call::Inline { span, .. })
+            | Event::IndicateCodeBlockCode(span)
+            | Event::IndicateTableCaption(span)
+            | Event::IndicateTableRow(span)
+            | Event::IndicateTableHeaderCell(TableCell { span, .. })
+            | Event::IndicateTableDataCell(TableCell { span, .. })
+            | Event::IndicateCallNormalArgument(CallArgument { span, .. })
+            | Event::IndicateCallVerbatimArgument(CallArgument { span, .. })
+            | Event::IndicateParameterFillingFallback(span)
+            | Event::ExitBlock(ExitBlock { span, .. })
+            | Event::EnterCodeSpan(span)
+            | Event::EnterEmphasis(span)
+            | Event::EnterStrong(span)
+            | Event::EnterStrikethrough(span)
+            | Event::EnterRuby(span)
+            | Event::EnterRubyText(span)
+            | Event::EnterDicexp(span)
+            | Event::EnterAssigningDicexp(AssigningDicexp { span, .. })
+            | Event::EnterWikiLink(WikiLink { span, .. })
+            | Event::ExitInline(span) => span,
+            Event::__Unparsed(_)
+            | Event::Raw(_)
+            | Event::VerbatimEscaping(_)
+            | Event::NewLine(_)
+            | Event::Text(_)
+            | Event::BlockAttachedInfo(_)
+            | Event::RefLink(_)
+            | Event::ExternalLink(_)
+            | Event::InlineAttachedInfo(_) => return None,
+        };
+
+        Some(*span)
+    }
+
     pub(crate) fn is_block_event_that_opens_inline_phase(&self) -> bool {
         #[cfg(debug_assertions)]
         debug_assert!(is_event_of!(Block, self));
@@ -11,26 +11,27 @@ use core::ops::Range;
 
 use crate::{
     Event,
-    common::{is_valid_character_in_name, m},
-    events::{Call, NewLine, VerbatimEscaping, ev},
+    common::{is_valid_character_in_name, is_valid_character_in_variable_name, m},
+    diagnostics::{DiagnosticCode, DiagnosticReporter, DiagnosticSink},
+    events::{AssigningDicexp, Call, CallArgument, NewLine, VerbatimEscaping, WikiLink, ev},
     internal_utils::{
         peekable::Peekable,
         string::{
             count_continuous_character, count_continuous_character_with_maximum, is_whitespace,
         },
     },
-    types::{Stack, TYM_UNIT, Tym},
+    types::{SourceSpan, Stack, TYM_UNIT, Tym},
 };
 
 use parser_inner::{ParserInner, ToSkipInputEvents};
-use stack_wrapper::{Leaf, LeafCodeSpan};
+use stack_wrapper::{Leaf, LeafCodeSpan, LeafDicexp, StackEntryKind};
 use types::{Cursor, YieldContext};
 
 pub struct Parser<'a, TInlineStack: Stack<StackEntry>> {
     full_input: &'a [u8],
 
     state: State<'a>,
-    inner: ParserInner<TInlineStack>,
+    inner: ParserInner<'a, TInlineStack>,
 }
 
 enum State<'a> {
@@ -41,11 +42,11 @@ enum State<'a> {
 }
 
 impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
-    pub fn new(full_input: &'a [u8]) -> Self {
+    pub fn new(full_input: &'a [u8], diagnostic_sink: Option<DiagnosticSink<'a>>) -> Self {
         Self {
             full_input,
             state: State::Idle,
-            inner: ParserInner::new(),
+            inner: ParserInner::new(DiagnosticReporter::new(full_input, diagnostic_sink)),
         }
     }
 
@@ -60,12 +61,19 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                 break Some(Ok(ev));
             }
 
-            if let Some(end_entry) = &self.inner.to_exit_until_popped_entry_from_stack {
+            if let Some((end_kind, closing_end)) = self.inner.to_exit_until_popped_entry_from_stack
+            {
                 let entry = self.inner.stack.pop_entry().unwrap();
-                if &entry == end_entry {
+                let end = if entry.kind == end_kind {
                     self.inner.to_exit_until_popped_entry_from_stack = None;
-                }
-                break Some(Ok(ev!(Inline, ExitInline)));
+                    self.inner.extend_span_end(closing_end);
+                    closing_end
+                } else {
+                    self.inner.report_unclosed_entry(&entry);
+                    self.inner.span_end()
+                };
+                self.inner.leave_slot_beginning();
+                break Some(Ok(self.inner.make_exit_event_for_entry(&entry, end)));
             }
 
             let result = match &mut self.state {
@@ -119,11 +127,17 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                             continue;
                         }
                         Event::VerbatimEscaping(verbatim_escaping) => {
+                            #[cfg(feature = "source-span")]
+                            self.inner.extend_span_end(verbatim_escaping_end(
+                                self.full_input,
+                                &verbatim_escaping,
+                            ));
                             ev!(Inline, VerbatimEscaping(verbatim_escaping))
                         }
                         Event::NewLine(new_line) => ev!(Inline, NewLine(new_line)),
                     };
 
+                    self.inner.leave_slot_beginning();
                     break Some(Ok(to_yield));
                 }
                 State::Parsing { input, cursor } => {
@@ -146,7 +160,7 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
     fn parse(
         input: &[u8],
         cursor: &mut Cursor,
-        inner: &mut ParserInner<TInlineStack>,
+        inner: &mut ParserInner<'_, TInlineStack>,
         event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
     ) -> crate::Result<Tym<4>> {
         match inner.stack.pop_leaf() {
@@ -155,6 +169,14 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                 terminal::code_span::parse_content_and_process(input, cursor, inner, leaf)
                     .map(|tym| tym.into())
             }
+            Some(Leaf::Dicexp(leaf)) => Ok(terminal::dicexp::parse_content_and_process(
+                input,
+                cursor,
+                inner,
+                event_stream,
+                leaf,
+            )
+            .into()),
             Some(Leaf::CallVerbatimArgumentValue) => {
                 terminal::call_verbatim_argument_value::parse_content_and_process(
                     input,
@@ -171,7 +193,7 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
     fn parse_normal(
         input: &[u8],
         cursor: &mut Cursor,
-        inner: &mut ParserInner<TInlineStack>,
+        inner: &mut ParserInner<'_, TInlineStack>,
         event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
     ) -> crate::Result<Tym<4>> {
         let end_condition = inner.stack.make_end_condition();
@@ -219,15 +241,45 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                         None => continue,
                     }
                 }
+                b'h' if input[cursor.value()..].starts_with(b"http")
+                    && !inner.stack.is_in_wiki_link() =>
+                {
+                    match terminal::external_link::process_potential(input, cursor, &end_condition)
+                    {
+                        Some(result) => {
+                            break result;
+                        }
+                        None => {
+                            cursor.move_forward(1);
+                            continue;
+                        }
+                    }
+                }
                 m!('[') => match input.get(cursor.value() + 1) {
                     None => {
                         cursor.move_forward(1);
                         break (cursor.value(), None);
                     }
                     Some(m!('=')) => {
-                        let text_end = cursor.value();
-                        let ev = terminal::dicexp::process(input, cursor);
-                        break (text_end, Some(ev));
+                        let tym_a = yield_text_if_not_empty(text_start, cursor.value(), inner);
+                        let tym_b =
+                            terminal::dicexp::process_and_yield(input, cursor, inner, event_stream);
+                        return Ok(tym_a.add(tym_b));
+                    }
+                    Some(m!('@')) => {
+                        match terminal::dicexp::process_and_yield_potential_assigning(
+                            input,
+                            text_start,
+                            cursor,
+                            inner,
+                            event_stream,
+                        ) {
+                            Some(tym) => return Ok(tym),
+                            None => {
+                                cursor.move_forward(1);
+                                continue;
+                            }
+                        }
                     }
                     Some(m!('`')) => {
                         let text_end = cursor.value();
@@ -238,8 +290,11 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                         let text_end = cursor.value();
 
                         cursor.move_forward("[/".len());
-                        inner.stack.push_entry(StackEntry::Emphasis)?;
-                        let to_yield_after_text = ev!(Inline, EnterEmphasis);
+                        inner
+                            .stack
+                            .push_entry(StackEntry::new(StackEntryKind::Emphasis, text_end))?;
+                        let span = SourceSpan::new_universal(text_end, cursor.value());
+                        let to_yield_after_text = ev!(Inline, EnterEmphasis(span));
 
                         break (text_end, Some(to_yield_after_text));
                     }
@@ -247,8 +302,11 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                         let text_end = cursor.value();
 
                         cursor.move_forward("[*".len());
-                        inner.stack.push_entry(StackEntry::Strong)?;
-                        let to_yield_after_text = ev!(Inline, EnterStrong);
+                        inner
+                            .stack
+                            .push_entry(StackEntry::new(StackEntryKind::Strong, text_end))?;
+                        let span = SourceSpan::new_universal(text_end, cursor.value());
+                        let to_yield_after_text = ev!(Inline, EnterStrong(span));
 
                         break (text_end, Some(to_yield_after_text));
                     }
@@ -256,17 +314,37 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                         let text_end = cursor.value();
 
                         cursor.move_forward("[~".len());
-                        inner.stack.push_entry(StackEntry::Strikethrough)?;
-                        let to_yield_after_text = ev!(Inline, EnterStrikethrough);
+                        inner
+                            .stack
+                            .push_entry(StackEntry::new(StackEntryKind::Strikethrough, text_end))?;
+                        let span = SourceSpan::new_universal(text_end, cursor.value());
+                        let to_yield_after_text = ev!(Inline, EnterStrikethrough(span));
 
                         break (text_end, Some(to_yield_after_text));
                     }
+                    Some(m!('&')) => {
+                        let is_at_slot_beginning =
+                            inner.is_at_slot_beginning() && cursor.value() == text_start;
+                        let text_end = cursor.value();
+                        match bracketed::attached_info::process_potential(
+                            input,
+                            cursor,
+                            is_at_slot_beginning,
+                        ) {
+                            Some(ev) => break (text_end, Some(ev)),
+                            None => {
+                                cursor.move_forward(1);
+                                continue;
+                            }
+                        }
+                    }
                     Some(m!(';')) if !inner.stack.is_in_ruby() => {
                         let text_end = cursor.value();
 
                         cursor.move_forward("[;".len());
-                        inner.stack.enter_ruby()?;
-                        let to_yield_after_text = ev!(Inline, EnterRuby);
+                        inner.stack.enter_ruby(text_end)?;
+                        let span = SourceSpan::new_universal(text_end, cursor.value());
+                        let to_yield_after_text = ev!(Inline, EnterRuby(span));
 
                         break (text_end, Some(to_yield_after_text));
                     }
@@ -285,6 +363,15 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                         }
                     }
                     Some(m!('{')) => {
+                        if input.get(cursor.value() + 2) == Some(&m!('{')) {
+                            if let Some(tym) =
+                                bracketed::parameter_filling::process_and_yield_potential(
+                                    input, text_start, cursor, inner,
+                                )?
+                            {
+                                return Ok(tym.into());
+                            }
+                        }
                         match bracketed::call::process_and_yield_potential(
                             input,
                             text_start,
@@ -312,12 +399,14 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                         text_end
                     };
 
+                    let opening_start = cursor.value();
                     cursor.move_forward(":".len());
                     while input.get(cursor.value()).is_some_and(|c| is_whitespace!(c)) {
                         cursor.move_forward(1);
                     }
-                    inner.stack.enter_ruby_text()?;
-                    let to_yield_after_text = ev!(Inline, EnterRubyText);
+                    inner.stack.enter_ruby_text(opening_start)?;
+                    let span = SourceSpan::new_universal(opening_start, cursor.value());
+                    let to_yield_after_text = ev!(Inline, EnterRubyText(span));
 
                     break (text_end, Some(to_yield_after_text));
                 }
@@ -333,23 +422,28 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
                     break (text_end, Some(ev));
                 }
                 &char => {
-                    let test_result =
-                        #[allow(clippy::manual_map)]
-                        if let Some(entry_to_be_popped_until) = end_condition.test_1(char) {
-                            Some((entry_to_be_popped_until, 1))
-                        } else if let Some(entry_to_be_popped_until) =
-                            end_condition.test_2(char, input.get(cursor.value() + 1).copied())
-                        {
-                            Some((entry_to_be_popped_until, 2))
-                        } else {
-                            None
-                        };
+                    let test_result = #[allow(clippy::manual_map)]
+                    if let Some(entry_to_be_popped_until) = end_condition.test_3(
+                        char,
+                        input.get(cursor.value() + 1).copied(),
+                        input.get(cursor.value() + 2).copied(),
+                    ) {
+                        Some((entry_to_be_popped_until, 3))
+                    } else if let Some(entry_to_be_popped_until) = end_condition.test_1(char) {
+                        Some((entry_to_be_popped_until, 1))
+                    } else if let Some(entry_to_be_popped_until) =
+                        end_condition.test_2(char, input.get(cursor.value() + 1).copied())
+                    {
+                        Some((entry_to_be_popped_until, 2))
+                    } else {
+                        None
+                    };
 
                     if let Some((entry_to_be_popped_until, to_move_forward)) = test_result {
                         let text_end = cursor.value();
                         cursor.move_forward(to_move_forward);
                         inner.to_exit_until_popped_entry_from_stack =
-                            Some(entry_to_be_popped_until);
+                            Some((entry_to_be_popped_until, cursor.value()));
                         break (text_end, None);
                     } else {
                         cursor.move_forward(1);
@@ -370,19 +464,37 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
     }
 
     fn exit_until_stack_is_empty_and_then_end(
-        inner: &mut ParserInner<TInlineStack>,
+        inner: &mut ParserInner<'_, TInlineStack>,
     ) -> (Tym<1>, Option<State<'a>>) {
         match inner.stack.pop_leaf() {
             Some(leaf) => {
                 let tym = match leaf {
-                    stack_wrapper::Leaf::CodeSpan(leaf) => inner.r#yield(leaf.make_exit_event()),
+                    stack_wrapper::Leaf::CodeSpan(leaf) => {
+                        let start = leaf.opening_start;
+                        inner.report_diagnostic(
+                            DiagnosticCode::UnclosedInlineElement,
+                            start..(start + "[".len() + leaf.backticks),
+                        );
+                        inner.r#yield(leaf.make_exit_event(inner.span_end()))
+                    }
+                    stack_wrapper::Leaf::Dicexp(leaf) => {
+                        let start = leaf.opening_start;
+                        inner.report_diagnostic(
+                            DiagnosticCode::UnclosedInlineElement,
+                            start..(start + 2),
+                        );
+                        let span = SourceSpan::new_universal(start, inner.span_end());
+                        inner.r#yield(ev!(Inline, ExitInline(span)))
+                    }
                     stack_wrapper::Leaf::CallVerbatimArgumentValue => TYM_UNIT.into(), // noop
                 };
                 (tym, None)
             }
             _ => match inner.stack.pop_entry() {
-                Some(_entry) => {
-                    let tym = inner.r#yield(ev!(Inline, ExitInline));
+                Some(entry) => {
+                    inner.report_unclosed_entry(&entry);
+                    let ev = inner.make_exit_event_for_entry(&entry, inner.span_end());
+                    let tym = inner.r#yield(ev);
 
                     (tym, None)
                 }
@@ -392,10 +504,24 @@ impl<'a, TInlineStack: Stack<StackEntry>> Parser<'a, TInlineStack> {
     }
 }
 
+/// 逐字转义在源码中的末尾，即闭合部分之后（被强制闭合时为内容的末尾）。
+#[cfg(feature = "source-span")]
+fn verbatim_escaping_end(full_input: &[u8], verbatim_escaping: &VerbatimEscaping) -> usize {
+    let mut end = verbatim_escaping.content.end;
+    if verbatim_escaping.is_closed_forcedly {
+        return end;
+    }
+
+    if full_input.get(end) == Some(&b' ') {
+        end += 1;
+    }
+    end + count_continuous_character(full_input, m!('`'), end) + ">".len()
+}
+
 fn yield_text_if_not_empty<TInlineStack: Stack<StackEntry>>(
     start: usize,
     end: usize,
-    inner: &mut ParserInner<TInlineStack>,
+    inner: &mut ParserInner<'_, TInlineStack>,
 ) -> Tym<1> {
     if end > start {
         inner.r#yield(ev!(Inline, Text(start..end)))
@@ -412,7 +538,7 @@ mod special {
     pub fn process_hard_break_mark<TInlineStack: Stack<StackEntry>>(
         _input: &[u8],
         cursor: &mut Cursor,
-        inner: &mut ParserInner<TInlineStack>,
+        inner: &mut ParserInner<'_, TInlineStack>,
         new_line: NewLine,
     ) -> Event {
         cursor.move_forward("\\".len());
@@ -424,7 +550,7 @@ mod special {
     pub fn process_lines_joint_mark<TInlineStack: Stack<StackEntry>>(
         _input: &[u8],
         cursor: &mut Cursor,
-        inner: &mut ParserInner<TInlineStack>,
+        inner: &mut ParserInner<'_, TInlineStack>,
     ) {
         cursor.move_forward("_".len());
         inner.to_skip_input = ToSkipInputEvents::new_one();
@@ -475,6 +601,40 @@ mod special {
 mod bracketed {
     use super::*;
 
+    pub mod attached_info {
+        use crate::events::{AttachedInfo, AttachedInfoTarget};
+
+        use super::*;
+
+        /// 尝试解析行内附加信息。只有在 `is_at_slot_beginning` 为真时，才会将
+        /// `[&<` 视为行内槽位附加信息的开启部分。
+        ///
+        /// 解析失败时不移动 `cursor`。返回的事件属于 `Inline` 分组。
+        pub fn process_potential(
+            input: &[u8],
+            cursor: &mut Cursor,
+            is_at_slot_beginning: bool,
+        ) -> Option<Event> {
+            let target = match input.get(cursor.value() + "[&".len()) {
+                Some(m!('>')) => AttachedInfoTarget::Following,
+                Some(m!('<')) if is_at_slot_beginning => AttachedInfoTarget::Slot,
+                _ => return None,
+            };
+
+            let start = cursor.value() + "[&>".len();
+            let end = crate::attached_info::parse_sequence(input, start, m!(']'))?;
+            cursor.set_value(end + "]".len());
+
+            Some(ev!(
+                Inline,
+                InlineAttachedInfo(AttachedInfo {
+                    target,
+                    content: start..end,
+                })
+            ))
+        }
+    }
+
     pub mod wiki_link {
         use super::*;
 
@@ -483,86 +643,106 @@ mod bracketed {
             input: &[u8],
             text_start: usize,
             cursor: &mut Cursor,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
         ) -> crate::Result<Option<Tym<4>>> {
             let maybe_text_end = cursor.value();
             cursor.move_forward("[[".len());
 
-            let (address, address_ev, indicator) = if let (Some(slot_content), after_slot) =
-                parse_first_slot_for_non_verbatim(input, cursor)
-            {
-                let AfterSlot::Indicator {
-                    indicator,
-                    index_after_indicator,
-                } = after_slot
-                else {
-                    // 有内容（标题）但没找到指示标记时，不视为Wiki链接。
-                    // 如：`[[f<`oo`>]]`、`[[f\noo]]` 都不被视为Wiki链接。
-                    return Ok(None);
-                };
-
-                cursor.set_value(index_after_indicator);
-                let address = slot_content.clone();
-                let address_ev = ev!(Inline, Text(slot_content));
-
-                (address, address_ev, indicator)
-            } else {
-                let Some((
-                    slot,
-                    AfterSlot::Indicator {
+            let (address, address_ev, indicator, opening_end) =
+                if let (Some(slot_content), after_slot) =
+                    parse_first_slot_for_non_verbatim(input, cursor)
+                {
+                    let AfterSlot::Indicator {
                         indicator,
                         index_after_indicator,
-                    },
-                )) = parse_first_slot_for_verbatim(input, event_stream)
-                else {
-                    return Ok(None);
-                };
+                    } = after_slot
+                    else {
+                        // 有内容（标题）但没找到指示标记时，不视为Wiki链接。
+                        // 如：`[[f<`oo`>]]`、`[[f\noo]]` 都不被视为Wiki链接。
+                        report_opening_as_text(inner, maybe_text_end);
+                        return Ok(None);
+                    };
 
-                // 跳过当前正在处理的事件（即以 “[[” 结尾的事件）以及作为第一个槽位的逐字转译的事件。
-                // 由于完成跳过后会设置游标，这里不用再用 `cursor.set_value` 来设置游标。
-                inner.to_skip_input = ToSkipInputEvents {
-                    count: 2,
-                    cursor_value: Some(index_after_indicator),
-                };
+                    cursor.set_value(index_after_indicator);
+                    let address = slot_content.clone();
+                    let address_ev = ev!(Inline, Text(slot_content));
 
-                let address = slot.content.clone();
-                let address_ev = ev!(Inline, VerbatimEscaping(slot));
+                    (address, address_ev, indicator, index_after_indicator)
+                } else {
+                    let Some((
+                        slot,
+                        AfterSlot::Indicator {
+                            indicator,
+                            index_after_indicator,
+                        },
+                    )) = parse_first_slot_for_verbatim(input, event_stream)
+                    else {
+                        report_opening_as_text(inner, maybe_text_end);
+                        return Ok(None);
+                    };
 
-                (address, address_ev, indicator)
-            };
+                    // 跳过当前正在处理的事件（即以 “[[” 结尾的事件）以及作为第一个槽位的逐字转译的事件。
+                    // 由于完成跳过后会设置游标，这里不用再用 `cursor.set_value` 来设置游标。
+                    inner.to_skip_input = ToSkipInputEvents {
+                        count: 2,
+                        cursor_value: Some(index_after_indicator),
+                    };
+
+                    let address = slot.content.clone();
+                    let address_ev = ev!(Inline, VerbatimEscaping(slot));
 
-            let tym_a = process_first_slot(text_start, maybe_text_end, inner, address)?;
-            let tym_b = process_indicator(inner, address_ev, indicator)?;
+                    (address, address_ev, indicator, index_after_indicator)
+                };
+
+            // 直接以 `]]` 闭合时，开启部分即整个元素。
+            let span = SourceSpan::new_universal(maybe_text_end, opening_end);
+            let tym_a = process_first_slot(text_start, maybe_text_end, inner, address, span)?;
+            let tym_b = process_indicator(inner, address_ev, indicator, maybe_text_end, span)?;
             Ok(Some(tym_a.add(tym_b)))
         }
 
+        fn report_opening_as_text<TInlineStack: Stack<StackEntry>>(
+            inner: &mut ParserInner<'_, TInlineStack>,
+            opening_start: usize,
+        ) {
+            inner.report_diagnostic(
+                DiagnosticCode::WikiLinkOpeningAsText,
+                opening_start..(opening_start + "[[".len()),
+            );
+        }
+
         fn process_first_slot<TInlineStack: Stack<StackEntry>>(
             text_start: usize,
             text_end: usize,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             address: Range<usize>,
+            span: SourceSpan,
         ) -> crate::Result<Tym<2>> {
             let tym_a = yield_text_if_not_empty(text_start, text_end, inner);
-            let tym_b = inner.r#yield(ev!(Inline, EnterWikiLink(address.clone())));
+            let tym_b = inner.r#yield(ev!(Inline, EnterWikiLink(WikiLink { address, span })));
             Ok(tym_a.add(tym_b))
         }
 
         /// `address_ev` 是属于 Inline 分组的事件，其具体应该是
         /// [Event::Text] 或  [Event::VerbatimEscaping]。
         fn process_indicator<TInlineStack: Stack<StackEntry>>(
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             address_ev: Event,
             indicator: Indicator,
+            opening_start: usize,
+            span: SourceSpan,
         ) -> crate::Result<Tym<2>> {
             let tym = match indicator {
                 Indicator::Closing => {
                     let tym_c1 = inner.r#yield(address_ev);
-                    let tym_c2 = inner.r#yield(ev!(Inline, ExitInline));
+                    let tym_c2 = inner.r#yield(ev!(Inline, ExitInline(span)));
                     tym_c1.add(tym_c2)
                 }
                 Indicator::Argument => {
-                    inner.stack.push_entry(StackEntry::WikiLink)?;
+                    inner
+                        .stack
+                        .push_entry(StackEntry::new(StackEntryKind::WikiLink, opening_start))?;
                     TYM_UNIT.into()
                 }
             };
@@ -730,7 +910,7 @@ mod bracketed {
             mut input: &[u8],
             text_start: usize,
             cursor: &mut Cursor,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
         ) -> crate::Result<Option<Tym<3>>> {
             let maybe_text_end = cursor.value();
@@ -834,8 +1014,10 @@ mod bracketed {
 
             let tym_a = yield_text_if_not_empty(text_start, maybe_text_end, inner);
 
+            let name = trim_end(input, name);
             let call = Call::Inline {
-                name: trim_end(input, name),
+                span: SourceSpan::new_universal(maybe_text_end, name.end),
+                name,
             };
             let tym_b = if is_extension {
                 inner.r#yield(Event::EnterCallOnExtension(call))
@@ -845,10 +1027,15 @@ mod bracketed {
 
             let tym_c = match found {
                 Found::Indicator => {
-                    inner.stack.push_entry(StackEntry::Call)?;
+                    inner
+                        .stack
+                        .push_entry(StackEntry::new(StackEntryKind::Call, maybe_text_end))?;
                     TYM_UNIT.into()
                 }
-                Found::End => inner.r#yield(ev!(Inline, ExitInline)),
+                Found::End => {
+                    let span = SourceSpan::new_universal(maybe_text_end, cursor.value());
+                    inner.r#yield(ev!(Inline, ExitInline(span)))
+                }
             };
 
             Ok(Some(tym_a.add(tym_b).add(tym_c)))
@@ -857,11 +1044,16 @@ mod bracketed {
         pub fn process_argument_indicator<TInlineStack: Stack<StackEntry>>(
             mut input: &[u8],
             cursor: &mut Cursor,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
         ) -> Event {
+            let indicator_start = cursor.value();
             cursor.move_forward("|".len());
             let cursor_backup = *cursor;
+            let make_argument = |name: Option<Range<usize>>, cursor: &Cursor| CallArgument {
+                name,
+                span: SourceSpan::new_universal(indicator_start, cursor.value()),
+            };
 
             let mut peeked = 0;
 
@@ -872,10 +1064,16 @@ mod bracketed {
                 cursor.move_forward("`".len());
                 if input.get(cursor.value()).is_some_and(|c| is_whitespace!(c)) {
                     return if can_be_unnamed_verbatim {
-                        ev!(Inline, IndicateCallVerbatimArgument(None))
+                        ev!(
+                            Inline,
+                            IndicateCallVerbatimArgument(make_argument(None, cursor))
+                        )
                     } else {
                         *cursor = cursor_backup;
-                        ev!(Inline, IndicateCallNormalArgument(None))
+                        ev!(
+                            Inline,
+                            IndicateCallNormalArgument(make_argument(None, cursor))
+                        )
                     };
                 }
             }
@@ -942,9 +1140,15 @@ mod bracketed {
 
             if is_verbatim {
                 inner.stack.push_leaf(Leaf::CallVerbatimArgumentValue);
-                ev!(Inline, IndicateCallVerbatimArgument(name))
+                ev!(
+                    Inline,
+                    IndicateCallVerbatimArgument(make_argument(name, cursor))
+                )
             } else {
-                ev!(Inline, IndicateCallNormalArgument(name))
+                ev!(
+                    Inline,
+                    IndicateCallNormalArgument(make_argument(name, cursor))
+                )
             }
         }
 
@@ -962,11 +1166,197 @@ mod bracketed {
             Some(unsafe { core::slice::from_raw_parts(input.as_ptr(), content.end) })
         }
     }
+
+    pub mod parameter_filling {
+        use crate::internal_utils::string::trim_end;
+
+        use super::*;
+
+        /// 尝试将位于 `cursor` 处的内容作为参数填充的开启部分（`[{{` 名称，以及 `|` 或作
+        /// 为闭合部分的 `}}]`）解析。名称不能跨行。失败时不做任何改动，返回 `None`。
+        pub fn process_and_yield_potential<TInlineStack: Stack<StackEntry>>(
+            input: &[u8],
+            text_start: usize,
+            cursor: &mut Cursor,
+            inner: &mut ParserInner<'_, TInlineStack>,
+        ) -> crate::Result<Option<Tym<3>>> {
+            debug_assert!(input[cursor.value()..].starts_with(b"[{{"));
+
+            let mut name_start = cursor.value() + "[{{".len();
+            while input.get(name_start).is_some_and(|c| is_whitespace!(c)) {
+                name_start += 1;
+            }
+            let mut end = name_start;
+            let (name, has_fallback) = loop {
+                match input.get(end) {
+                    None => return Ok(None),
+                    Some(m!('|')) => {
+                        let name = trim_end(input, name_start..end);
+                        end += "|".len();
+                        break (name, true);
+                    }
+                    Some(m!('}')) if input[end..].starts_with(b"}}]") => {
+                        let name = trim_end(input, name_start..end);
+                        end += "}}]".len();
+                        break (name, false);
+                    }
+                    Some(_) => end += 1,
+                }
+            };
+            if name.is_empty() {
+                return Ok(None);
+            }
+
+            let opening_start = cursor.value();
+            let tym_a = yield_text_if_not_empty(text_start, opening_start, inner);
+            cursor.set_value(end);
+
+            let opening = SourceSpan::new_universal(opening_start, name.end);
+            let tym_b = inner.r#yield(Event::EnterParameterFilling(Call::Inline {
+                name,
+                span: opening,
+            }));
+
+            let tym_c = if has_fallback {
+                cursor.skip_whitespaces(input);
+                inner.stack.push_entry(StackEntry::new(
+                    StackEntryKind::ParameterFilling,
+                    opening_start,
+                ))?;
+                let span = SourceSpan::new_universal(end - "|".len(), end);
+                inner.r#yield(ev!(Inline, IndicateParameterFillingFallback(span)))
+            } else {
+                let span = SourceSpan::new_universal(opening_start, end);
+                inner.r#yield(ev!(Inline, ExitInline(span)))
+            };
+
+            Ok(Some(tym_a.add(tym_b).add(tym_c)))
+        }
+    }
 }
 
 mod terminal {
     use super::*;
 
+    pub mod external_link {
+        use super::*;
+
+        use stack_wrapper::EndCondition;
+
+        /// 尝试将位于 `cursor` 处的内容作为以 `http://` 或 `https://` 开头的裸 URL 解析。
+        /// 成功时将游标移至 URL 之后；失败时不做任何改动，返回 `None`。
+        ///
+        /// 返回的事件属于 `Inline` 分组。
+        pub fn process_potential(
+            input: &[u8],
+            cursor: &mut Cursor,
+            end_condition: &EndCondition,
+        ) -> Option<(usize, Option<Event>)> {
+            let start = cursor.value();
+            if start > 0 && input[start - 1].is_ascii_alphanumeric() {
+                return None;
+            }
+            let rest = &input[start..];
+            let address_start = if rest.starts_with(b"https://") {
+                start + "https://".len()
+            } else if rest.starts_with(b"http://") {
+                start + "http://".len()
+            } else {
+                return None;
+            };
+
+            let mut end = address_start;
+            while let Some(&char) = input.get(end) {
+                if char.is_ascii() {
+                    if char.is_ascii_whitespace()
+                        || char.is_ascii_control()
+                        || matches!(
+                            char,
+                            m!('<')
+                                | m!('>')
+                                | m!('"')
+                                | m!('`')
+                                | m!('[')
+                                | m!(']')
+                                | m!('{')
+                                | m!('}')
+                                | m!('|')
+                        )
+                    {
+                        break;
+                    }
+                    end += 1;
+                } else {
+                    match decode_char(input, end) {
+                        Some((char, _)) if is_cjk_punctuation(char) => break,
+                        Some((_, width)) => end += width,
+                        None => break,
+                    }
+                }
+            }
+
+            // 剔除末尾的标点，它们通常属于 URL 所在的句子而非 URL 本身。
+            while end > address_start {
+                match input[end - 1] {
+                    m!('.') | m!(',') | m!(':') | m!(';') | m!('!') | m!('?') | m!('\'') => {
+                        end -= 1;
+                    }
+                    m!(')') if !has_paired_parentheses(&input[address_start..end]) => {
+                        end -= 1;
+                    }
+                    _ => break,
+                }
+            }
+            // 避免吞掉行内元素闭合部分的开头，如 `[/https://example.com/]` 中的 `/`。
+            if end > address_start
+                && end_condition
+                    .test_2(input[end - 1], input.get(end).copied())
+                    .is_some()
+            {
+                end -= 1;
+            }
+
+            if end == address_start {
+                return None;
+            }
+
+            cursor.set_value(end);
+            Some((start, Some(ev!(Inline, ExternalLink(start..end)))))
+        }
+
+        /// 解码位于 `at` 处的 UTF-8 字符，返回该字符及其所占的字节数。
+        fn decode_char(input: &[u8], at: usize) -> Option<(char, usize)> {
+            let width = match input[at] {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => return None,
+            };
+            let bytes = input.get(at..at + width)?;
+            let char = core::str::from_utf8(bytes).ok()?.chars().next()?;
+            Some((char, width))
+        }
+
+        /// 中日韩标点（包括全角标点）。URL 在遇到它们时结束。
+        fn is_cjk_punctuation(char: char) -> bool {
+            matches!(
+                char,
+                '\u{2010}'..='\u{2027}'
+                    | '\u{3000}'..='\u{303F}'
+                    | '\u{FF01}'..='\u{FF0F}'
+                    | '\u{FF1A}'..='\u{FF20}'
+                    | '\u{FF3B}'..='\u{FF40}'
+                    | '\u{FF5B}'..='\u{FF65}'
+            )
+        }
+
+        fn has_paired_parentheses(address: &[u8]) -> bool {
+            let opening = address.iter().filter(|c| **c == m!('(')).count();
+            let closing = address.iter().filter(|c| **c == m!(')')).count();
+            opening >= closing
+        }
+    }
+
     pub mod ref_link {
         use super::*;
 
@@ -1096,39 +1486,117 @@ mod terminal {
     pub mod dicexp {
         use super::*;
 
-        /// 返回的事件属于 `Inline` 分组。
+        /// `event_stream` 的迭代对象是属于 `InlineInput` 分组的事件。
         ///
-        /// TODO: 支持多行。
+        /// NOTE: 由于 dicexp 基于自定义元素，需要以属性（attribute）传递内容，
+        /// 其内容在渲染时才会被拼接起来。在那之前，其内容与 `code_span` 一样，以
+        /// 被 `EnterDicexp` 与 `ExitInline` 包围的 `Text` 与 `NewLine` 表示，
+        /// 这样就能支持多行了。
+        pub fn process_and_yield<TInlineStack: Stack<StackEntry>>(
+            input: &[u8],
+            cursor: &mut Cursor,
+            inner: &mut ParserInner<'_, TInlineStack>,
+            event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
+        ) -> Tym<3> {
+            let opening_start = cursor.value();
+            cursor.move_forward("[=".len());
+            let span = SourceSpan::new_universal(opening_start, cursor.value());
+            let tym_a = inner.r#yield(ev!(Inline, EnterDicexp(span)));
+            let tym_b = parse_content_and_process(input, cursor, inner, event_stream, LeafDicexp {
+                depth: 1,
+                opening_start,
+            });
+            tym_a.add(tym_b)
+        }
+
+        /// 尝试解析赋值骰子表达式（`[@<变量名>=…]`）。若并非赋值骰子表达式，返
+        /// 回 `None`，且不移动 `cursor`。
         ///
-        /// NOTE: 为什么 `dicexp` 不能像 `code_span` 那样相对简单地支持多行：
-        /// - `code_span` 基于原生的 HTML 元素 `<code/>`，内容可以直接作为其子节
-        ///   点，因此设计成了用两个事件（`EnterCodeSpan`、`Exit`）来包围内容。
-        ///   
-        /// - `dicexp` 基于自定义元素，需要以属性（attribute）传递内容，因此设计
-        ///   为了只用一个事件（`Dicexp`）。
+        /// `event_stream` 的迭代对象是属于 `InlineInput` 分组的事件。
+        pub fn process_and_yield_potential_assigning<TInlineStack: Stack<StackEntry>>(
+            input: &[u8],
+            text_start: usize,
+            cursor: &mut Cursor,
+            inner: &mut ParserInner<'_, TInlineStack>,
+            event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
+        ) -> Option<Tym<4>> {
+            let name_start = cursor.value() + "[@".len();
+            let mut name_end = name_start;
+            while input
+                .get(name_end)
+                .is_some_and(|c| is_valid_character_in_variable_name(*c))
+            {
+                name_end += 1;
+            }
+            if name_end == name_start || input.get(name_end) != Some(&m!('=')) {
+                return None;
+            }
+
+            let opening_start = cursor.value();
+            let tym_a = yield_text_if_not_empty(text_start, opening_start, inner);
+
+            cursor.set_value(name_end + "=".len());
+            let tym_b = inner.r#yield(ev!(
+                Inline,
+                EnterAssigningDicexp(AssigningDicexp {
+                    name: name_start..name_end,
+                    span: SourceSpan::new_universal(opening_start, cursor.value()),
+                })
+            ));
+            let tym_c = parse_content_and_process(input, cursor, inner, event_stream, LeafDicexp {
+                depth: 1,
+                opening_start,
+            });
+
+            Some(tym_a.add(tym_b).add(tym_c))
+        }
+
+        /// `event_stream` 的迭代对象是属于 `InlineInput` 分组的事件。
         ///
-        /// 后者的事件只能传递一整块范围的内容，而多行内容的范围并不连续，因此无
-        /// 法传递。未来可能会让 `dicexp` 的事件也以 Enter/Exit 的形式表示，在那
-        /// 之前 dicexp 无法支持多行。
-        pub fn process(input: &[u8], cursor: &mut Cursor) -> Event {
-            cursor.move_forward("[=".len());
-            let content = terminal::dicexp::advance_until_ends(input, cursor);
-            ev!(Inline, Dicexp(content))
+        /// 若直到 `input` 到头都没有闭合，且之后是换行，则在下一行继续；否则
+        /// （之后是逐字转义或者已经没有后续内容）就此结束。
+        pub fn parse_content_and_process<TInlineStack: Stack<StackEntry>>(
+            input: &[u8],
+            cursor: &mut Cursor,
+            inner: &mut ParserInner<'_, TInlineStack>,
+            event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
+            mut leaf: LeafDicexp,
+        ) -> Tym<2> {
+            let content = advance_until_ends(input, cursor, &mut leaf.depth);
+            let tym_a = yield_text_if_not_empty(content.start, content.end, inner);
+
+            let tym_b = if leaf.depth == 0 {
+                let span = SourceSpan::new_universal(leaf.opening_start, cursor.value());
+                inner.r#yield(ev!(Inline, ExitInline(span)))
+            } else if let Some(ev!(InlineInput, NewLine(_))) = event_stream.peek(0) {
+                inner.stack.push_leaf(leaf.into());
+                TYM_UNIT.into()
+            } else {
+                let start = leaf.opening_start;
+                inner.report_diagnostic(DiagnosticCode::UnclosedInlineElement, start..(start + 2));
+                let span = SourceSpan::new_universal(start, inner.span_end());
+                inner.r#yield(ev!(Inline, ExitInline(span)))
+            };
+
+            tym_a.add(tym_b)
         }
 
-        /// 推进游标，直到到了数量匹配的 “]” 之前，或者 `input` 到头时。如果是前者，结束时
-        /// `ctx.cursor()` 对应于 “]” 的索引，也即还没消耗掉那个 “]”。
-        pub fn advance_until_ends(input: &[u8], cursor: &mut Cursor) -> Range<usize> {
+        /// 推进游标，直到到了数量匹配的 “]” 之前，或者 `input` 到头时。`depth`
+        /// 是尚未配对的 “[” 的数量，在前者的情况下结束时为 0，且 `ctx.cursor()`
+        /// 对应于 “]” 之后的索引，也即已经消耗掉了那个 “]”。
+        pub fn advance_until_ends(
+            input: &[u8],
+            cursor: &mut Cursor,
+            depth: &mut usize,
+        ) -> Range<usize> {
             let start = cursor.value();
 
-            let mut depth = 1;
-
             while let Some(char) = input.get(cursor.value()) {
                 match char {
-                    m!('[') => depth += 1,
+                    m!('[') => *depth += 1,
                     m!(']') => {
-                        depth -= 1;
-                        if depth == 0 {
+                        *depth -= 1;
+                        if *depth == 0 {
                             let content = start..cursor.value();
                             cursor.move_forward(1);
                             return content;
@@ -1150,8 +1618,9 @@ mod terminal {
         pub fn process<TInlineStack: Stack<StackEntry>>(
             input: &[u8],
             cursor: &mut Cursor,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
         ) -> Event {
+            let opening_start = cursor.value();
             let backticks =
                 "`".len() + count_continuous_character(input, m!('`'), cursor.value() + "[`".len());
             cursor.move_forward("[".len() + backticks);
@@ -1160,7 +1629,10 @@ mod terminal {
                 cursor.move_forward(1);
             }
 
-            let leaf = LeafCodeSpan { backticks };
+            let leaf = LeafCodeSpan {
+                backticks,
+                opening_start,
+            };
             let ev = leaf.make_enter_event();
             inner.stack.push_leaf(leaf.into());
             ev
@@ -1169,7 +1641,7 @@ mod terminal {
         pub fn parse_content_and_process<TInlineStack: Stack<StackEntry>>(
             input: &[u8],
             cursor: &mut Cursor,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             leaf: LeafCodeSpan,
         ) -> crate::Result<Tym<3>> {
             let start = cursor.value();
@@ -1211,7 +1683,7 @@ mod terminal {
                 let tym_a = yield_text_if_not_empty(start, content_end, inner);
 
                 cursor.move_forward(leaf.backticks + "]".len());
-                let tym_b = inner.r#yield(leaf.make_exit_event());
+                let tym_b = inner.r#yield(leaf.make_exit_event(cursor.value()));
 
                 return Ok(tym_a.add(tym_b).into());
             }
@@ -1228,7 +1700,7 @@ mod terminal {
         pub fn parse_content_and_process<TInlineStack: Stack<StackEntry>>(
             input: &[u8],
             cursor: &mut Cursor,
-            inner: &mut ParserInner<TInlineStack>,
+            inner: &mut ParserInner<'_, TInlineStack>,
             event_stream: &mut Peekable<2, impl Iterator<Item = Event>>,
         ) -> crate::Result<Tym<2>> {
             let start = cursor.value();
@@ -1250,7 +1722,8 @@ mod terminal {
                     m!('}') if input.get(cursor.value() + 1) == Some(&m!(']')) => {
                         let tym = yield_text_if_not_empty(start, cursor.value(), inner);
                         cursor.move_forward("}]".len());
-                        inner.to_exit_until_popped_entry_from_stack = Some(StackEntry::Call);
+                        inner.to_exit_until_popped_entry_from_stack =
+                            Some((StackEntryKind::Call, cursor.value()));
                         return Ok(tym.into());
                     }
                     _ => cursor.move_forward(1),
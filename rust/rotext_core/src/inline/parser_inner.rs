@@ -1,15 +1,24 @@
 #[cfg(debug_assertions)]
 use crate::events::is_event_of;
-use crate::{Event, internal_utils::array_queue::ArrayQueue, types::Stack, types::Tym};
+use core::ops::Range;
+
+use crate::{
+    Event,
+    diagnostics::{DiagnosticCode, DiagnosticReporter},
+    events::{AttachedInfo, AttachedInfoTarget, ev},
+    internal_utils::array_queue::ArrayQueue,
+    types::Stack,
+    types::{SourceSpan, Tym},
+};
 
 use super::{
-    stack_wrapper::{StackEntry, StackWrapper},
+    stack_wrapper::{StackEntry, StackEntryKind, StackWrapper},
     types::YieldContext,
 };
 
 const MAX_TO_YIELD: usize = 4;
 
-pub struct ParserInner<TStack: Stack<StackEntry>> {
+pub struct ParserInner<'a, TStack: Stack<StackEntry>> {
     pub stack: StackWrapper<TStack>,
 
     /// 承载的事件属于 `Inline` 分组。
@@ -18,16 +27,30 @@ pub struct ParserInner<TStack: Stack<StackEntry>> {
     /// XXX: 要确保 `cursor` 到达 `input.len()`，以让 `state` 变为 [State::Idle]。
     pub to_skip_input: ToSkipInputEvents,
 
-    pub to_exit_until_popped_entry_from_stack: Option<StackEntry>,
+    /// 第二个值是闭合部分的末尾。
+    pub to_exit_until_popped_entry_from_stack: Option<(StackEntryKind, usize)>,
+
+    /// 是否位于槽位的开头，即可以在此处放置行内槽位附加信息。
+    is_at_slot_beginning: bool,
+
+    diagnostic_reporter: DiagnosticReporter<'a>,
+
+    /// 已产出的事件所涉及的源码的末尾，用于确定强制退出的元素的范围。
+    #[cfg(feature = "source-span")]
+    span_end: usize,
 }
 
-impl<TStack: Stack<StackEntry>> ParserInner<TStack> {
-    pub fn new() -> Self {
+impl<'a, TStack: Stack<StackEntry>> ParserInner<'a, TStack> {
+    pub fn new(diagnostic_reporter: DiagnosticReporter<'a>) -> Self {
         Self {
             stack: StackWrapper::new(),
             to_yield: ArrayQueue::new(),
             to_skip_input: ToSkipInputEvents::default(),
             to_exit_until_popped_entry_from_stack: None,
+            is_at_slot_beginning: false,
+            diagnostic_reporter,
+            #[cfg(feature = "source-span")]
+            span_end: 0,
         }
     }
 
@@ -37,13 +60,107 @@ impl<TStack: Stack<StackEntry>> ParserInner<TStack> {
     pub fn pop_to_be_yielded(&mut self) -> Option<Event> {
         self.to_yield.pop_front()
     }
+
+    pub fn is_at_slot_beginning(&self) -> bool {
+        self.is_at_slot_beginning
+    }
+
+    /// 用于不经由 [YieldContext::yield] 直接产出事件的场合。
+    pub fn leave_slot_beginning(&mut self) {
+        self.is_at_slot_beginning = false;
+    }
+
+    /// 已产出的事件所涉及的源码的末尾。未启用 `source-span` 特性时恒为 0。
+    pub fn span_end(&self) -> usize {
+        #[cfg(feature = "source-span")]
+        {
+            self.span_end
+        }
+        #[cfg(not(feature = "source-span"))]
+        {
+            0
+        }
+    }
+
+    /// 用于不经由 [YieldContext::yield] 直接产出事件的场合。
+    pub fn extend_span_end(&mut self, #[allow(unused_variables)] end: usize) {
+        #[cfg(feature = "source-span")]
+        {
+            self.span_end = self.span_end.max(end);
+        }
+    }
+
+    /// 构造退出 `entry` 对应元素的事件，其范围直到 `end`。
+    pub fn make_exit_event_for_entry(&self, entry: &StackEntry, end: usize) -> Event {
+        ev!(
+            Inline,
+            ExitInline(SourceSpan::new_universal(entry.opening_start, end))
+        )
+    }
+
+    /// 报告诊断，见 [crate::diagnostics]。
+    pub fn report_diagnostic(&mut self, code: DiagnosticCode, range: Range<usize>) {
+        self.diagnostic_reporter.report(code, range);
+    }
+
+    /// 报告 `entry` 在没有闭合的情况下被弹出。
+    pub fn report_unclosed_entry(&mut self, entry: &StackEntry) {
+        let opening_length = match entry.kind {
+            StackEntryKind::ParameterFilling => "[{{".len(),
+            // 注音文本随注音一同闭合，只报告注音本身。
+            StackEntryKind::_RubyText => return,
+            _ => 2,
+        };
+
+        let start = entry.opening_start;
+        self.report_diagnostic(
+            DiagnosticCode::UnclosedInlineElement,
+            start..(start + opening_length),
+        );
+    }
 }
-impl<TStack: Stack<StackEntry>> YieldContext for ParserInner<TStack> {
+impl<TStack: Stack<StackEntry>> YieldContext for ParserInner<'_, TStack> {
     /// `ev` 是属于 `Inline` 分组的事件。
     #[must_use]
     fn r#yield(&mut self, ev: Event) -> Tym<1> {
         #[cfg(debug_assertions)]
         debug_assert!(is_event_of!(Inline, ev));
+        self.is_at_slot_beginning = matches!(
+            ev,
+            ev!(Inline, EnterEmphasis(..))
+                | ev!(Inline, EnterStrong(..))
+                | ev!(Inline, EnterStrikethrough(..))
+                | ev!(Inline, EnterRuby(..))
+                | ev!(Inline, EnterRubyText(..))
+                | ev!(Inline, EnterWikiLink(..))
+                | ev!(Inline, IndicateCallNormalArgument(..))
+                | ev!(Inline, IndicateParameterFillingFallback(..))
+                | ev!(
+                    Inline,
+                    InlineAttachedInfo(AttachedInfo {
+                        target: AttachedInfoTarget::Slot,
+                        ..
+                    })
+                )
+        );
+
+        #[cfg(feature = "source-span")]
+        {
+            let end = match &ev {
+                ev!(Inline, Text(content))
+                | ev!(Inline, Raw(content))
+                | ev!(Inline, RefLink(content))
+                | ev!(Inline, ExternalLink(content)) => Some(content.end),
+                ev!(Inline, InlineAttachedInfo(AttachedInfo { content, .. })) => {
+                    Some(content.end + "]".len())
+                }
+                ev => ev.span().map(|span| span.end()),
+            };
+            if let Some(end) = end {
+                self.extend_span_end(end);
+            }
+        }
+
         self.to_yield.push_back(ev);
         Tym::<1>::new()
     }
@@ -1,4 +1,9 @@
-use crate::{Event, common::m, events::ev, types::Stack};
+use crate::{
+    Event,
+    common::m,
+    events::ev,
+    types::{SourceSpan, Stack},
+};
 
 pub struct StackWrapper<TStack: Stack<StackEntry>> {
     stack: TStack,
@@ -24,23 +29,26 @@ impl<TStack: Stack<StackEntry>> StackWrapper<TStack> {
     pub fn push_entry(&mut self, entry: StackEntry) -> crate::Result<()> {
         debug_assert!(self.leaf.is_none());
 
-        match entry {
-            StackEntry::Emphasis => {
+        match entry.kind {
+            StackEntryKind::Emphasis => {
                 self.stack_entry_counts.emphasis += 1;
             }
-            StackEntry::Strong => {
+            StackEntryKind::Strong => {
                 self.stack_entry_counts.strong += 1;
             }
-            StackEntry::Strikethrough => {
+            StackEntryKind::Strikethrough => {
                 self.stack_entry_counts.strikethrough += 1;
             }
-            StackEntry::WikiLink => {
+            StackEntryKind::WikiLink => {
                 self.stack_entry_counts.wiki_link += 1;
             }
-            StackEntry::Call => {
+            StackEntryKind::Call => {
                 self.stack_entry_counts.call += 1;
             }
-            StackEntry::_Ruby | StackEntry::_RubyText => {}
+            StackEntryKind::ParameterFilling => {
+                self.stack_entry_counts.parameter_filling += 1;
+            }
+            StackEntryKind::_Ruby | StackEntryKind::_RubyText => {}
         }
 
         self.stack.try_push(entry)
@@ -52,19 +60,37 @@ impl<TStack: Stack<StackEntry>> StackWrapper<TStack> {
     pub fn is_in_ruby_but_not_in_ruby_text(&self) -> bool {
         self.ruby_state == RubyState::Base
     }
+    pub fn is_in_wiki_link(&self) -> bool {
+        self.stack_entry_counts.wiki_link > 0
+    }
+    /// 参数填充只有一个 fallback，因此位于参数填充之中（且未再进入调用）时不视为在调用中。
     pub fn is_in_call(&self) -> bool {
-        self.stack_entry_counts.call > 0
+        if self.stack_entry_counts.parameter_filling == 0 {
+            return self.stack_entry_counts.call > 0;
+        }
+
+        self.stack
+            .as_slice()
+            .iter()
+            .rev()
+            .find(|entry| {
+                matches!(
+                    entry.kind,
+                    StackEntryKind::Call | StackEntryKind::ParameterFilling
+                )
+            })
+            .is_some_and(|entry| entry.kind == StackEntryKind::Call)
     }
 
-    pub fn enter_ruby(&mut self) -> crate::Result<()> {
+    pub fn enter_ruby(&mut self, opening_start: usize) -> crate::Result<()> {
         debug_assert_eq!(self.ruby_state, RubyState::None);
         self.ruby_state = RubyState::Base;
-        self.push_entry(StackEntry::_Ruby)
+        self.push_entry(StackEntry::new(StackEntryKind::_Ruby, opening_start))
     }
-    pub fn enter_ruby_text(&mut self) -> crate::Result<()> {
+    pub fn enter_ruby_text(&mut self, opening_start: usize) -> crate::Result<()> {
         debug_assert_eq!(self.ruby_state, RubyState::Base);
         self.ruby_state = RubyState::Text;
-        self.push_entry(StackEntry::_RubyText)
+        self.push_entry(StackEntry::new(StackEntryKind::_RubyText, opening_start))
     }
     pub fn exit_ruby(&mut self) {
         debug_assert_ne!(self.ruby_state, RubyState::None);
@@ -81,26 +107,29 @@ impl<TStack: Stack<StackEntry>> StackWrapper<TStack> {
         debug_assert!(self.leaf.is_none());
 
         let entry = self.stack.pop()?;
-        match entry {
-            StackEntry::Emphasis => {
+        match entry.kind {
+            StackEntryKind::Emphasis => {
                 self.stack_entry_counts.emphasis -= 1;
             }
-            StackEntry::Strong => {
+            StackEntryKind::Strong => {
                 self.stack_entry_counts.strong -= 1;
             }
-            StackEntry::Strikethrough => {
+            StackEntryKind::Strikethrough => {
                 self.stack_entry_counts.strikethrough -= 1;
             }
-            StackEntry::WikiLink => {
+            StackEntryKind::WikiLink => {
                 self.stack_entry_counts.wiki_link -= 1;
             }
-            StackEntry::Call => {
+            StackEntryKind::Call => {
                 self.stack_entry_counts.call -= 1;
             }
-            StackEntry::_Ruby => {
+            StackEntryKind::ParameterFilling => {
+                self.stack_entry_counts.parameter_filling -= 1;
+            }
+            StackEntryKind::_Ruby => {
                 self.exit_ruby();
             }
-            StackEntry::_RubyText => {}
+            StackEntryKind::_RubyText => {}
         }
 
         Some(entry)
@@ -119,19 +148,35 @@ impl<TStack: Stack<StackEntry>> StackWrapper<TStack> {
             on_strikethrough_closing: self.stack_entry_counts.strikethrough > 0,
             on_wiki_link_closing: self.stack_entry_counts.wiki_link > 0,
             on_call_closing: self.stack_entry_counts.call > 0,
+            on_parameter_filling_closing: self.stack_entry_counts.parameter_filling > 0,
 
             on_ruby_closing: self.is_in_ruby(),
         }
     }
 }
 
-#[derive(PartialEq, Eq)]
-pub enum StackEntry {
+pub struct StackEntry {
+    pub kind: StackEntryKind,
+    /// 开启部分开始的位置，用于报告诊断。
+    pub opening_start: usize,
+}
+impl StackEntry {
+    pub fn new(kind: StackEntryKind, opening_start: usize) -> Self {
+        Self {
+            kind,
+            opening_start,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackEntryKind {
     Emphasis,
     Strong,
     Strikethrough,
     WikiLink,
     Call,
+    ParameterFilling,
     /// 应仅在本模块内直接使用。
     _Ruby,
     /// 应仅在本模块内直接使用。
@@ -145,6 +190,7 @@ struct StackEntryCounts {
     strikethrough: usize,
     wiki_link: usize,
     call: usize,
+    parameter_filling: usize,
 }
 
 #[derive(PartialEq, Eq, Default, Debug)]
@@ -157,6 +203,7 @@ enum RubyState {
 
 pub enum Leaf {
     CodeSpan(LeafCodeSpan),
+    Dicexp(LeafDicexp),
     CallVerbatimArgumentValue,
 }
 impl From<LeafCodeSpan> for Leaf {
@@ -164,56 +211,97 @@ impl From<LeafCodeSpan> for Leaf {
         Self::CodeSpan(value)
     }
 }
+impl From<LeafDicexp> for Leaf {
+    fn from(value: LeafDicexp) -> Self {
+        Self::Dicexp(value)
+    }
+}
 
 pub struct LeafCodeSpan {
     pub backticks: usize,
+    /// 开启部分开始的位置，用于报告诊断。
+    pub opening_start: usize,
 }
 impl LeafCodeSpan {
     pub fn make_enter_event(&self) -> Event {
-        ev!(Inline, EnterCodeSpan)
+        let opening_end = self.opening_start + "[".len() + self.backticks;
+        ev!(
+            Inline,
+            EnterCodeSpan(SourceSpan::new_universal(self.opening_start, opening_end))
+        )
     }
 
-    pub fn make_exit_event(&self) -> Event {
-        ev!(Inline, ExitInline)
+    pub fn make_exit_event(&self, end: usize) -> Event {
+        ev!(
+            Inline,
+            ExitInline(SourceSpan::new_universal(self.opening_start, end))
+        )
     }
 }
 
+/// 跨行的 dicexp。
+pub struct LeafDicexp {
+    /// 尚未配对的 “[” 的数量（包括开启部分中的那个）。
+    pub depth: usize,
+    /// 开启部分开始的位置，用于报告诊断。
+    pub opening_start: usize,
+}
+
 pub struct EndCondition {
     pub on_em_closing: bool,
     pub on_strong_closing: bool,
     pub on_strikethrough_closing: bool,
     pub on_wiki_link_closing: bool,
     pub on_call_closing: bool,
+    pub on_parameter_filling_closing: bool,
 
     pub on_ruby_closing: bool,
 }
 
 impl EndCondition {
-    /// 若返回的栈的 entry 不为 None，则应该退出直至有一个该 entry 被弹出。
-    pub fn test_1(&self, char: u8) -> Option<StackEntry> {
+    /// 若返回的栈的 entry 种类不为 None，则应该退出直至有一个该种类的 entry 被弹出。
+    pub fn test_1(&self, char: u8) -> Option<StackEntryKind> {
         if self.on_ruby_closing && char == m!(']') {
-            Some(StackEntry::_Ruby)
+            Some(StackEntryKind::_Ruby)
+        } else {
+            None
+        }
+    }
+
+    /// 若返回的栈的 entry 种类不为 None，则应该退出直至有一个该种类的 entry 被弹出。
+    pub fn test_3(
+        &self,
+        char: u8,
+        char_next: Option<u8>,
+        char_next_next: Option<u8>,
+    ) -> Option<StackEntryKind> {
+        if self.on_parameter_filling_closing
+            && char == m!('}')
+            && char_next == Some(m!('}'))
+            && char_next_next == Some(m!(']'))
+        {
+            Some(StackEntryKind::ParameterFilling)
         } else {
             None
         }
     }
 
-    /// 若返回的栈的 entry 不为 None，则应该退出直至有一个该 entry 被弹出。
-    pub fn test_2(&self, char: u8, char_next: Option<u8>) -> Option<StackEntry> {
+    /// 若返回的栈的 entry 种类不为 None，则应该退出直至有一个该种类的 entry 被弹出。
+    pub fn test_2(&self, char: u8, char_next: Option<u8>) -> Option<StackEntryKind> {
         if char_next != Some(m!(']')) {
             return None;
         }
 
         if self.on_em_closing && char == m!('/') {
-            Some(StackEntry::Emphasis)
+            Some(StackEntryKind::Emphasis)
         } else if self.on_strong_closing && char == m!('*') {
-            Some(StackEntry::Strong)
+            Some(StackEntryKind::Strong)
         } else if self.on_strikethrough_closing && char == m!('~') {
-            Some(StackEntry::Strikethrough)
+            Some(StackEntryKind::Strikethrough)
         } else if self.on_wiki_link_closing && char == m!(']') {
-            Some(StackEntry::WikiLink)
+            Some(StackEntryKind::WikiLink)
         } else if self.on_call_closing && char == m!('}') {
-            Some(StackEntry::Call)
+            Some(StackEntryKind::Call)
         } else {
             None
         }
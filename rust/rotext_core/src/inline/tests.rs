@@ -70,7 +70,8 @@ mod for_mod_leaf {
 
             fn test(input: &[u8], expected: Range<usize>, expected_cursor: Cursor) {
                 let mut cursor = Cursor::new(0);
-                let actual = advance_until_ends(input, &mut cursor);
+                let mut depth = 1;
+                let actual = advance_until_ends(input, &mut cursor, &mut depth);
                 assert_eq!((expected, expected_cursor), (actual, cursor))
             }
 
@@ -1,3 +1,5 @@
 pub mod array_queue;
 pub mod peekable;
+#[cfg(feature = "line-number")]
+pub mod position_resolver;
 pub mod string;
@@ -0,0 +1,161 @@
+#[cfg(feature = "position")]
+use crate::types::Position;
+
+/// 根据字节位置计算其所在的行（以及列）。
+///
+/// 记录上次向前计算到的位置，因此按位置先后依次计算时开销较小；计算较早的位置时，则从记录
+/// 的位置往回数。
+pub struct PositionResolver<'a> {
+    input: &'a [u8],
+    /// 上次向前计算到的位置、其所在的行，以及该行开头的位置。
+    last: (usize, usize, usize),
+    /// 上次计算列时所在的行的开头、计算到的位置，以及两者之间的内容在 UTF-16 中的长度。同一
+    /// 行中之后的位置可以从这里接着计算。
+    #[cfg(feature = "position")]
+    last_column: (usize, usize, usize),
+}
+
+impl<'a> PositionResolver<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            last: (0, 1, 0),
+            #[cfg(feature = "position")]
+            last_column: (0, 0, 0),
+        }
+    }
+
+    /// 从 `line_start` 处开始计算，该处须位于第 `line` 行的开头。用于不从输入的开头开始解析
+    /// 的场合。
+    #[cfg(feature = "position")]
+    pub fn set_line_start(&mut self, line_start: usize, line: usize) {
+        self.last = (line_start, line, line_start);
+    }
+
+    /// 返回 `position` 所在的行，以及该行开头的位置。
+    pub fn line_at(&mut self, position: usize) -> (usize, usize) {
+        let (last_position, last_line, last_line_start) = self.last;
+
+        if position >= last_position {
+            let (mut line, mut line_start) = (last_line, last_line_start);
+            for i in last_position..position {
+                if self.is_line_end(i) {
+                    line += 1;
+                    line_start = i + 1;
+                }
+            }
+            self.last = (position, line, line_start);
+            (line, line_start)
+        } else if position >= last_line_start {
+            (last_line, last_line_start)
+        } else {
+            let line = last_line
+                - (position..last_line_start)
+                    .filter(|i| self.is_line_end(*i))
+                    .count();
+            let line_start = (0..position)
+                .rev()
+                .find(|i| self.is_line_end(*i))
+                .map_or(0, |i| i + 1);
+            (line, line_start)
+        }
+    }
+
+    #[cfg(feature = "position")]
+    pub fn position_at(&mut self, position: usize) -> Position {
+        let (line, line_start) = self.line_at(position);
+        let (counted_line_start, counted, counted_utf16_length) = self.last_column;
+        let (from, utf16_length) = if counted_line_start == line_start && counted <= position {
+            (counted, counted_utf16_length)
+        } else {
+            (line_start, 0)
+        };
+        let utf16_length = utf16_length + utf16_length_of(&self.input[from..position]);
+        self.last_column = (line_start, position, utf16_length);
+
+        Position::new(line, position - line_start + 1, utf16_length + 1)
+    }
+
+    /// `i` 处的字节是否是换行（`\n`、`\r\n` 或单独的 `\r`）的最后一个字节。
+    fn is_line_end(&self, i: usize) -> bool {
+        match self.input[i] {
+            b'\n' => true,
+            b'\r' => self.input.get(i + 1) != Some(&b'\n'),
+            _ => false,
+        }
+    }
+}
+
+/// `utf8` 在 UTF-16 中的长度。`utf8` 可以在字符的中间断开，分段计算的结果之和不变。
+#[cfg(feature = "position")]
+fn utf16_length_of(utf8: &[u8]) -> usize {
+    // UTF-8 中，非后续字节各自开始一个字符；四字节的字符在 UTF-16 中占两个码元。
+    utf8.iter()
+        .map(|b| match b {
+            0x80..=0xBF => 0,
+            0xF0..=0xFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_at_works() {
+        let input = b"ab\ncd\r\nef\rgh";
+        let mut resolver = PositionResolver::new(input);
+        for (position, expected) in [
+            (0, (1, 0)),
+            (4, (2, 3)),
+            (9, (3, 7)),
+            (5, (2, 3)),
+            (1, (1, 0)),
+            (11, (4, 10)),
+            (8, (3, 7)),
+            (12, (4, 10)),
+        ] {
+            assert_eq!(resolver.line_at(position), expected, "position: {position}");
+        }
+    }
+
+    #[cfg(feature = "position")]
+    #[test]
+    fn position_at_counts_columns() {
+        let input = "a\n中文😀b".as_bytes();
+        let mut resolver = PositionResolver::new(input);
+        assert_eq!(resolver.position_at(1), Position::new(1, 2, 2));
+        assert_eq!(resolver.position_at(2), Position::new(2, 1, 1));
+        assert_eq!(resolver.position_at(8), Position::new(2, 7, 3));
+        assert_eq!(resolver.position_at(12), Position::new(2, 11, 5));
+    }
+
+    #[cfg(feature = "position")]
+    #[test]
+    fn position_at_works_in_any_order() {
+        let input = "中文😀b\n😀\r\nc中".as_bytes();
+        let expected = [
+            (0, Position::new(1, 1, 1)),
+            (3, Position::new(1, 4, 2)),
+            (6, Position::new(1, 7, 3)),
+            (10, Position::new(1, 11, 5)),
+            (11, Position::new(1, 12, 6)),
+            (12, Position::new(2, 1, 1)),
+            (16, Position::new(2, 5, 3)),
+            (18, Position::new(3, 1, 1)),
+            (19, Position::new(3, 2, 2)),
+            (22, Position::new(3, 5, 3)),
+        ];
+        let mut resolver = PositionResolver::new(input);
+        for index in [0, 2, 4, 3, 1, 9, 5, 8, 6, 7, 6, 9] {
+            let (position, expected) = expected[index];
+            assert_eq!(
+                resolver.position_at(position),
+                expected,
+                "position: {position}"
+            );
+        }
+    }
+}
@@ -4,17 +4,20 @@
 #![feature(proc_macro_hygiene)]
 #![feature(stmt_expr_attributes)]
 
+pub mod attached_info;
 mod blend;
 mod block;
 mod common;
+pub mod diagnostics;
 pub mod events;
 mod inline;
 mod types;
 
 mod internal_utils;
 
+pub use diagnostics::{Diagnostic, DiagnosticCode, DiagnosticSink};
 pub use events::{Event, EventType};
-pub use types::{BlockId, Error, LineNumber, Result, Stack};
+pub use types::{BlockId, Error, LineNumber, Position, Result, SourceSpan, Stack};
 
 pub use block::StackEntry as BlockStackEntry;
 pub use inline::StackEntry as InlineStackEntry;
@@ -30,3 +33,29 @@ pub fn parse<TBlockStack: Stack<BlockStackEntry>, TInlineStack: Stack<InlineStac
 
     blend::BlockEventStreamInlineSegmentMapper::new(input, block_parser)
 }
+
+/// 同 [parse]，但会将解析时发现的问题报告给 `sink`，见 [diagnostics]。
+pub fn parse_with_diagnostics<
+    'a,
+    TBlockStack: Stack<BlockStackEntry>,
+    TInlineStack: Stack<InlineStackEntry>,
+>(
+    input: &'a [u8],
+    sink: DiagnosticSink<'a>,
+) -> blend::BlockEventStreamInlineSegmentMapper<'a, block::Parser<'a, TBlockStack>, TInlineStack> {
+    let block_parser = block::Parser::new(input).with_diagnostic_sink(sink);
+
+    blend::BlockEventStreamInlineSegmentMapper::new(input, block_parser).with_diagnostic_sink(sink)
+}
+
+/// 将整份输入视为单个段落进行解析，见 [BlockParser::new_inline_only]。
+pub fn parse_inline_only<
+    TBlockStack: Stack<BlockStackEntry>,
+    TInlineStack: Stack<InlineStackEntry>,
+>(
+    input: &[u8],
+) -> blend::BlockEventStreamInlineSegmentMapper<block::Parser<TBlockStack>, TInlineStack> {
+    let block_parser = block::Parser::new_inline_only(input);
+
+    blend::BlockEventStreamInlineSegmentMapper::new(input, block_parser)
+}
@@ -94,6 +94,127 @@ impl LineNumber {
     }
 }
 
+/// 源码中的一段范围（以字节计）。仅在启用 `source-span` 特性时才记录实际的值。
+///
+/// 对于进入某元素的事件，范围是该元素的开启部分；对于指示事件，范围是指示标记；对于退出
+/// 某元素的事件，范围是整个元素，从开启部分的开头直到闭合部分的末尾（没有闭合部分时，直到
+/// 最后的内容的末尾）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan(
+    #[cfg(feature = "source-span")] usize,
+    #[cfg(feature = "source-span")] usize,
+);
+impl SourceSpan {
+    #[cfg(feature = "source-span")]
+    pub fn new(start: usize, end: usize) -> Self {
+        Self(start, end)
+    }
+    #[cfg(not(feature = "source-span"))]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self()
+    }
+    pub fn new_universal(
+        #[allow(unused_variables)] start: usize,
+        #[allow(unused_variables)] end: usize,
+    ) -> Self {
+        #[cfg(feature = "source-span")]
+        {
+            Self::new(start, end)
+        }
+        #[cfg(not(feature = "source-span"))]
+        {
+            Self::new()
+        }
+    }
+    #[cfg(any(test, feature = "test"))]
+    pub fn new_invalid() -> Self {
+        Self::new_universal(99999999, 99999999)
+    }
+
+    /// 返回开头不变、末尾为 `end`（但不早于原先的末尾）的范围。
+    pub(crate) fn with_end(self, #[allow(unused_variables)] end: usize) -> Self {
+        #[cfg(feature = "source-span")]
+        {
+            Self::new(self.0, end.max(self.1))
+        }
+        #[cfg(not(feature = "source-span"))]
+        {
+            self
+        }
+    }
+
+    #[cfg(feature = "source-span")]
+    pub fn start(&self) -> usize {
+        self.0
+    }
+    #[cfg(feature = "source-span")]
+    pub fn end(&self) -> usize {
+        self.1
+    }
+    #[cfg(feature = "source-span")]
+    pub fn range(&self) -> core::ops::Range<usize> {
+        self.0..self.1
+    }
+}
+
+/// 源码中的位置。仅在启用 `position` 特性时才记录实际的值。
+///
+/// 行与列均从 1 开始计。列分别以字节与 UTF-16 码元计，后者便于 JavaScript 一侧（如编辑
+/// 器）直接使用。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position(
+    #[cfg(feature = "position")] usize,
+    #[cfg(feature = "position")] usize,
+    #[cfg(feature = "position")] usize,
+);
+impl Position {
+    #[cfg(feature = "position")]
+    pub fn new(line: usize, column: usize, utf16_column: usize) -> Self {
+        Self(line, column, utf16_column)
+    }
+    #[cfg(not(feature = "position"))]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self()
+    }
+    pub fn new_universal(
+        #[allow(unused_variables)] line: usize,
+        #[allow(unused_variables)] column: usize,
+        #[allow(unused_variables)] utf16_column: usize,
+    ) -> Self {
+        #[cfg(feature = "position")]
+        {
+            Self::new(line, column, utf16_column)
+        }
+        #[cfg(not(feature = "position"))]
+        {
+            Self::new()
+        }
+    }
+    #[cfg(any(test, feature = "test"))]
+    pub fn new_invalid() -> Self {
+        Self::new_universal(99999999, 99999999, 99999999)
+    }
+    /// 尚待解析器根据事件的范围填入实际的值。
+    pub(crate) fn new_unresolved() -> Self {
+        Self::new_universal(0, 0, 0)
+    }
+
+    #[cfg(feature = "position")]
+    pub fn line(&self) -> usize {
+        self.0
+    }
+    #[cfg(feature = "position")]
+    pub fn column(&self) -> usize {
+        self.1
+    }
+    #[cfg(feature = "position")]
+    pub fn utf16_column(&self) -> usize {
+        self.2
+    }
+}
+
 /// Tym = To Yield Mark. 用于确保代码执行过程中不会爆 `to_yield` 栈的辅助类型。
 pub struct Tym<const N: usize>;
 pub const TYM_UNIT: Tym<0> = Tym::<0> {};
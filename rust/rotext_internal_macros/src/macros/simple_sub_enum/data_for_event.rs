@@ -38,6 +38,7 @@ static EVENT_TO_GROUP_RAW: &[(&str, &[&str])] = &[
     ("EnterTable", &["Block", "Blend"]),
     ("EnterCallOnTemplate", &["Block", "Inline", "Blend"]),
     ("EnterCallOnExtension", &["Block", "Inline", "Blend"]),
+    ("EnterParameterFilling", &["Block", "Inline", "Blend"]),
     ("IndicateCodeBlockCode", &["Block", "Blend"]),
     ("IndicateTableCaption", &["Block", "Blend"]),
     ("IndicateTableRow", &["Block", "Blend"]),
@@ -47,16 +48,23 @@ static EVENT_TO_GROUP_RAW: &[(&str, &[&str])] = &[
     ("IndicateCallVerbatimArgument", &[
         "Block", "Inline", "Blend",
     ]),
+    ("IndicateParameterFillingFallback", &[
+        "Block", "Inline", "Blend",
+    ]),
+    ("BlockAttachedInfo", &["Block", "Blend"]),
     ("ExitBlock", &["Block", "Blend"]),
     ("RefLink", &["Inline", "Blend"]),
-    ("Dicexp", &["Inline", "Blend"]),
+    ("ExternalLink", &["Inline", "Blend"]),
     ("EnterCodeSpan", &["Inline", "Blend"]),
     ("EnterEmphasis", &["Inline", "Blend"]),
     ("EnterStrong", &["Inline", "Blend"]),
     ("EnterStrikethrough", &["Inline", "Blend"]),
     ("EnterRuby", &["Inline", "Blend"]),
     ("EnterRubyText", &["Inline", "Blend"]),
+    ("EnterDicexp", &["Inline", "Blend"]),
+    ("EnterAssigningDicexp", &["Inline", "Blend"]),
     ("EnterWikiLink", &["Inline", "Blend"]),
+    ("InlineAttachedInfo", &["Inline", "Blend"]),
     ("ExitInline", &["Inline", "Blend"]),
 ];
 
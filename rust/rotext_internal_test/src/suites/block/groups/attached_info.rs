@@ -0,0 +1,295 @@
+use indoc::indoc;
+
+use rotext_core::EventType;
+
+use crate::suites::block::support::{GroupedCases, case};
+
+pub fn groups_attached_info() -> Vec<GroupedCases> {
+    vec![
+        GroupedCases {
+            group: "块级附加信息>整体",
+            cases: vec![
+                case!(
+                    vec![
+                        "{&>␣.foo}\na",
+                        "{&>␣.foo}a",
+                        "{&>␣.foo}␣a",
+                        "␣{&>␣.foo}\n\na",
+                    ],
+                    vec![
+                        (EventType::BlockAttachedInfo, Some(" .foo")),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec!["{&>.foo␣}\na"], vec![
+                    (EventType::BlockAttachedInfo, Some(".foo ")),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.foo␣bar=\"baz␣}\"␣.qux}---"], vec![
+                    (
+                        EventType::BlockAttachedInfo,
+                        Some(" .foo bar=\"baz }\" .qux")
+                    ),
+                    (EventType::ThematicBreak, None),
+                ]),
+                case!(vec!["{&>␣.foo}{&>␣.bar}\n==␣a␣=="], vec![
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::BlockAttachedInfo, Some(" .bar")),
+                    (EventType::EnterHeading2, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    vec![indoc! {"
+                        {&>␣.foo}
+                        *␣a
+                        {&>␣.bar}
+                        *␣b"}],
+                    vec![
+                        (EventType::BlockAttachedInfo, Some(" .foo")),
+                        (EventType::EnterUnorderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::BlockAttachedInfo, Some(" .bar")),
+                        (EventType::EnterUnorderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec![">␣a\n{&>␣.foo}␣#␣b"], vec![
+                    (EventType::EnterBlockQuote, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterOrderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("b")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    vec!["*␣a\n{&>␣.foo}␣#␣b", "*␣a\n{&>␣.foo}\n#␣b"],
+                    vec![
+                        (EventType::EnterUnorderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::BlockAttachedInfo, Some(" .foo")),
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec![">␣*␣a\n>␣{&>␣.foo}␣#␣b"], vec![
+                    (EventType::EnterBlockQuote, None),
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterOrderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("b")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.foo}{{#Div||a}}"], vec![
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterCallOnExtension, Some("Div")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "块级附加信息>槽位",
+            cases: vec![
+                case!(vec!["*␣{&<␣.foo}␣a"], vec![
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec![">␣{&<␣.foo}{&<␣.bar}␣a"], vec![
+                    (EventType::EnterBlockQuote, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::BlockAttachedInfo, Some(" .bar")),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["*␣{&<␣.foo}␣*␣a"], vec![
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    vec![indoc! {"
+                        {|
+                        !!␣{&<␣colspan=\"2\"}␣a
+                        |-
+                        ||␣{&<␣.foo}␣b␣||␣c
+                        |}"}],
+                    vec![
+                        (EventType::EnterTable, None),
+                        (EventType::IndicateTableHeaderCell, None),
+                        (EventType::BlockAttachedInfo, Some(" colspan=\"2\"")),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::IndicateTableRow, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::BlockAttachedInfo, Some(" .foo")),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("c")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec!["{{#Div||{&<␣.foo}␣a}}"], vec![
+                    (EventType::EnterCallOnExtension, Some("Div")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "块级附加信息>不匹配",
+            cases: vec![
+                case!(vec!["{&<␣.foo}␣a"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&< .foo} a")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["a\n{&>␣.foo}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::NewLine, None),
+                    (EventType::__Unparsed, Some("{&> .foo}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&>}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> }")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣foo}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> foo}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> .}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.foo.bar}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> .foo.bar}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.类}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> .类}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣键=\"值\"}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> 键=\"值\"}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.foo"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> .foo")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣foo=bar}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> foo=bar}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣foo=\"bar}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> foo=\"bar}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣foo=\"bar\"baz}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> foo=\"bar\"baz}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{&>␣.foo\n}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{&> .foo")),
+                    (EventType::NewLine, None),
+                    (EventType::__Unparsed, Some("}")),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
+    ]
+}
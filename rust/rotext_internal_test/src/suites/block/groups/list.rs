@@ -1,6 +1,6 @@
 use indoc::indoc;
 
-use rotext_core::EventType;
+use rotext_core::{EventType, events::OrderedListNumbering};
 
 use crate::suites::block::support::{GroupedCases, case};
 
@@ -18,7 +18,7 @@ pub fn groups_list() -> Vec<GroupedCases> {
                     (EventType::ExitBlock, None),
                     (EventType::ExitBlock, None),
                 ]),
-                case!(vec!["*␠1"], vec![
+                case!(@list_items = vec![None], vec!["*␠1"], vec![
                     (EventType::EnterUnorderedList, None),
                     (EventType::EnterListItem, None),
                     (EventType::EnterParagraph, None),
@@ -576,5 +576,236 @@ pub fn groups_list() -> Vec<GroupedCases> {
                 ),
             ],
         },
+        GroupedCases {
+            group: "列表>任务列表项",
+            cases: vec![
+                case!(@list_items = vec![Some(false)], vec!["*␠[␣]␠a"], vec![
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    @list_items = vec![Some(true)],
+                    vec!["*␠[x]␠a", "*␠[X]␠a", "*␠[x]␠␠a"],
+                    vec![
+                        (EventType::EnterUnorderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(@list_items = vec![Some(false)], vec!["#␠[␣]"], vec![
+                    (EventType::EnterOrderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(@list_items = vec![Some(true)], vec!["#␠[x]␠"], vec![
+                    (EventType::EnterOrderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    @list_items = vec![Some(false), Some(true)],
+                    vec![indoc! {"
+                        *␠[␣]␠a
+                        *␠[x]␠b"},],
+                    vec![
+                        (EventType::EnterUnorderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(@list_items = vec![Some(false)], vec!["*␠[␣]␠{&<␣.foo}␠a"], vec![
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::BlockAttachedInfo, Some(" .foo")),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "列表>有序列表的序号",
+            cases: vec![
+                case!(@ordered_lists = vec![(None, None)], vec!["#␣a"], vec![
+                    (EventType::EnterOrderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    @ordered_lists = vec![(Some(3), Some(OrderedListNumbering::LowerRoman))],
+                    vec![r#"#␣{&<␣start="3"␣type="i"}␣a"#],
+                    vec![
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="3" type="i""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(
+                    @ordered_lists = vec![(None, Some(OrderedListNumbering::UpperAlpha))],
+                    vec![r#"#␣{&<␣.foo}{&<␣type="A"}␣a"#],
+                    vec![
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(" .foo")),
+                        (EventType::BlockAttachedInfo, Some(r#" type="A""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                // 只有第一个列表项的附加信息会被采用。
+                case!(
+                    @ordered_lists = vec![(Some(3), None)],
+                    vec![indoc! {r#"
+                        #␣{&<␣start="3"}␣a
+                        #␣{&<␣start="5"}␣b"#},],
+                    vec![
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="3""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="5""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(
+                    @ordered_lists = vec![(None, None)],
+                    vec![indoc! {r#"
+                        #␣a
+                        #␣{&<␣start="5"}␣b"#},],
+                    vec![
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="5""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(
+                    @ordered_lists = vec![(Some(0), None), (Some(2), None)],
+                    vec![indoc! {r#"
+                        >␣#␣{&<␣start="0"}␣a
+                        >␣#␣#␣{&<␣start="2"}␣b"#},],
+                    vec![
+                        (EventType::EnterBlockQuote, None),
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="0""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="2""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                // 作用于后方内容的附加信息不会被采用。
+                case!(
+                    @ordered_lists = vec![(None, None)],
+                    vec![r#"#␣{&>␣start="3"}␣a"#],
+                    vec![
+                        (EventType::EnterOrderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::BlockAttachedInfo, Some(r#" start="3""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "列表>任务列表项>不匹配",
+            cases: vec![
+                case!(@list_items = vec![None], vec!["*␣[x]a", "*␣␣[x]a"], vec![
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("[x]a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(@list_items = vec![None], vec!["*␣[y]␣a"], vec![
+                    (EventType::EnterUnorderedList, None),
+                    (EventType::EnterListItem, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("[y] a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec![";␣[x]␣a"], vec![
+                    (EventType::EnterDescriptionList, None),
+                    (EventType::EnterDescriptionTerm, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("[x] a")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
     ]
 }
@@ -1,3 +1,4 @@
+pub mod attached_info;
 pub mod block_quote;
 pub mod call;
 pub mod code_block;
@@ -6,4 +7,5 @@ pub mod heading;
 pub mod horizontal_rule;
 pub mod list;
 pub mod paragraph;
+pub mod parameter_filling;
 pub mod table;
@@ -0,0 +1,207 @@
+use indoc::indoc;
+
+use rotext_core::EventType;
+
+use crate::suites::block::support::{GroupedCases, case};
+
+pub fn groups_parameter_filling() -> Vec<GroupedCases> {
+    vec![
+        GroupedCases {
+            group: "参数填充",
+            cases: vec![
+                case!(vec!["{{{foo}}}", "{{{␣foo␣}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo␣bar}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo bar")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo||}}}", "{{{foo||\n}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    vec!["{{{foo||bar}}}", "{{{␣foo␣||␣bar␣}}}", "{{{foo||\nbar\n}}}"],
+                    vec![
+                        (EventType::EnterParameterFilling, Some("foo")),
+                        (EventType::IndicateParameterFillingFallback, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("bar")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec!["{{{foo||bar}}baz}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("bar}}baz")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo}}}\nbar"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::ExitBlock, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("bar")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    vec![indoc! {"
+                        {{{foo||
+                        *␣bar
+                        }}}"}],
+                    vec![
+                        (EventType::EnterParameterFilling, Some("foo")),
+                        (EventType::IndicateParameterFillingFallback, None),
+                        (EventType::EnterUnorderedList, None),
+                        (EventType::EnterListItem, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("bar")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec!["{{{foo||a||b}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a||b")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo||a␣||␣b}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a || b")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(
+                    vec![indoc! {"
+                        {{{foo||a
+                        ||b}}}"}],
+                    vec![
+                        (EventType::EnterParameterFilling, Some("foo")),
+                        (EventType::IndicateParameterFillingFallback, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::NewLine, None),
+                        (EventType::__Unparsed, Some("||b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+            ],
+        },
+        GroupedCases {
+            group: "参数填充>嵌套",
+            cases: vec![
+                case!(
+                    vec![indoc! {"
+                        {{{foo||{|
+                        ||␣a␣||␣b
+                        |}}}}"}],
+                    vec![
+                        (EventType::EnterParameterFilling, Some("foo")),
+                        (EventType::IndicateParameterFillingFallback, None),
+                        (EventType::EnterTable, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(vec!["{|␣||␣{{{foo||a||b}}}␣||␣c␣|}"], vec![
+                    (EventType::EnterTable, None),
+                    (EventType::IndicateTableDataCell, None),
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("a||b")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::IndicateTableDataCell, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("c")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo||{{bar}}}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterCallOnTemplate, Some("bar")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo||{{bar||baz}}}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterCallOnTemplate, Some("bar")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("baz")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{bar||{{{foo}}}}}"], vec![
+                    (EventType::EnterCallOnTemplate, Some("bar")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo||{{{bar||baz}}}}}}"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParameterFilling, Some("bar")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("baz")),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "参数填充>不匹配",
+            cases: vec![
+                case!(vec!["{{{}}}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{{{}}}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{␣}}}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{{{ }}}")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{{{foo")),
+                    (EventType::ExitBlock, None),
+                ]),
+                case!(vec!["{{{foo\n}}}"], vec![
+                    (EventType::EnterParagraph, None),
+                    (EventType::__Unparsed, Some("{{{foo")),
+                    (EventType::NewLine, None),
+                    (EventType::__Unparsed, Some("}}}")),
+                    (EventType::ExitBlock, None),
+                ]),
+            ],
+        },
+    ]
+}
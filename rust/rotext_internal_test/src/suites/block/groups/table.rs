@@ -188,6 +188,74 @@ pub fn groups_table() -> Vec<GroupedCases> {
                 ),
             ],
         },
+        GroupedCases {
+            group: "表格>单元格的跨列与跨行",
+            cases: vec![
+                case!(
+                    @table_cells = vec![(Some(2), Some(3)), (None, None), (None, Some(2)), (None, None)],
+                    vec![indoc! {r#"
+                        {|
+                        !!␣{&<␣colspan="2"␣rowspan="3"}␣a␣!!␣b
+                        |-
+                        ||␣{&<␣.foo}{&<␣rowspan="2"}␣c␣||␣{&<␣colspan="0"}␣d
+                        |}"#}],
+                    vec![
+                        (EventType::EnterTable, None),
+                        (EventType::IndicateTableHeaderCell, None),
+                        (EventType::BlockAttachedInfo, Some(r#" colspan="2" rowspan="3""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("a")),
+                        (EventType::ExitBlock, None),
+                        (EventType::IndicateTableHeaderCell, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("b")),
+                        (EventType::ExitBlock, None),
+                        (EventType::IndicateTableRow, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::BlockAttachedInfo, Some(" .foo")),
+                        (EventType::BlockAttachedInfo, Some(r#" rowspan="2""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("c")),
+                        (EventType::ExitBlock, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::BlockAttachedInfo, Some(r#" colspan="0""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("d")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(
+                    @table_cells = vec![(Some(5), None)],
+                    vec![r#"{|␣||␣{&<␣.a}{&<␣.b}␣{&<␣.c}{&<␣.d}{&<␣colspan="5"}␣e␣|}"#],
+                    vec![
+                        (EventType::EnterTable, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::BlockAttachedInfo, Some(" .a")),
+                        (EventType::BlockAttachedInfo, Some(" .b")),
+                        (EventType::BlockAttachedInfo, Some(" .c")),
+                        (EventType::BlockAttachedInfo, Some(" .d")),
+                        (EventType::BlockAttachedInfo, Some(r#" colspan="5""#)),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some("e")),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+                case!(
+                    @table_cells = vec![(None, None)],
+                    vec![r#"{|␣||␣a␣{&<␣colspan="2"}␣|}"#],
+                    vec![
+                        (EventType::EnterTable, None),
+                        (EventType::IndicateTableDataCell, None),
+                        (EventType::EnterParagraph, None),
+                        (EventType::__Unparsed, Some(r#"a {&< colspan="2"}"#)),
+                        (EventType::ExitBlock, None),
+                        (EventType::ExitBlock, None),
+                    ]
+                ),
+            ],
+        },
         GroupedCases {
             group: "表格>标题",
             cases: vec![
@@ -31,6 +31,8 @@ pub fn run<TContext: Context + RefUnwindSafe>(ctx: &TContext) {
         table.extend(groups::code_block::groups_code_block());
         table.extend(groups::table::groups_table());
         table.extend(groups::call::groups_call());
+        table.extend(groups::parameter_filling::groups_parameter_filling());
+        table.extend(groups::attached_info::groups_attached_info());
 
         table
     };
@@ -1,23 +1,35 @@
 use std::panic::{RefUnwindSafe, catch_unwind};
 
-use rotext_core::{Error, Event, EventType};
+use rotext_core::{Error, Event, EventType, events::OrderedListNumbering};
 
 use crate::support::{FailedCase, FailureReason, make_whitespace_variants};
 
 macro_rules! case {
     ($input_variants:expr, $expected:expr) => {
-        case!(@__inner, $input_variants, $expected, false, false)
+        case!(@__inner, $input_variants, $expected, None, None, None, false, false)
     };
     (@todo, $input_variants:expr, $expected:expr) => {
-        case!(@__inner, $input_variants, $expected, true, false)
+        case!(@__inner, $input_variants, $expected, None, None, None, true, false)
     };
     (@only, $input_variants:expr, $expected:expr) => {
-        case!(@__inner, $input_variants, $expected, false, true)
+        case!(@__inner, $input_variants, $expected, None, None, None, false, true)
     };
-    (@__inner, $input_variants:expr, $expected:expr, $flag_todo:literal, $flag_only:literal) => {
+    (@table_cells = $table_cells:expr, $input_variants:expr, $expected:expr) => {
+        case!(@__inner, $input_variants, $expected, Some($table_cells), None, None, false, false)
+    };
+    (@ordered_lists = $ordered_lists:expr, $input_variants:expr, $expected:expr) => {
+        case!(@__inner, $input_variants, $expected, None, Some($ordered_lists), None, false, false)
+    };
+    (@list_items = $list_items:expr, $input_variants:expr, $expected:expr) => {
+        case!(@__inner, $input_variants, $expected, None, None, Some($list_items), false, false)
+    };
+    (@__inner, $input_variants:expr, $expected:expr, $table_cells:expr, $ordered_lists:expr, $list_items:expr, $flag_todo:literal, $flag_only:literal) => {
         $crate::suites::block::support::Case {
             input_variants: $input_variants,
             expected: $expected,
+            expected_table_cells: $table_cells,
+            expected_ordered_lists: $ordered_lists,
+            expected_list_items: $list_items,
             flags: $crate::suites::block::support::Flags {
                 to_do: $flag_todo,
                 only: $flag_only,
@@ -69,6 +81,13 @@ impl GroupedCases {
 pub(super) struct Case {
     pub input_variants: Vec<&'static str>,
     pub expected: Vec<EventMatcher>,
+    /// 为 `Some` 时，还会检查各个表格单元格的指示事件所携带的数据，见 [TableCellMatcher]。
+    pub expected_table_cells: Option<Vec<TableCellMatcher>>,
+    /// 为 `Some` 时，还会检查各个有序列表的进入事件所携带的数据，见
+    /// [OrderedListMatcher]。
+    pub expected_ordered_lists: Option<Vec<OrderedListMatcher>>,
+    /// 为 `Some` 时，还会检查各个列表项的进入事件所携带的数据，见 [ListItemMatcher]。
+    pub expected_list_items: Option<Vec<ListItemMatcher>>,
     pub flags: Flags,
 }
 pub(super) struct Flags {
@@ -133,7 +152,15 @@ impl Case {
                 let panic = {
                     let input = input.clone();
                     catch_unwind(|| {
-                        assert_auto_variant_ok(ctx, auto_variant.clone(), input, &self.expected)
+                        assert_auto_variant_ok(
+                            ctx,
+                            auto_variant.clone(),
+                            input,
+                            &self.expected,
+                            self.expected_table_cells.as_ref(),
+                            self.expected_ordered_lists.as_ref(),
+                            self.expected_list_items.as_ref(),
+                        )
                     })
                     .err()
                 }?;
@@ -171,12 +198,21 @@ impl Case {
 }
 
 type EventMatcher = (EventType, Option<&'static str>);
+/// 表格单元格的 `colspan` 与 `rowspan`。
+type TableCellMatcher = (Option<usize>, Option<usize>);
+/// 有序列表的 `start` 与 `numbering`。
+type OrderedListMatcher = (Option<usize>, Option<OrderedListNumbering>);
+/// 列表项的 `checked`。
+type ListItemMatcher = Option<bool>;
 
 fn assert_auto_variant_ok<TContext: Context>(
     ctx: &InternalContext<TContext>,
     variant: AutoVariant,
     input: String,
     expected: &Vec<EventMatcher>,
+    expected_table_cells: Option<&Vec<TableCellMatcher>>,
+    expected_ordered_lists: Option<&Vec<OrderedListMatcher>>,
+    expected_list_items: Option<&Vec<ListItemMatcher>>,
 ) {
     let input = match variant {
         AutoVariant::Normal => input.to_string(),
@@ -184,7 +220,51 @@ fn assert_auto_variant_ok<TContext: Context>(
         AutoVariant::WithTrailingLineFeed => format!("{}\n", input),
     };
 
-    assert_parse_ok_and_output_matches(ctx.external, &input, expected)
+    assert_parse_ok_and_output_matches(ctx.external, &input, expected);
+    if let Some(expected_table_cells) = expected_table_cells {
+        assert_table_cells_match::<TContext>(&input, expected_table_cells);
+    }
+    if let Some(expected_ordered_lists) = expected_ordered_lists {
+        assert_ordered_lists_match::<TContext>(&input, expected_ordered_lists);
+    }
+    if let Some(expected_list_items) = expected_list_items {
+        assert_list_items_match::<TContext>(&input, expected_list_items);
+    }
+}
+
+fn assert_table_cells_match<TContext: Context>(input: &str, expected: &Vec<TableCellMatcher>) {
+    let actual: Vec<_> = TContext::parse(input)
+        .filter_map(|ev| match ev.unwrap() {
+            Event::IndicateTableHeaderCell(cell) | Event::IndicateTableDataCell(cell) => {
+                Some((cell.colspan.map(|n| n.get()), cell.rowspan.map(|n| n.get())))
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(expected, &actual)
+}
+
+fn assert_ordered_lists_match<TContext: Context>(input: &str, expected: &Vec<OrderedListMatcher>) {
+    let actual: Vec<_> = TContext::parse(input)
+        .filter_map(|ev| match ev.unwrap() {
+            Event::EnterOrderedList(list) => Some((list.start, list.numbering)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(expected, &actual)
+}
+
+fn assert_list_items_match<TContext: Context>(input: &str, expected: &Vec<ListItemMatcher>) {
+    let actual: Vec<_> = TContext::parse(input)
+        .filter_map(|ev| match ev.unwrap() {
+            Event::EnterListItem(item) => Some(item.checked),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(expected, &actual)
 }
 
 #[derive(Clone)]
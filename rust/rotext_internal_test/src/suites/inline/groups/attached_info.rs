@@ -0,0 +1,104 @@
+use rotext_core::EventType;
+
+use crate::suites::inline::support::{GroupedCases, case};
+
+pub fn groups_attached_info() -> Vec<GroupedCases> {
+    vec![
+        GroupedCases {
+            group: "行内附加信息>整体",
+            cases: vec![
+                case!(vec!["[&>␣.foo][;a:b]"], vec![
+                    (EventType::InlineAttachedInfo, Some(" .foo")),
+                    (EventType::EnterRuby, None),
+                    (EventType::Text, Some("a")),
+                    (EventType::EnterRubyText, None),
+                    (EventType::Text, Some("b")),
+                    (EventType::ExitInline, None),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["a[&>␣.foo␣bar=\"]\"][*b*]c"], vec![
+                    (EventType::Text, Some("a")),
+                    (EventType::InlineAttachedInfo, Some(" .foo bar=\"]\"")),
+                    (EventType::EnterStrong, None),
+                    (EventType::Text, Some("b")),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("c")),
+                ]),
+                case!(vec!["[&>␣.foo][&>␣.bar][{#span|a}]"], vec![
+                    (EventType::InlineAttachedInfo, Some(" .foo")),
+                    (EventType::InlineAttachedInfo, Some(" .bar")),
+                    (EventType::EnterCallOnExtension, Some("span")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::Text, Some("a")),
+                    (EventType::ExitInline, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "行内附加信息>槽位",
+            cases: vec![
+                case!(vec!["[*[&<␣.foo]a*]"], vec![
+                    (EventType::EnterStrong, None),
+                    (EventType::InlineAttachedInfo, Some(" .foo")),
+                    (EventType::Text, Some("a")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[;[&<␣.foo]a:[&<␣.bar][&<␣.baz]b]"], vec![
+                    (EventType::EnterRuby, None),
+                    (EventType::InlineAttachedInfo, Some(" .foo")),
+                    (EventType::Text, Some("a")),
+                    (EventType::EnterRubyText, None),
+                    (EventType::InlineAttachedInfo, Some(" .bar")),
+                    (EventType::InlineAttachedInfo, Some(" .baz")),
+                    (EventType::Text, Some("b")),
+                    (EventType::ExitInline, None),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{#span|[&<␣.foo]a}]"], vec![
+                    (EventType::EnterCallOnExtension, Some("span")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::InlineAttachedInfo, Some(" .foo")),
+                    (EventType::Text, Some("a")),
+                    (EventType::ExitInline, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "行内附加信息>不匹配",
+            cases: vec![
+                case!(vec!["[&<␣.foo]"], vec![(
+                    EventType::Text,
+                    Some("[&< .foo]")
+                ),]),
+                case!(vec!["[*a[&<␣.foo]*]"], vec![
+                    (EventType::EnterStrong, None),
+                    (EventType::Text, Some("a[&< .foo]")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[*[/*][&<␣.foo]"], vec![
+                    (EventType::EnterStrong, None),
+                    (EventType::EnterEmphasis, None),
+                    (EventType::ExitInline, None),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("[&< .foo]")),
+                ]),
+                case!(vec!["[&>]"], vec![(EventType::Text, Some("[&>]")),]),
+                case!(vec!["[&>␣]"], vec![(EventType::Text, Some("[&> ]")),]),
+                case!(vec!["[&>␣foo]"], vec![(
+                    EventType::Text,
+                    Some("[&> foo]")
+                ),]),
+                case!(vec!["[&>␣.foo"], vec![(
+                    EventType::Text,
+                    Some("[&> .foo")
+                ),]),
+                case!(vec!["[&>␣.foo\n]"], vec![
+                    (EventType::Text, Some("[&> .foo")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("]")),
+                ]),
+                case!(vec!["[&]"], vec![(EventType::Text, Some("[&]")),]),
+            ],
+        },
+    ]
+}
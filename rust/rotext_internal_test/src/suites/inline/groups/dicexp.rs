@@ -3,21 +3,113 @@ use rotext_core::EventType;
 use crate::suites::inline::support::{GroupedCases, case};
 
 pub fn groups_dicexp() -> Vec<GroupedCases> {
-    vec![GroupedCases {
-        group: "骰子表达式",
-        cases: vec![
-            case!(vec!["[=d100]", "[=d100"], vec![(
-                EventType::Dicexp,
-                Some("d100")
-            ),]),
-            case!(vec!["abc[=d100]"], vec![
-                (EventType::Text, Some("abc")),
-                (EventType::Dicexp, Some("d100")),
-            ]),
-            case!(vec!["[=d100]abc"], vec![
-                (EventType::Dicexp, Some("d100")),
-                (EventType::Text, Some("abc")),
-            ]),
-        ],
-    }]
+    vec![
+        GroupedCases {
+            group: "骰子表达式",
+            cases: vec![
+                case!(vec!["[=d100]", "[=d100"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("d100")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["abc[=d100]"], vec![
+                    (EventType::Text, Some("abc")),
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("d100")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[=d100]abc"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("d100")),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("abc")),
+                ]),
+                case!(vec!["[=]"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[=[d]100]]"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("[d]100")),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("]")),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "骰子表达式>多行",
+            cases: vec![
+                case!(vec!["[=d100\n+1]"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("d100")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("+1")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[=\nd100]"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("d100")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[=[d\n100]]abc"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("[d")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("100]")),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("abc")),
+                ]),
+                case!(vec!["[=d\n100\n+1"], vec![
+                    (EventType::EnterDicexp, None),
+                    (EventType::Text, Some("d")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("100")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("+1")),
+                    (EventType::ExitInline, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "骰子表达式>赋值",
+            cases: vec![
+                case!(vec!["[@_balls=d100]", "[@_balls=d100"], vec![
+                    (EventType::EnterAssigningDicexp, Some("_balls")),
+                    (EventType::Text, Some("d100")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["a[@_redBalls=d(@_balls)]b"], vec![
+                    (EventType::Text, Some("a")),
+                    (EventType::EnterAssigningDicexp, Some("_redBalls")),
+                    (EventType::Text, Some("d(@_balls)")),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("b")),
+                ]),
+                case!(vec!["[@foo.bar=[d]\n6]"], vec![
+                    (EventType::EnterAssigningDicexp, Some("foo.bar")),
+                    (EventType::Text, Some("[d]")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("6")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[@=d100]"], vec![(EventType::Text, Some("[@=d100]")),]),
+                case!(vec!["[@foo]"], vec![(EventType::Text, Some("[@foo]")),]),
+                case!(vec!["[@foo␣=d100]"], vec![(
+                    EventType::Text,
+                    Some("[@foo =d100]")
+                ),]),
+            ],
+        },
+        GroupedCases {
+            group: "骰子表达式>逐字转义",
+            cases: vec![case!(vec!["[=d<`100`>]"], vec![
+                (EventType::EnterDicexp, None),
+                (EventType::Text, Some("d")),
+                (EventType::ExitInline, None),
+                (EventType::VerbatimEscaping, Some("100")),
+                (EventType::Text, Some("]")),
+            ])],
+        },
+    ]
 }
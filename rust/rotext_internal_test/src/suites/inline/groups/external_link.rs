@@ -0,0 +1,138 @@
+use rotext_core::EventType;
+
+use crate::suites::inline::support::{GroupedCases, case};
+
+pub fn groups_external_link() -> Vec<GroupedCases> {
+    vec![
+        GroupedCases {
+            group: "自动外部链接",
+            cases: vec![
+                case!(vec!["https://example.com"], vec![(
+                    EventType::ExternalLink,
+                    Some("https://example.com")
+                ),]),
+                case!(vec!["http://example.com/a?b=c&d=e#f"], vec![(
+                    EventType::ExternalLink,
+                    Some("http://example.com/a?b=c&d=e#f")
+                ),]),
+                case!(vec!["a␣https://example.com␣b"], vec![
+                    (EventType::Text, Some("a ")),
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some(" b")),
+                ]),
+                case!(vec!["见https://example.com/维基"], vec![
+                    (EventType::Text, Some("见")),
+                    (EventType::ExternalLink, Some("https://example.com/维基")),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "自动外部链接>末尾标点",
+            cases: vec![
+                case!(vec!["https://example.com."], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some(".")),
+                ]),
+                case!(vec!["https://example.com,"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some(",")),
+                ]),
+                case!(vec!["https://example.com?!"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some("?!")),
+                ]),
+                case!(vec!["https://example.com:"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some(":")),
+                ]),
+                case!(vec!["https://example.com;"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some(";")),
+                ]),
+                case!(vec!["https://example.com'"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some("'")),
+                ]),
+                case!(vec!["https://example.com。"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some("。")),
+                ]),
+                case!(vec!["https://example.com，a"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some("，a")),
+                ]),
+                case!(vec!["https://example.com…"], vec![
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some("…")),
+                ]),
+                case!(vec!["(https://example.com)"], vec![
+                    (EventType::Text, Some("(")),
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some(")")),
+                ]),
+                case!(vec!["https://example.com/a_(b)"], vec![(
+                    EventType::ExternalLink,
+                    Some("https://example.com/a_(b)")
+                ),]),
+                case!(vec!["（https://example.com/a）"], vec![
+                    (EventType::Text, Some("（")),
+                    (EventType::ExternalLink, Some("https://example.com/a")),
+                    (EventType::Text, Some("）")),
+                ]),
+                case!(vec!["「https://example.com」"], vec![
+                    (EventType::Text, Some("「")),
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::Text, Some("」")),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "自动外部链接>与其他行内元素",
+            cases: vec![
+                case!(vec!["[*https://example.com*]"], vec![
+                    (EventType::EnterStrong, None),
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[/https://example.com/]"], vec![
+                    (EventType::EnterEmphasis, None),
+                    (EventType::ExternalLink, Some("https://example.com")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[[https://example.com]]"], vec![
+                    (EventType::EnterWikiLink, Some("https://example.com")),
+                    (EventType::Text, Some("https://example.com")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{#ExternalLink|`https://example.com}]"], vec![
+                    (EventType::EnterCallOnExtension, Some("ExternalLink")),
+                    (EventType::IndicateCallVerbatimArgument, None),
+                    (EventType::Text, Some("https://example.com")),
+                    (EventType::ExitInline, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "自动外部链接>不匹配",
+            cases: vec![
+                case!(vec!["https://"], vec![(EventType::Text, Some("https://")),]),
+                case!(vec!["https://."], vec![(
+                    EventType::Text,
+                    Some("https://.")
+                ),]),
+                case!(vec!["ftp://example.com"], vec![(
+                    EventType::Text,
+                    Some("ftp://example.com")
+                ),]),
+                case!(vec!["ahttps://example.com"], vec![(
+                    EventType::Text,
+                    Some("ahttps://example.com")
+                ),]),
+                case!(vec!["http:example.com"], vec![(
+                    EventType::Text,
+                    Some("http:example.com")
+                ),]),
+            ],
+        },
+    ]
+}
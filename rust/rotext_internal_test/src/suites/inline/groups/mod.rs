@@ -1,7 +1,10 @@
+pub mod attached_info;
 pub mod basic;
 pub mod call;
 pub mod code_span;
 pub mod dicexp;
+pub mod external_link;
+pub mod parameter_filling;
 pub mod ref_link;
 pub mod ruby;
 pub mod text_styles;
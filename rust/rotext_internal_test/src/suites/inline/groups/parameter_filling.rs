@@ -0,0 +1,113 @@
+use rotext_core::EventType;
+
+use crate::suites::inline::support::{GroupedCases, case};
+
+pub fn groups_parameter_filling() -> Vec<GroupedCases> {
+    vec![
+        GroupedCases {
+            group: "参数填充",
+            cases: vec![
+                case!(vec!["[{{foo}}]", "[{{␣foo␣}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{{foo␣bar}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo bar")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{{foo|bar}}]", "[{{␣foo␣|␣bar}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::Text, Some("bar")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{{foo|}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["a[{{foo|[*b*]}}]c"], vec![
+                    (EventType::Text, Some("a")),
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterStrong, None),
+                    (EventType::Text, Some("b")),
+                    (EventType::ExitInline, None),
+                    (EventType::ExitInline, None),
+                    (EventType::Text, Some("c")),
+                ]),
+                case!(vec!["[{{foo|a|b}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::Text, Some("a|b")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{{foo|a\nb}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::Text, Some("a")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("b")),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{{foo|a"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::Text, Some("a")),
+                    (EventType::ExitInline, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "参数填充>嵌套",
+            cases: vec![
+                case!(vec!["[{{foo|[{{bar|a}}]}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterParameterFilling, Some("bar")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::Text, Some("a")),
+                    (EventType::ExitInline, None),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{{foo|[{bar|a}]}}]"], vec![
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::EnterCallOnTemplate, Some("bar")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::Text, Some("a")),
+                    (EventType::ExitInline, None),
+                    (EventType::ExitInline, None),
+                ]),
+                case!(vec!["[{bar|[{{foo|a|b}}]|c}]"], vec![
+                    (EventType::EnterCallOnTemplate, Some("bar")),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::EnterParameterFilling, Some("foo")),
+                    (EventType::IndicateParameterFillingFallback, None),
+                    (EventType::Text, Some("a|b")),
+                    (EventType::ExitInline, None),
+                    (EventType::IndicateCallNormalArgument, None),
+                    (EventType::Text, Some("c")),
+                    (EventType::ExitInline, None),
+                ]),
+            ],
+        },
+        GroupedCases {
+            group: "参数填充>不匹配",
+            cases: vec![
+                case!(vec!["[{{"], vec![(EventType::Text, Some("[{{")),]),
+                case!(vec!["[{{}}]"], vec![(EventType::Text, Some("[{{}}]")),]),
+                case!(vec!["[{{␣|a}}]"], vec![(
+                    EventType::Text,
+                    Some("[{{ |a}}]")
+                ),]),
+                case!(vec!["[{{foo"], vec![(EventType::Text, Some("[{{foo")),]),
+                case!(vec!["[{{foo\n}}]"], vec![
+                    (EventType::Text, Some("[{{foo")),
+                    (EventType::NewLine, None),
+                    (EventType::Text, Some("}}]")),
+                ]),
+            ],
+        },
+    ]
+}
@@ -127,7 +127,9 @@ pub fn groups_text_styles() -> Vec<GroupedCases> {
                         (EventType::Text, Some(" ")),
                         (EventType::RefLink, Some("TP.4")),
                         (EventType::NewLine, None),
-                        (EventType::Dicexp, Some("d5")),
+                        (EventType::EnterDicexp, None),
+                        (EventType::Text, Some("d5")),
+                        (EventType::ExitInline, None),
                         (EventType::ExitInline, None),
                     ]
                 ),
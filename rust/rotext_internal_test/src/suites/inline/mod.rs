@@ -21,12 +21,15 @@ pub fn run<TContext: Context + RefUnwindSafe>(ctx: &TContext) {
 
         table.extend(groups::basic::groups_basic());
         table.extend(groups::ref_link::groups_ref_link());
+        table.extend(groups::external_link::groups_external_link());
         table.extend(groups::wiki_link::groups_wiki_link());
         table.extend(groups::dicexp::groups_dicexp());
         table.extend(groups::text_styles::groups_text_styles());
         table.extend(groups::ruby::groups_ruby());
         table.extend(groups::code_span::groups_code_span());
         table.extend(groups::call::groups_call());
+        table.extend(groups::parameter_filling::groups_parameter_filling());
+        table.extend(groups::attached_info::groups_attached_info());
 
         table
     };
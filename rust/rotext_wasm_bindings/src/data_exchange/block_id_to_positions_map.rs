@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use super::position::{PositionOutput, convert_position};
+
+pub type BlockIdToPositionsMap = HashMap<usize, (PositionOutput, PositionOutput)>;
+
+pub fn create_block_id_to_positions_map(all_events: &[rotext::Event]) -> BlockIdToPositionsMap {
+    let mut result = HashMap::new();
+
+    for ev in all_events.iter() {
+        match ev {
+            rotext::Event::ThematicBreak(data) => {
+                result.insert(
+                    data.id.value(),
+                    (convert_position(&data.start), convert_position(&data.end)),
+                );
+            }
+            rotext::Event::ExitBlock(data) => {
+                result.insert(
+                    data.id.value(),
+                    (convert_position(&data.start), convert_position(&data.end)),
+                );
+            }
+            _ => continue,
+        }
+    }
+
+    result
+}
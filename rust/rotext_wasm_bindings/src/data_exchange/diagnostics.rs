@@ -0,0 +1,18 @@
+use super::position::{PositionOutput, convert_position};
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DiagnosticOutput {
+    pub code: &'static str,
+    pub range: (usize, usize),
+    pub line: usize,
+    pub position: PositionOutput,
+}
+
+pub fn convert_diagnostic(diagnostic: rotext::diagnostics::Diagnostic) -> DiagnosticOutput {
+    DiagnosticOutput {
+        code: diagnostic.code.name(),
+        range: (diagnostic.range.start, diagnostic.range.end),
+        line: diagnostic.line.value(),
+        position: convert_position(&diagnostic.position),
+    }
+}
@@ -9,6 +9,9 @@ use rotext::executing::extensions::{
 #[derive(Debug, serde::Deserialize)]
 pub enum ExtensionInput {
     ElementMapper(ExtensionElementMapperInput),
+    FootnoteReference { name: String },
+    FootnoteList { name: String },
+    TableOfContents { name: String },
     Alias { name: String, to: String },
 }
 
@@ -149,6 +152,15 @@ pub fn convert_to_extension_map(
             ExtensionInput::ElementMapper(item) => {
                 result.insert(item.name.as_bytes(), item.convert());
             }
+            ExtensionInput::FootnoteReference { name } => {
+                result.insert(name.as_bytes(), Extension::FootnoteReference);
+            }
+            ExtensionInput::FootnoteList { name } => {
+                result.insert(name.as_bytes(), Extension::FootnoteList);
+            }
+            ExtensionInput::TableOfContents { name } => {
+                result.insert(name.as_bytes(), Extension::TableOfContents);
+            }
             ExtensionInput::Alias { name, to } => {
                 alias_map.insert(name.as_bytes(), to.as_bytes());
             }
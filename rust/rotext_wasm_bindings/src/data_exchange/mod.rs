@@ -1,5 +1,10 @@
 pub mod block_id_to_lines_map;
+pub mod block_id_to_positions_map;
+pub mod diagnostics;
 pub mod extension;
+pub mod outline;
+pub mod position;
+pub mod statistics;
 pub mod tag_name_map;
 
 #[cfg(debug_assertions)]
@@ -0,0 +1,27 @@
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OutlineItemOutput {
+    pub level: u8,
+    pub text: String,
+    pub id: String,
+    pub block_id: usize,
+    pub lines: (usize, usize),
+    pub children: Vec<OutlineItemOutput>,
+}
+
+pub fn create_outline(input: &[u8], all_events: &[rotext::Event]) -> Vec<OutlineItemOutput> {
+    convert(rotext::headings::outline(input, all_events))
+}
+
+fn convert(items: Vec<rotext::headings::OutlineItem>) -> Vec<OutlineItemOutput> {
+    items
+        .into_iter()
+        .map(|item| OutlineItemOutput {
+            level: item.level,
+            text: item.text,
+            id: item.id,
+            block_id: item.block_id.value(),
+            lines: (item.start_line.value(), item.end_line.value()),
+            children: convert(item.children),
+        })
+        .collect()
+}
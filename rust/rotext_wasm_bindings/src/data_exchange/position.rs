@@ -0,0 +1,14 @@
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct PositionOutput {
+    pub line: usize,
+    pub column: usize,
+    pub utf16_column: usize,
+}
+
+pub fn convert_position(position: &rotext::Position) -> PositionOutput {
+    PositionOutput {
+        line: position.line(),
+        column: position.column(),
+        utf16_column: position.utf16_column(),
+    }
+}
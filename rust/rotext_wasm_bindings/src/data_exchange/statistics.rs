@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Clone, serde::Serialize)]
+pub struct StatisticsOutput {
+    pub document: StatisticsItemOutput,
+    pub blocks: HashMap<usize, StatisticsItemOutput>,
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize)]
+pub struct StatisticsItemOutput {
+    pub characters: usize,
+    pub cjk_characters: usize,
+    pub latin_words: usize,
+    pub links: usize,
+    pub calls: usize,
+    pub tables: usize,
+    pub code_blocks: usize,
+}
+
+pub fn create_statistics(input: &[u8], all_events: &[rotext::Event]) -> StatisticsOutput {
+    let stats = rotext::statistics::collect_statistics(input, all_events);
+
+    StatisticsOutput {
+        document: convert(stats.document),
+        blocks: stats
+            .blocks
+            .into_iter()
+            .map(|block| (block.id.value(), convert(block.statistics)))
+            .collect(),
+    }
+}
+
+fn convert(stats: rotext::statistics::Statistics) -> StatisticsItemOutput {
+    StatisticsItemOutput {
+        characters: stats.characters,
+        cjk_characters: stats.cjk_characters,
+        latin_words: stats.latin_words,
+        links: stats.links,
+        calls: stats.calls,
+        tables: stats.tables,
+        code_blocks: stats.code_blocks,
+    }
+}
@@ -8,6 +8,7 @@ pub struct TagNameMapInput {
     pub code_block: String,
 
     pub ref_link: String,
+    pub external_link: String,
     pub dicexp: String,
     pub wiki_link: String,
 }
@@ -20,6 +21,7 @@ impl TagNameMapInput {
             code_block: self.code_block.as_bytes(),
 
             ref_link: self.ref_link.as_bytes(),
+            external_link: self.external_link.as_bytes(),
             dicexp: self.dicexp.as_bytes(),
             wiki_link: self.wiki_link.as_bytes(),
         }
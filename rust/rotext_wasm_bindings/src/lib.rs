@@ -2,8 +2,12 @@ mod data_exchange;
 
 extern crate alloc;
 
+use std::cell::RefCell;
+
 use data_exchange::{
-    block_id_to_lines_map::create_block_id_to_lines_map, extension::convert_to_extension_map,
+    block_id_to_lines_map::create_block_id_to_lines_map,
+    block_id_to_positions_map::create_block_id_to_positions_map, diagnostics::convert_diagnostic,
+    extension::convert_to_extension_map, outline::create_outline, statistics::create_statistics,
 };
 
 #[cfg(debug_assertions)]
@@ -29,12 +33,28 @@ pub struct ParseAndRenderOptions {
     pub block_extension_list: Vec<data_exchange::extension::ExtensionInput>,
     pub inline_extension_list: Vec<data_exchange::extension::ExtensionInput>,
     pub should_include_block_ids: bool,
+    /// 是否将代码块渲染为标准的 `<pre><code>`，而非 `tag_name_map` 中的 `code_block`。
+    #[serde(default)]
+    pub should_render_code_blocks_as_pre_code: bool,
+    /// 是否生成文档大纲（`outline`）。
+    #[serde(default)]
+    pub should_include_outline: bool,
+    /// 是否生成文档统计（`statistics`）。
+    #[serde(default)]
+    pub should_include_statistics: bool,
 }
 
 #[derive(Default, Clone, serde::Serialize)]
 pub struct ParseAndRenderOutput {
     pub html: String,
     pub block_id_to_lines_map: data_exchange::block_id_to_lines_map::BlockIdToLInesMap,
+    /// 各块级元素开头与末尾的位置，比 `block_id_to_lines_map` 更精确。
+    pub block_id_to_positions_map: data_exchange::block_id_to_positions_map::BlockIdToPositionsMap,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline: Option<Vec<data_exchange::outline::OutlineItemOutput>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<data_exchange::statistics::StatisticsOutput>,
+    pub diagnostics: Vec<data_exchange::diagnostics::DiagnosticOutput>,
 
     #[cfg(debug_assertions)]
     pub dev_events_in_debug_format: String,
@@ -80,7 +100,14 @@ pub fn parse_and_render(input: &[u8], opts: &[u8]) -> Result<Vec<u8>, String> {
         }
     };
 
-    let all_events: Result<Vec<_>, _> = rotext::parse(input).collect();
+    let diagnostics = RefCell::new(vec![]);
+    let diagnostic_sink = |diagnostic| {
+        diagnostics
+            .borrow_mut()
+            .push(convert_diagnostic(diagnostic))
+    };
+    let all_events: Result<Vec<_>, _> =
+        rotext::parse_with_diagnostics(input, &diagnostic_sink).collect();
     let all_events = match all_events {
         Ok(all_events) => all_events,
         Err(error) => return Err(format!("ParseError/{}", error.name())),
@@ -90,6 +117,8 @@ pub fn parse_and_render(input: &[u8], opts: &[u8]) -> Result<Vec<u8>, String> {
         restrictions: rotext::CompileRestrictions {
             max_call_depth_in_document: 100,
         },
+        block_extension_map: &block_extension_map,
+        inline_extension_map: &inline_extension_map,
     };
     let compiled = rotext::compile(input, &all_events, &compile_opts);
     let compiled = match compiled {
@@ -101,6 +130,14 @@ pub fn parse_and_render(input: &[u8], opts: &[u8]) -> Result<Vec<u8>, String> {
         tag_name_map: &tag_name_map,
         block_extension_map: &block_extension_map,
         inline_extension_map: &inline_extension_map,
+        template_provider: None,
+        compile_options: &compile_opts,
+        code_block_rendering: if opts.should_render_code_blocks_as_pre_code {
+            rotext::CodeBlockRendering::PreCode
+        } else {
+            rotext::CodeBlockRendering::CustomElement
+        },
+        footnote_id_prefix: b"",
         should_include_block_ids: opts.should_include_block_ids,
     };
     let html = rotext::execute(input, &all_events, &compiled, &execute_opts);
@@ -110,11 +147,22 @@ pub fn parse_and_render(input: &[u8], opts: &[u8]) -> Result<Vec<u8>, String> {
     };
 
     let block_id_to_lines_map = create_block_id_to_lines_map(&all_events);
+    let block_id_to_positions_map = create_block_id_to_positions_map(&all_events);
+    let outline = opts
+        .should_include_outline
+        .then(|| create_outline(input, &all_events));
+    let statistics = opts
+        .should_include_statistics
+        .then(|| create_statistics(input, &all_events));
 
     #[allow(unused_mut)]
     let mut output = ParseAndRenderOutput {
         html,
         block_id_to_lines_map,
+        block_id_to_positions_map,
+        outline,
+        statistics,
+        diagnostics: diagnostics.into_inner(),
         ..Default::default()
     };
 
@@ -47,6 +47,10 @@ export const BLOCK_EXTENSION_LIST: Extension[] = [
     },
   },
   { Alias: { name: "折叠", to: "Collapse" } },
+  { FootnoteList: { name: "Footnotes" } },
+  { Alias: { name: "旁注内容", to: "Footnotes" } },
+  { TableOfContents: { name: "TableOfContents" } },
+  { Alias: { name: "目录", to: "TableOfContents" } },
   ...([
     ["Note", "注"],
     ["Tip", "提示"],
@@ -109,6 +113,8 @@ export const INLINE_EXTENSION_LIST: Extension[] = [
     },
   },
   { Alias: { name: "刮开", to: "ScratchOff" } },
+  { FootnoteReference: { name: "Footnote" } },
+  { Alias: { name: "旁注", to: "Footnote" } },
 ];
 
 export const PROSE_CLASS = "tuan-prose";